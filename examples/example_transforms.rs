@@ -23,15 +23,13 @@ fn main() -> Result<()> {
         let x = 100.0 + (i % 3) as f32 * 200.0;
         let y = 50.0 + (i / 3) as f32 * 200.0;
         
-        // Note: Transform would be applied at layer level in real implementation
-        // For now, we'll draw shapes at different positions to simulate rotation
-        
-        // Draw rotated rectangle (simulated)
+        // Rotated around the rectangle's center
         layer.draw_rect()
             .position(x, y)
             .size(100.0, 80.0)
             .fill(Color::Hsla(Hsla::new(*angle, 0.8, 0.6, 0.8)?))
             .stroke(Color::named(NamedColor::DarkBlue), 2.0)
+            .rotate(*angle)
             .draw()?;
     }
     
@@ -129,12 +127,14 @@ fn main() -> Result<()> {
         .stroke(Color::named(NamedColor::Teal), 2.0)
         .draw()?;
     
-    // Flipped (simulated by drawing mirrored)
+    // Flipped horizontally and skewed so the flip is visible
     layer.draw_rect()
         .position(300.0, 950.0)
         .size(100.0, 80.0)
         .fill(Color::hex("#16A085")?)
         .stroke(Color::named(NamedColor::Teal), 2.0)
+        .skew(20.0, 0.0)
+        .transform(clove2d::transform::Transform::new().flip_horizontal())
         .draw()?;
     
     // Section 6: Flip Vertical
@@ -145,18 +145,20 @@ fn main() -> Result<()> {
         .stroke(Color::named(NamedColor::DarkOrange), 2.0)
         .draw()?;
     
-    // Flipped vertically (simulated)
+    // Flipped vertically and skewed so the flip is visible
     layer.draw_rect()
         .position(700.0, 950.0)
         .size(100.0, 80.0)
         .fill(Color::hex("#D35400")?)
         .stroke(Color::named(NamedColor::DarkOrange), 2.0)
+        .skew(0.0, 20.0)
+        .transform(clove2d::transform::Transform::new().flip_vertical())
         .draw()?;
     
     // Section 7: Complex Transformations
     println!("Drawing Complex Transformations...");
     
-    // Star with rotation simulation
+    // Stars orbiting a point, each turned to face outward
     for i in 0..8 {
         let angle = i as f32 * 45.0;
         let radius = 120.0;
@@ -164,6 +166,7 @@ fn main() -> Result<()> {
         let y = 300.0 + radius * (angle.to_radians().sin());
         
         StarBuilder::new(&mut layer, Point::new(x, y), 30.0, 15.0, 5)
+            .rotate(angle + 90.0)
             .fill(Color::Hsla(Hsla::new(angle, 1.0, 0.5, 0.8)?))
             .stroke(Color::named(NamedColor::Black), 1.0)
             .draw()?;
@@ -1,15 +1,19 @@
 pub mod builder;
 pub mod state;
+pub mod project;
+pub mod openraster;
 
 pub use builder::CanvasBuilder;
 pub use state::CanvasState;
+pub use project::PROJECT_VERSION;
 
 use crate::backend::{RenderBackend, TinySkiaBackend};
-use crate::color::Color;
+use crate::color::{Color, Rgba};
 use crate::error::{Result, CloveError};
 use crate::image::formats::ImageFormat;
 use crate::text::font_manager::FontManager;
-use crate::layer::{Layer, LayerManager};
+use crate::history::History;
+use crate::layer::{Adjustment, Layer, LayerManager};
 
 /// Main canvas struct
 pub struct Canvas {
@@ -17,6 +21,7 @@ pub struct Canvas {
     state_stack: Vec<CanvasState>,
     font_manager: Option<FontManager>,
     layer_manager: LayerManager,
+    background: Option<Rgba>,
     width: u32,
     height: u32,
 }
@@ -46,10 +51,16 @@ impl Canvas {
     
     /// Clear canvas with color
     pub fn clear(&mut self, color: Color) -> &mut Self {
+        self.background = Some(color.to_rgba());
         self.backend.clear(color);
         self
     }
     
+    /// Get background color set by the builder or the last `clear`
+    pub fn background(&self) -> Option<Rgba> {
+        self.background
+    }
+    
     /// Save current state
     pub fn save_state(&mut self) -> &mut Self {
         let state = CanvasState::from_canvas(self);
@@ -113,44 +124,22 @@ impl Canvas {
     
     /// Merge all layers with backend pixmap
     fn merge_layers(&mut self) -> Result<()> {
-        use tiny_skia::{Pixmap, Transform};
+        let composite = self.composite()?;
+        *self.backend.get_pixmap_mut() = composite;
+        Ok(())
+    }
+    
+    /// Composite all layers over the backend pixmap without modifying it
+    pub(crate) fn composite(&self) -> Result<tiny_skia::Pixmap> {
+        use tiny_skia::Transform;
         
         // Get merged layers image
         let merged_image = self.layer_manager.merge_all()?;
         
         // Convert merged RgbaImage to Pixmap
-        let merged_pixmap = {
-            let width = merged_image.width();
-            let height = merged_image.height();
-            let mut pixmap = Pixmap::new(width, height)
-                .ok_or_else(|| CloveError::InvalidDimensions { width, height })?;
-            
-            // Copy image data to pixmap (convert from straight alpha to premultiplied)
-            let img_data = merged_image.as_raw();
-            let pixmap_data = pixmap.data_mut();
-            
-            for (i, chunk) in img_data.chunks_exact(4).enumerate() {
-                let r = chunk[0];
-                let g = chunk[1];
-                let b = chunk[2];
-                let a = chunk[3];
-                
-                let idx = i * 4;
-                if idx + 3 < pixmap_data.len() {
-                    // Premultiply alpha
-                    let alpha_f = a as f32 / 255.0;
-                    pixmap_data[idx] = (r as f32 * alpha_f) as u8;
-                    pixmap_data[idx + 1] = (g as f32 * alpha_f) as u8;
-                    pixmap_data[idx + 2] = (b as f32 * alpha_f) as u8;
-                    pixmap_data[idx + 3] = a;
-                }
-            }
-            
-            pixmap
-        };
+        let merged_pixmap = crate::layer::image_to_pixmap(&merged_image)?;
         
-        // Get backend pixmap
-        let backend_pixmap = self.backend.get_pixmap_mut();
+        let mut composite = self.backend.get_pixmap().clone();
         
         // Use draw_pixmap to composite
         let pixmap_paint = tiny_skia::PixmapPaint {
@@ -159,7 +148,7 @@ impl Canvas {
             blend_mode: tiny_skia::BlendMode::SourceOver,
         };
         
-        backend_pixmap.draw_pixmap(
+        composite.draw_pixmap(
             0, 0,
             merged_pixmap.as_ref(),
             &pixmap_paint,
@@ -167,7 +156,7 @@ impl Canvas {
             None,
         );
         
-        Ok(())
+        Ok(composite)
     }
     
     /// Get backend reference (for advanced operations)
@@ -204,6 +193,75 @@ impl Canvas {
         Ok(layer)
     }
     
+    /// Get layer manager reference
+    pub fn layer_manager(&self) -> &LayerManager {
+        &self.layer_manager
+    }
+    
+    /// Get layer manager mutable reference
+    pub fn layer_manager_mut(&mut self) -> &mut LayerManager {
+        &mut self.layer_manager
+    }
+    
+    /// Create an adjustment layer that filters everything beneath it at merge time
+    pub fn create_adjustment_layer(&mut self, name: &str, adjustment: impl Into<Adjustment>) -> Result<&mut Layer> {
+        let id = self.layer_manager.create_adjustment(name, adjustment.into());
+        let layer = self.layer_manager.get(id)
+            .ok_or_else(|| CloveError::LayerNotFound(name.to_string()))?;
+        Ok(layer)
+    }
+
+    /// Find the topmost visible layer with a pixel at least `alpha_threshold` opaque at a point
+    pub fn layer_at(&self, x: f32, y: f32, alpha_threshold: u8) -> Option<&Layer> {
+        let id = self.layer_manager.hit_test(x, y, alpha_threshold)?;
+        self.layer_manager.layers().iter().find(|l| l.id == id)
+    }
+
+    /// Record layer draws, filters, property changes and creation for undo/redo
+    ///
+    /// Keeps at most `max_depth` steps using at most `max_memory` bytes of deltas.
+    /// Drawing on the canvas itself and writing `Layer` fields directly is not recorded.
+    pub fn enable_history(&mut self, max_depth: usize, max_memory: usize) -> &mut Self {
+        self.layer_manager.enable_history(History::new(max_depth, max_memory));
+        self
+    }
+
+    /// Undo the last recorded step, returns false if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        self.layer_manager.undo()
+    }
+
+    /// Redo the last undone step, returns false if there was nothing to redo
+    pub fn redo(&mut self) -> bool {
+        self.layer_manager.redo()
+    }
+
+    /// Check if there is a step to undo
+    pub fn can_undo(&self) -> bool {
+        self.layer_manager.history().is_some_and(|h| h.can_undo())
+    }
+
+    /// Check if there is a step to redo
+    pub fn can_redo(&self) -> bool {
+        self.layer_manager.history().is_some_and(|h| h.can_redo())
+    }
+
+    /// Group following edits into one undo step until `end_history_group`
+    pub fn begin_history_group(&mut self, label: &str) -> &mut Self {
+        if let Some(mut history) = self.layer_manager.history() {
+            history.begin_group(label);
+        }
+        self
+    }
+
+    /// Close a group started with `begin_history_group`
+    pub fn end_history_group(&mut self) -> &mut Self {
+        if let Some(mut history) = self.layer_manager.history() {
+            history.end_group();
+        }
+        self
+    }
+
     pub(crate) fn new_internal(
         backend: TinySkiaBackend,
         font_manager: Option<FontManager>,
@@ -215,6 +273,7 @@ impl Canvas {
             state_stack: Vec::new(),
             font_manager,
             layer_manager: LayerManager::new(width, height),
+            background: None,
             width,
             height,
         }
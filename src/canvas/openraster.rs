@@ -0,0 +1,338 @@
+use crate::canvas::Canvas;
+use crate::error::{Result, CloveError};
+use crate::layer::{BlendMode, Layer, LayerId};
+use image::RgbaImage;
+use std::io::{Read, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// MIME type stored as the first, uncompressed entry of every ORA archive
+const ORA_MIMETYPE: &str = "image/openraster";
+
+/// Largest edge of the thumbnail required by the OpenRaster spec
+const THUMBNAIL_SIZE: u32 = 256;
+
+impl Canvas {
+    /// Export layers as an OpenRaster (.ora) archive
+    ///
+    /// The canvas background becomes a bottom "Background" layer. Adjustment
+    /// layers and masks have no OpenRaster equivalent, so they only appear
+    /// baked into `mergedimage.png`.
+    pub fn save_ora(&self, path: &str) -> Result<()> {
+        let buffer = self.to_ora_bytes()?;
+        std::fs::write(path, buffer)?;
+        Ok(())
+    }
+
+    /// Import an OpenRaster (.ora) archive as a canvas with layers
+    pub fn open_ora(path: &str) -> Result<Canvas> {
+        let buffer = std::fs::read(path)?;
+        Self::from_ora_bytes(&buffer)
+    }
+
+    /// Serialize layers to OpenRaster archive bytes
+    pub fn to_ora_bytes(&self) -> Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        write_entry(&mut zip, "mimetype", ORA_MIMETYPE.as_bytes(), stored)?;
+
+        // stack.xml lists layers top to bottom
+        let mut entries = Vec::new();
+        for (index, layer) in self.layer_manager().layers().iter().enumerate() {
+            if layer.is_adjustment() {
+                continue;
+            }
+            let src = format!("data/layer{}.png", index);
+            write_entry(&mut zip, &src, &encode_png(&layer.content)?, stored)?;
+            entries.push(format!(
+                "    <layer name=\"{}\" src=\"{}\" x=\"{}\" y=\"{}\" opacity=\"{}\" visibility=\"{}\" composite-op=\"{}\"/>",
+                escape_xml(&layer.name),
+                src,
+                layer.x.round() as i32,
+                layer.y.round() as i32,
+                layer.opacity,
+                if layer.visible { "visible" } else { "hidden" },
+                composite_op(layer.blend_mode),
+            ));
+        }
+        if let Some(bg) = self.background() {
+            let background = RgbaImage::from_pixel(self.width(), self.height(), image::Rgba([bg.r, bg.g, bg.b, bg.a]));
+            write_entry(&mut zip, "data/background.png", &encode_png(&background)?, stored)?;
+            entries.insert(0, "    <layer name=\"Background\" src=\"data/background.png\" x=\"0\" y=\"0\"/>".to_string());
+        }
+        entries.reverse();
+
+        let stack = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<image version=\"0.0.5\" w=\"{}\" h=\"{}\">\n  <stack>\n{}\n  </stack>\n</image>\n",
+            self.width(),
+            self.height(),
+            entries.join("\n"),
+        );
+        write_entry(&mut zip, "stack.xml", stack.as_bytes(), deflated)?;
+
+        let merged = self.composite()?;
+        let merged = crate::layer::pixmap_to_image(&merged);
+        write_entry(&mut zip, "mergedimage.png", &encode_png(&merged)?, stored)?;
+        let thumbnail = if merged.width() > THUMBNAIL_SIZE || merged.height() > THUMBNAIL_SIZE {
+            let scale = THUMBNAIL_SIZE as f32 / merged.width().max(merged.height()) as f32;
+            image::imageops::thumbnail(
+                &merged,
+                ((merged.width() as f32 * scale).round() as u32).max(1),
+                ((merged.height() as f32 * scale).round() as u32).max(1),
+            )
+        } else {
+            merged
+        };
+        write_entry(&mut zip, "Thumbnails/thumbnail.png", &encode_png(&thumbnail)?, stored)?;
+
+        let cursor = zip.finish().map_err(|e| CloveError::OpenRasterError(e.to_string()))?;
+        Ok(cursor.into_inner())
+    }
+
+    /// Rebuild a canvas and its layer stack from OpenRaster archive bytes
+    ///
+    /// Nested stacks (layer groups) are flattened into the main stack, with
+    /// their offsets added to the layers inside them.
+    pub fn from_ora_bytes(data: &[u8]) -> Result<Canvas> {
+        let mut archive = ZipArchive::new(std::io::Cursor::new(data))
+            .map_err(|e| CloveError::OpenRasterError(e.to_string()))?;
+
+        let stack = String::from_utf8(read_entry(&mut archive, "stack.xml")?)
+            .map_err(|e| CloveError::OpenRasterError(e.to_string()))?;
+
+        let document = roxmltree::Document::parse(&stack)
+            .map_err(|e| CloveError::OpenRasterError(format!("stack.xml: {}", e)))?;
+        let image = document.root_element();
+        if !image.has_tag_name("image") {
+            return Err(CloveError::OpenRasterError("stack.xml has no <image> element".to_string()));
+        }
+        let dimension = |name: &str| -> Result<u32> {
+            image.attribute(name)
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| CloveError::OpenRasterError(format!("Missing or invalid image {}", name)))
+        };
+        let (width, height) = (dimension("w")?, dimension("h")?);
+
+        let mut canvas = Canvas::new(width, height)?;
+
+        // stack.xml lists layers top to bottom
+        let mut layers = Vec::new();
+        collect_layers(image, (0.0, 0.0), &mut layers);
+        for (node, (offset_x, offset_y)) in layers.into_iter().rev() {
+            let attr = |name: &str| node.attribute(name);
+
+            let src = attr("src")
+                .ok_or_else(|| CloveError::OpenRasterError("Layer without src".to_string()))?;
+            let content = image::load_from_memory(&read_entry(&mut archive, src)?)
+                .map_err(|e| CloveError::ImageLoadError(e.to_string()))?
+                .to_rgba8();
+
+            let mut layer = Layer::new(LayerId::new(), attr("name").unwrap_or(""), width, height);
+            layer.width = Some(content.width());
+            layer.height = Some(content.height());
+            layer.content = content;
+            layer.x = offset_x + position(node, "x");
+            layer.y = offset_y + position(node, "y");
+            layer.opacity = attr("opacity").and_then(|v| v.parse::<f32>().ok()).unwrap_or(1.0).clamp(0.0, 1.0);
+            layer.visible = attr("visibility") != Some("hidden");
+            layer.blend_mode = attr("composite-op").map(blend_mode_from_op).unwrap_or_default();
+
+            if let Some(fm) = canvas.font_manager() {
+                layer.set_font_manager(std::sync::Arc::new(std::sync::Mutex::new(fm.clone())));
+            }
+            canvas.layer_manager_mut().push(layer);
+        }
+
+        Ok(canvas)
+    }
+}
+
+/// OpenRaster composite-op for a blend mode
+///
+/// Modes outside the spec use the same `svg:` naming scheme; readers that
+/// don't know them fall back to `svg:src-over`.
+fn composite_op(mode: BlendMode) -> &'static str {
+    match mode {
+        BlendMode::Normal => "svg:src-over",
+        BlendMode::Multiply => "svg:multiply",
+        BlendMode::Screen => "svg:screen",
+        BlendMode::Overlay => "svg:overlay",
+        BlendMode::Darken => "svg:darken",
+        BlendMode::Lighten => "svg:lighten",
+        BlendMode::ColorDodge => "svg:color-dodge",
+        BlendMode::ColorBurn => "svg:color-burn",
+        BlendMode::HardLight => "svg:hard-light",
+        BlendMode::SoftLight => "svg:soft-light",
+        BlendMode::Difference => "svg:difference",
+        BlendMode::Exclusion => "svg:exclusion",
+        BlendMode::Hue => "svg:hue",
+        BlendMode::Saturation => "svg:saturation",
+        BlendMode::Color => "svg:color",
+        BlendMode::Luminosity => "svg:luminosity",
+        BlendMode::Plus => "svg:plus",
+        BlendMode::Modulate => "svg:modulate",
+        BlendMode::Clear => "svg:clear",
+        BlendMode::Source => "svg:src",
+        BlendMode::Destination => "svg:dst",
+        BlendMode::DestinationOver => "svg:dst-over",
+        BlendMode::SourceIn => "svg:src-in",
+        BlendMode::DestinationIn => "svg:dst-in",
+        BlendMode::SourceOut => "svg:src-out",
+        BlendMode::DestinationOut => "svg:dst-out",
+        BlendMode::SourceAtop => "svg:src-atop",
+        BlendMode::DestinationAtop => "svg:dst-atop",
+        BlendMode::Xor => "svg:xor",
+    }
+}
+
+/// Blend mode for an OpenRaster composite-op, defaulting to Normal
+fn blend_mode_from_op(op: &str) -> BlendMode {
+    BlendMode::ALL.iter()
+        .copied()
+        .find(|&mode| composite_op(mode) == op)
+        .unwrap_or_default()
+}
+
+/// Collect `<layer>` elements top to bottom with the summed offsets of the stacks around them
+fn collect_layers<'a, 'input>(
+    parent: roxmltree::Node<'a, 'input>,
+    offset: (f32, f32),
+    layers: &mut Vec<(roxmltree::Node<'a, 'input>, (f32, f32))>,
+) {
+    for child in parent.children().filter(|node| node.is_element()) {
+        match child.tag_name().name() {
+            "stack" => {
+                let offset = (offset.0 + position(child, "x"), offset.1 + position(child, "y"));
+                collect_layers(child, offset, layers);
+            }
+            "layer" => layers.push((child, offset)),
+            _ => {}
+        }
+    }
+}
+
+/// Position attribute, 0 when absent or invalid
+fn position(node: roxmltree::Node, name: &str) -> f32 {
+    node.attribute(name).and_then(|value| value.parse().ok()).unwrap_or(0.0)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    image.write_to(&mut buffer, image::ImageFormat::Png)
+        .map_err(|e| CloveError::ImageEncodeError(e.to_string()))?;
+    Ok(buffer.into_inner())
+}
+
+fn write_entry<W: Write + std::io::Seek>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    data: &[u8],
+    options: SimpleFileOptions,
+) -> Result<()> {
+    zip.start_file(name, options)
+        .map_err(|e| CloveError::OpenRasterError(e.to_string()))?;
+    zip.write_all(data)?;
+    Ok(())
+}
+
+fn read_entry<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Vec<u8>> {
+    let mut entry = archive.by_name(name)
+        .map_err(|e| CloveError::OpenRasterError(format!("{}: {}", name, e)))?;
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    #[test]
+    fn test_ora_round_trip() {
+        let mut canvas = Canvas::builder()
+            .size(8, 6)
+            .background(Color::rgba(0, 0, 255, 255))
+            .build()
+            .unwrap();
+        {
+            let layer = canvas.create_layer_with_size("Fish & <Chips>", 3, 2).unwrap();
+            layer.content.put_pixel(2, 1, image::Rgba([255, 0, 0, 255]));
+            layer.x = 4.0;
+            layer.y = 1.0;
+            layer.opacity = 0.5;
+            layer.visible = false;
+            layer.blend_mode = BlendMode::Multiply;
+        }
+
+        let restored = Canvas::from_ora_bytes(&canvas.to_ora_bytes().unwrap()).unwrap();
+        assert_eq!((restored.width(), restored.height()), (8, 6));
+
+        let layers = restored.layer_manager().layers();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].name, "Background");
+        assert_eq!(layers[0].content.get_pixel(7, 5).0, [0, 0, 255, 255]);
+        assert_eq!(layers[1].name, "Fish & <Chips>");
+        assert_eq!(layers[1].dimensions(), (3, 2));
+        assert_eq!(layers[1].content.get_pixel(2, 1).0, [255, 0, 0, 255]);
+        assert_eq!((layers[1].x, layers[1].y), (4.0, 1.0));
+        assert_eq!(layers[1].opacity, 0.5);
+        assert!(!layers[1].visible);
+        assert_eq!(layers[1].blend_mode, BlendMode::Multiply);
+    }
+
+    #[test]
+    fn test_mimetype_is_first_and_stored() {
+        let canvas = Canvas::new(2, 2).unwrap();
+        let bytes = canvas.to_ora_bytes().unwrap();
+        let mut archive = ZipArchive::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+        let entry = archive.by_index(0).unwrap();
+        assert_eq!(entry.name(), "mimetype");
+        assert_eq!(entry.compression(), CompressionMethod::Stored);
+    }
+
+    #[test]
+    fn test_unknown_composite_op_is_normal() {
+        assert_eq!(blend_mode_from_op("krita:dissolve"), BlendMode::Normal);
+        assert_eq!(blend_mode_from_op("svg:dst-out"), BlendMode::DestinationOut);
+    }
+
+    #[test]
+    fn test_reads_nested_stacks_and_character_references() {
+        let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        write_entry(&mut zip, "mimetype", ORA_MIMETYPE.as_bytes(), options).unwrap();
+        write_entry(&mut zip, "data/a.png", &encode_png(&RgbaImage::new(1, 1)).unwrap(), options).unwrap();
+        let stack = r#"<?xml version="1.0" encoding="UTF-8"?>
+<image w="10" h="10">
+  <stack>
+    <stack x="3" y="2">
+      <stack x="1">
+        <layer name="a &gt; b &#x263A; &#65;" src="data/a.png" x="2" y="1"/>
+      </stack>
+    </stack>
+    <layer name="x>y" src="data/a.png"/>
+  </stack>
+</image>"#;
+        write_entry(&mut zip, "stack.xml", stack.as_bytes(), options).unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+
+        let canvas = Canvas::from_ora_bytes(&bytes).unwrap();
+        let layers = canvas.layer_manager().layers();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].name, "x>y");
+        assert_eq!((layers[0].x, layers[0].y), (0.0, 0.0));
+        assert_eq!(layers[1].name, "a > b \u{263A} A");
+        assert_eq!((layers[1].x, layers[1].y), (6.0, 3.0));
+    }
+}
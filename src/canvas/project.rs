@@ -0,0 +1,547 @@
+use crate::canvas::Canvas;
+use crate::color::{Color, Rgba};
+use crate::error::{Result, CloveError};
+use crate::filter::Filter;
+use crate::layer::{Adjustment, BlendMode, Layer, LayerId};
+use crate::text::font_manager::FontManager;
+
+/// Magic bytes at the start of every project file
+const MAGIC: &[u8; 8] = b"CLOVE2D\0";
+
+/// Project format version written by this build
+///
+/// Readers accept any file with a version up to this one. Unknown chunks
+/// are skipped, unknown blend modes fall back to normal and unknown adjustment
+/// filters are dropped, so new optional data can be added without bumping it.
+pub const PROJECT_VERSION: u16 = 1;
+
+impl Canvas {
+    /// Save canvas size, background, fonts and layers to a project file
+    pub fn save_project(&self, path: &str) -> Result<()> {
+        let buffer = self.to_project_bytes()?;
+        std::fs::write(path, buffer)?;
+        Ok(())
+    }
+
+    /// Open a project file written by `save_project`
+    pub fn open_project(path: &str) -> Result<Canvas> {
+        let buffer = std::fs::read(path)?;
+        Self::from_project_bytes(&buffer)
+    }
+
+    /// Open a project file, also returning warnings for data that had to be dropped
+    pub fn open_project_with_warnings(path: &str) -> Result<(Canvas, Vec<String>)> {
+        let buffer = std::fs::read(path)?;
+        Self::from_project_bytes_with_warnings(&buffer)
+    }
+
+    /// Serialize canvas and layers to project bytes
+    pub fn to_project_bytes(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&PROJECT_VERSION.to_le_bytes());
+
+        let mut canvas_chunk = Vec::new();
+        put_u32(&mut canvas_chunk, self.width());
+        put_u32(&mut canvas_chunk, self.height());
+        put_chunk(&mut out, b"CANV", &canvas_chunk);
+
+        if let Some(bg) = self.background() {
+            put_chunk(&mut out, b"BKGD", &[bg.r, bg.g, bg.b, bg.a]);
+        }
+
+        if let Some(font_manager) = self.font_manager() {
+            let mut fonts: Vec<_> = font_manager.font_paths().collect();
+            fonts.sort();
+            for (name, path) in fonts {
+                let mut font_chunk = Vec::new();
+                put_str(&mut font_chunk, name);
+                put_str(&mut font_chunk, path);
+                put_chunk(&mut out, b"FONT", &font_chunk);
+            }
+            if let Some(default) = font_manager.default_family() {
+                let mut default_chunk = Vec::new();
+                put_str(&mut default_chunk, default);
+                put_chunk(&mut out, b"DFNT", &default_chunk);
+            }
+        }
+
+        for layer in self.layer_manager().layers() {
+            put_chunk(&mut out, b"LAYR", &encode_layer(layer)?);
+        }
+
+        Ok(out)
+    }
+
+    /// Rebuild a canvas and its layer stack from project bytes
+    pub fn from_project_bytes(data: &[u8]) -> Result<Canvas> {
+        Self::from_project_bytes_with_warnings(data).map(|(canvas, _)| canvas)
+    }
+
+    /// Rebuild a canvas from project bytes, also returning warnings for data that had to be dropped
+    ///
+    /// Values from newer writers and fonts missing on this machine are skipped with a warning.
+    pub fn from_project_bytes_with_warnings(data: &[u8]) -> Result<(Canvas, Vec<String>)> {
+        let mut reader = Reader::new(data);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(CloveError::InvalidProject("Not a Clove2d project file".to_string()));
+        }
+        let version = reader.u16()?;
+        if version > PROJECT_VERSION {
+            return Err(CloveError::UnsupportedProjectVersion {
+                found: version,
+                supported: PROJECT_VERSION,
+            });
+        }
+
+        let mut size = None;
+        let mut background = None;
+        let mut fonts = Vec::new();
+        let mut default_font = None;
+        let mut layers = Vec::new();
+
+        while let Some((tag, payload)) = reader.chunk()? {
+            let mut chunk = Reader::new(payload);
+            match &tag {
+                b"CANV" => size = Some((chunk.u32()?, chunk.u32()?)),
+                b"BKGD" => {
+                    let c = chunk.take(4)?;
+                    background = Some(Rgba::new(c[0], c[1], c[2], c[3]));
+                }
+                b"FONT" => fonts.push((chunk.string()?, chunk.string()?)),
+                b"DFNT" => default_font = Some(chunk.string()?),
+                b"LAYR" => layers.push(payload),
+                // Chunks added by newer writers are skipped
+                _ => {}
+            }
+        }
+
+        let (width, height) = size
+            .ok_or_else(|| CloveError::InvalidProject("Missing canvas size".to_string()))?;
+
+        let mut warnings = Vec::new();
+        let mut builder = Canvas::builder().size(width, height);
+        if let Some(bg) = background {
+            builder = builder.background(Color::Rgba(bg));
+        }
+        if !fonts.is_empty() {
+            let mut font_manager = FontManager::new();
+            // A font missing on this machine shouldn't make the whole project unreadable
+            for (name, path) in &fonts {
+                if font_manager.load(name, path).is_err() {
+                    warnings.push(format!("Font '{}' could not be loaded from '{}', skipped", name, path));
+                }
+            }
+            if let Some(default) = &default_font {
+                if font_manager.set_default(default).is_err() {
+                    warnings.push(format!("Default font '{}' is not loaded, ignored", default));
+                }
+            }
+            builder = builder.font_manager(font_manager);
+        }
+        let mut canvas = builder.build()?;
+
+        for payload in layers {
+            let mut layer = decode_layer(payload, &mut warnings)?;
+            if let Some(fm) = canvas.font_manager() {
+                layer.set_font_manager(std::sync::Arc::new(std::sync::Mutex::new(fm.clone())));
+            }
+            canvas.layer_manager_mut().push(layer);
+        }
+
+        Ok((canvas, warnings))
+    }
+}
+
+/// Encode one layer as a sequence of tagged fields
+fn encode_layer(layer: &Layer) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+
+    let mut name = Vec::new();
+    put_str(&mut name, &layer.name);
+    put_chunk(&mut out, b"NAME", &name);
+
+    let (base_width, base_height) = layer.base_dimensions();
+    let mut size = Vec::new();
+    put_u32(&mut size, base_width);
+    put_u32(&mut size, base_height);
+    // Zero marks an unset explicit width/height
+    put_u32(&mut size, layer.width.unwrap_or(0));
+    put_u32(&mut size, layer.height.unwrap_or(0));
+    put_chunk(&mut out, b"SIZE", &size);
+
+    let mut offset = Vec::new();
+    put_f32(&mut offset, layer.x);
+    put_f32(&mut offset, layer.y);
+    put_chunk(&mut out, b"OFFS", &offset);
+
+    put_chunk(&mut out, b"OPAC", &layer.opacity.to_le_bytes());
+
+    let mut blend = Vec::new();
+    put_str(&mut blend, layer.blend_mode.name());
+    put_chunk(&mut out, b"BLND", &blend);
+
+    put_chunk(&mut out, b"VISB", &[layer.visible as u8]);
+    put_chunk(&mut out, b"PIXL", &encode_png(&layer.content)?);
+
+    if let Some(adjustment) = &layer.adjustment {
+        let mut filters = Vec::new();
+        put_u32(&mut filters, adjustment.filters.len() as u32);
+        for filter in &adjustment.filters {
+            let (code, value) = filter_code(filter);
+            filters.push(code);
+            put_f32(&mut filters, value);
+        }
+        put_chunk(&mut out, b"ADJM", &filters);
+    }
+
+    if let Some(mask) = &layer.mask {
+        put_chunk(&mut out, b"MASK", &encode_png(mask)?);
+    }
+
+    Ok(out)
+}
+
+/// Decode a layer written by `encode_layer`, noting values from newer writers in `warnings`
+fn decode_layer(payload: &[u8], warnings: &mut Vec<String>) -> Result<Layer> {
+    let mut reader = Reader::new(payload);
+    let mut name = String::new();
+    let mut size = None;
+    let mut pixels = None;
+    let mut layer_fields: Vec<([u8; 4], &[u8])> = Vec::new();
+
+    while let Some((tag, data)) = reader.chunk()? {
+        let mut field = Reader::new(data);
+        match &tag {
+            b"NAME" => name = field.string()?,
+            b"SIZE" => size = Some((field.u32()?, field.u32()?, field.u32()?, field.u32()?)),
+            b"PIXL" => pixels = Some(data),
+            _ => layer_fields.push((tag, data)),
+        }
+    }
+
+    let (base_width, base_height, width, height) = size
+        .ok_or_else(|| CloveError::InvalidProject(format!("Layer '{}' has no size", name)))?;
+    let pixels = pixels
+        .ok_or_else(|| CloveError::InvalidProject(format!("Layer '{}' has no pixels", name)))?;
+
+    // Content is scaled to the explicit size only when both dimensions are set
+    let expected = if width > 0 && height > 0 { (width, height) } else { (base_width, base_height) };
+    let content = decode_image(pixels)?.to_rgba8();
+    if content.dimensions() != expected {
+        return Err(CloveError::InvalidDimensions { width: content.width(), height: content.height() });
+    }
+
+    let mut layer = Layer::with_content(LayerId::new(), &name, base_width, base_height, content);
+    layer.width = (width > 0).then_some(width);
+    layer.height = (height > 0).then_some(height);
+
+    for (tag, data) in layer_fields {
+        let mut field = Reader::new(data);
+        match &tag {
+            b"OFFS" => {
+                layer.x = field.f32()?;
+                layer.y = field.f32()?;
+            }
+            b"OPAC" => layer.opacity = field.f32()?,
+            b"BLND" => {
+                let mode = field.string()?;
+                layer.blend_mode = BlendMode::from_name(&mode).unwrap_or_else(|| {
+                    warnings.push(format!("Layer '{}': unknown blend mode '{}', using normal", layer.name, mode));
+                    BlendMode::Normal
+                });
+            }
+            b"VISB" => layer.visible = field.u8()? != 0,
+            b"ADJM" => {
+                let count = field.u32()?;
+                let mut filters = Vec::new();
+                for _ in 0..count {
+                    // Every filter is a code and one value, so unknown ones can be skipped
+                    let (code, value) = (field.u8()?, field.f32()?);
+                    match filter_from_code(code, value) {
+                        Some(filter) => filters.push(filter),
+                        None => warnings.push(format!("Layer '{}': unknown filter code {}, skipped", layer.name, code)),
+                    }
+                }
+                layer.adjustment = Some(Adjustment::from(filters));
+            }
+            b"MASK" => layer.mask = Some(decode_image(data)?.to_luma8()),
+            _ => {}
+        }
+    }
+
+    Ok(layer)
+}
+
+/// Stable numeric code and parameter for a filter
+fn filter_code(filter: &Filter) -> (u8, f32) {
+    match *filter {
+        Filter::Blur(radius) => (0, radius),
+        Filter::Sharpen(intensity) => (1, intensity),
+        Filter::Grayscale => (2, 0.0),
+        Filter::Sepia => (3, 0.0),
+        Filter::Invert => (4, 0.0),
+        Filter::Brightness(amount) => (5, amount),
+        Filter::Contrast(amount) => (6, amount),
+        Filter::Saturation(amount) => (7, amount),
+        Filter::HueRotate(degrees) => (8, degrees),
+    }
+}
+
+/// Rebuild a filter from its numeric code and parameter, `None` for codes this build doesn't know
+fn filter_from_code(code: u8, value: f32) -> Option<Filter> {
+    Some(match code {
+        0 => Filter::Blur(value),
+        1 => Filter::Sharpen(value),
+        2 => Filter::Grayscale,
+        3 => Filter::Sepia,
+        4 => Filter::Invert,
+        5 => Filter::Brightness(value),
+        6 => Filter::Contrast(value),
+        7 => Filter::Saturation(value),
+        8 => Filter::HueRotate(value),
+        _ => return None,
+    })
+}
+
+fn encode_png<P, C>(image: &image::ImageBuffer<P, C>) -> Result<Vec<u8>>
+where
+    P: image::PixelWithColorType,
+    [P::Subpixel]: image::EncodableLayout,
+    C: std::ops::Deref<Target = [P::Subpixel]>,
+{
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    image.write_to(&mut buffer, image::ImageFormat::Png)
+        .map_err(|e| CloveError::ImageEncodeError(e.to_string()))?;
+    Ok(buffer.into_inner())
+}
+
+fn decode_image(data: &[u8]) -> Result<image::DynamicImage> {
+    image::load_from_memory_with_format(data, image::ImageFormat::Png)
+        .map_err(|e| CloveError::InvalidProject(e.to_string()))
+}
+
+fn put_chunk(out: &mut Vec<u8>, tag: &[u8; 4], payload: &[u8]) {
+    out.extend_from_slice(tag);
+    put_u32(out, payload.len() as u32);
+    out.extend_from_slice(payload);
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_f32(out: &mut Vec<u8>, value: f32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_str(out: &mut Vec<u8>, value: &str) {
+    put_u32(out, value.len() as u32);
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Little-endian cursor over project bytes
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| CloveError::InvalidProject("Unexpected end of data".to_string()))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|e| CloveError::InvalidProject(e.to_string()))
+    }
+
+    /// Read the next tagged chunk, or `None` at end of data
+    fn chunk(&mut self) -> Result<Option<([u8; 4], &'a [u8])>> {
+        if self.pos >= self.data.len() {
+            return Ok(None);
+        }
+        let tag = self.take(4)?;
+        let len = self.u32()? as usize;
+        Ok(Some(([tag[0], tag[1], tag[2], tag[3]], self.take(len)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_round_trip() {
+        let mut canvas = Canvas::builder()
+            .size(4, 3)
+            .background(Color::rgba(10, 20, 30, 255))
+            .build()
+            .unwrap();
+        {
+            let layer = canvas.create_layer_with_size("art", 2, 2).unwrap();
+            layer.content.put_pixel(1, 1, image::Rgba([1, 2, 3, 200]));
+            layer.x = 1.5;
+            layer.opacity = 0.25;
+            layer.visible = false;
+            layer.blend_mode = BlendMode::DestinationOut;
+        }
+        canvas.create_adjustment_layer("fx", Adjustment::new(Filter::Blur(2.0)).then(Filter::Sepia))
+            .unwrap()
+            .mask(image::GrayImage::from_pixel(4, 3, image::Luma([77])));
+
+        let bytes = canvas.to_project_bytes().unwrap();
+        let restored = Canvas::from_project_bytes(&bytes).unwrap();
+
+        assert_eq!((restored.width(), restored.height()), (4, 3));
+        assert_eq!(restored.background(), Some(Rgba::new(10, 20, 30, 255)));
+
+        let layers = restored.layer_manager().layers();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].name, "art");
+        assert_eq!(layers[0].dimensions(), (2, 2));
+        assert_eq!(layers[0].content.get_pixel(1, 1).0, [1, 2, 3, 200]);
+        assert_eq!(layers[0].x, 1.5);
+        assert_eq!(layers[0].opacity, 0.25);
+        assert!(!layers[0].visible);
+        assert_eq!(layers[0].blend_mode, BlendMode::DestinationOut);
+        assert_eq!(layers[1].adjustment, Some(Adjustment::new(Filter::Blur(2.0)).then(Filter::Sepia)));
+        assert_eq!(layers[1].mask.as_ref().unwrap().get_pixel(3, 2)[0], 77);
+    }
+
+    #[test]
+    fn test_rejects_newer_version() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&(PROJECT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Canvas::from_project_bytes(&bytes),
+            Err(CloveError::UnsupportedProjectVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_skips_unknown_chunks() {
+        let canvas = Canvas::new(2, 2).unwrap();
+        let mut bytes = canvas.to_project_bytes().unwrap();
+        put_chunk(&mut bytes, b"ZZZZ", &[1, 2, 3]);
+        assert!(Canvas::from_project_bytes(&bytes).is_ok());
+    }
+
+    /// Project bytes holding one layer built from raw fields
+    fn project_with_layer(size: [u32; 4], pixels: (u32, u32), fields: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&PROJECT_VERSION.to_le_bytes());
+        let mut canvas_chunk = Vec::new();
+        put_u32(&mut canvas_chunk, 2);
+        put_u32(&mut canvas_chunk, 2);
+        put_chunk(&mut bytes, b"CANV", &canvas_chunk);
+
+        let mut layer = Vec::new();
+        let mut name = Vec::new();
+        put_str(&mut name, "newer");
+        put_chunk(&mut layer, b"NAME", &name);
+        let mut size_chunk = Vec::new();
+        for value in size {
+            put_u32(&mut size_chunk, value);
+        }
+        put_chunk(&mut layer, b"SIZE", &size_chunk);
+        put_chunk(&mut layer, b"PIXL", &encode_png(&image::RgbaImage::new(pixels.0, pixels.1)).unwrap());
+        for (tag, payload) in fields {
+            put_chunk(&mut layer, tag, payload);
+        }
+        put_chunk(&mut bytes, b"LAYR", &layer);
+        bytes
+    }
+
+    #[test]
+    fn test_unknown_blend_mode_falls_back_to_normal() {
+        let mut blend = Vec::new();
+        put_str(&mut blend, "future-blend");
+        let bytes = project_with_layer([2, 2, 0, 0], (2, 2), &[(b"BLND", blend)]);
+
+        let (canvas, warnings) = Canvas::from_project_bytes_with_warnings(&bytes).unwrap();
+        assert_eq!(canvas.layer_manager().layers()[0].blend_mode, BlendMode::Normal);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("future-blend"));
+    }
+
+    #[test]
+    fn test_unknown_filter_is_skipped() {
+        let mut filters = Vec::new();
+        put_u32(&mut filters, 3);
+        filters.push(0);
+        put_f32(&mut filters, 2.0);
+        filters.push(250);
+        put_f32(&mut filters, 1.0);
+        filters.push(3);
+        put_f32(&mut filters, 0.0);
+        let bytes = project_with_layer([2, 2, 0, 0], (2, 2), &[(b"ADJM", filters)]);
+
+        let (canvas, warnings) = Canvas::from_project_bytes_with_warnings(&bytes).unwrap();
+        let adjustment = canvas.layer_manager().layers()[0].adjustment.clone();
+        assert_eq!(adjustment, Some(Adjustment::new(Filter::Blur(2.0)).then(Filter::Sepia)));
+        assert_eq!(warnings, vec!["Layer 'newer': unknown filter code 250, skipped".to_string()]);
+        assert!(Canvas::from_project_bytes(&bytes).is_ok());
+    }
+
+    #[test]
+    fn test_missing_font_is_a_warning() {
+        let mut bytes = project_with_layer([2, 2, 0, 0], (2, 2), &[]);
+        let mut font = Vec::new();
+        put_str(&mut font, "Gone");
+        put_str(&mut font, "/nonexistent/gone.ttf");
+        put_chunk(&mut bytes, b"FONT", &font);
+
+        let (canvas, warnings) = Canvas::from_project_bytes_with_warnings(&bytes).unwrap();
+        assert_eq!(canvas.layer_manager().layers().len(), 1);
+        assert_eq!(warnings, vec!["Font 'Gone' could not be loaded from '/nonexistent/gone.ttf', skipped".to_string()]);
+    }
+
+    #[test]
+    fn test_pixels_must_match_layer_size() {
+        let wrong_size = project_with_layer([2, 2, 0, 0], (3, 3), &[]);
+        assert!(matches!(
+            Canvas::from_project_bytes(&wrong_size),
+            Err(CloveError::InvalidDimensions { width: 3, height: 3 })
+        ));
+
+        // A huge declared base size is rejected before anything that size is allocated
+        let huge_base = project_with_layer([100_000, 100_000, 0, 0], (2, 2), &[]);
+        assert!(matches!(
+            Canvas::from_project_bytes(&huge_base),
+            Err(CloveError::InvalidDimensions { .. })
+        ));
+
+        let scaled = project_with_layer([100_000, 100_000, 2, 2], (2, 2), &[]);
+        let canvas = Canvas::from_project_bytes(&scaled).unwrap();
+        assert_eq!(canvas.layer_manager().layers()[0].dimensions(), (2, 2));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::color::interpolation::Ramp;
+use crate::color::{Color, GradientStop, Hsla, InterpolationSpace, Rgba};
+
+/// Lightness, saturation and hue are adjusted in HSL, as CSS preprocessors do; amounts are 0.0-1.0
+impl Rgba {
+    /// Raise lightness by `amount`
+    pub fn lighten(&self, amount: f32) -> Rgba {
+        self.adjust_hsl(|hsla| hsla.l = (hsla.l + amount).clamp(0.0, 1.0))
+    }
+
+    /// Lower lightness by `amount`
+    pub fn darken(&self, amount: f32) -> Rgba {
+        self.lighten(-amount)
+    }
+
+    /// Raise saturation by `amount`
+    pub fn saturate(&self, amount: f32) -> Rgba {
+        self.adjust_hsl(|hsla| hsla.s = (hsla.s + amount).clamp(0.0, 1.0))
+    }
+
+    /// Lower saturation by `amount`
+    pub fn desaturate(&self, amount: f32) -> Rgba {
+        self.saturate(-amount)
+    }
+
+    /// Turn the hue by `degrees`
+    pub fn rotate_hue(&self, degrees: f32) -> Rgba {
+        self.adjust_hsl(|hsla| hsla.h = (hsla.h + degrees).rem_euclid(360.0))
+    }
+
+    /// Color opposite on the hue wheel
+    pub fn complement(&self) -> Rgba {
+        self.rotate_hue(180.0)
+    }
+
+    /// Same color with alpha set to `alpha` (0.0-1.0)
+    pub fn with_alpha(&self, alpha: f32) -> Rgba {
+        let (r, g, b, _) = self.as_f32();
+        Rgba::from_f32(r, g, b, alpha.clamp(0.0, 1.0))
+    }
+
+    /// Raise alpha by `amount`, or lower it when negative
+    pub fn adjust_alpha(&self, amount: f32) -> Rgba {
+        self.with_alpha(self.a as f32 / 255.0 + amount)
+    }
+
+    /// Blend towards `other`, from this color at `ratio` 0.0 to `other` at 1.0, interpolating in `space`
+    pub fn mix(&self, other: Rgba, ratio: f32, space: InterpolationSpace) -> Rgba {
+        let stops = [GradientStop::new(Color::Rgba(*self), 0.0), GradientStop::new(Color::Rgba(other), 1.0)];
+        let [r, g, b, a] = Ramp::new(&stops, space).sample(ratio.clamp(0.0, 1.0));
+        Rgba::from_f32(r, g, b, a)
+    }
+
+    fn adjust_hsl(&self, adjust: impl FnOnce(&mut Hsla)) -> Rgba {
+        let mut hsla = Hsla::from_rgba(*self);
+        adjust(&mut hsla);
+        hsla.to_rgba()
+    }
+}
+
+/// Adjustments apply to every color of a gradient; patterns only change their solid color.
+/// Solid colors come back as RGBA.
+impl Color {
+    /// Raise HSL lightness by `amount` (0.0-1.0)
+    pub fn lighten(&self, amount: f32) -> Color {
+        self.map_colors(&|rgba| rgba.lighten(amount))
+    }
+
+    /// Lower HSL lightness by `amount` (0.0-1.0)
+    pub fn darken(&self, amount: f32) -> Color {
+        self.map_colors(&|rgba| rgba.darken(amount))
+    }
+
+    /// Raise HSL saturation by `amount` (0.0-1.0)
+    pub fn saturate(&self, amount: f32) -> Color {
+        self.map_colors(&|rgba| rgba.saturate(amount))
+    }
+
+    /// Lower HSL saturation by `amount` (0.0-1.0)
+    pub fn desaturate(&self, amount: f32) -> Color {
+        self.map_colors(&|rgba| rgba.desaturate(amount))
+    }
+
+    /// Turn the hue by `degrees`
+    pub fn rotate_hue(&self, degrees: f32) -> Color {
+        self.map_colors(&|rgba| rgba.rotate_hue(degrees))
+    }
+
+    /// Color opposite on the hue wheel
+    pub fn complement(&self) -> Color {
+        self.map_colors(&|rgba| rgba.complement())
+    }
+
+    /// Same color with alpha set to `alpha` (0.0-1.0)
+    pub fn with_alpha(&self, alpha: f32) -> Color {
+        self.map_colors(&|rgba| rgba.with_alpha(alpha))
+    }
+
+    /// Raise alpha by `amount`, or lower it when negative
+    pub fn adjust_alpha(&self, amount: f32) -> Color {
+        self.map_colors(&|rgba| rgba.adjust_alpha(amount))
+    }
+
+    /// Blend the solid colors of this and `other` by `ratio` (0.0-1.0), interpolating in `space`
+    pub fn mix(&self, other: &Color, ratio: f32, space: InterpolationSpace) -> Color {
+        Color::Rgba(self.to_rgba().mix(other.to_rgba(), ratio, space))
+    }
+
+    fn map_colors(&self, adjust: &dyn Fn(Rgba) -> Rgba) -> Color {
+        let mut color = self.clone();
+        let stops = match &mut color {
+            Color::LinearGradient(gradient) => &mut gradient.stops,
+            Color::RadialGradient(gradient) => &mut gradient.stops,
+            Color::ConicGradient(gradient) => &mut gradient.stops,
+            Color::MeshGradient(gradient) => {
+                for point in &mut gradient.points {
+                    point.color = point.color.map_colors(adjust);
+                }
+                return color;
+            }
+            Color::FreeformGradient(gradient) => {
+                for point in &mut gradient.points {
+                    point.color = point.color.map_colors(adjust);
+                }
+                return color;
+            }
+            Color::Pattern(pattern) => {
+                pattern.color = adjust(pattern.color);
+                return color;
+            }
+            solid => return Color::Rgba(adjust(solid.to_rgba())),
+        };
+        for stop in stops {
+            stop.color = stop.color.map_colors(adjust);
+        }
+        color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::HueDirection;
+
+    #[test]
+    fn test_hsl_adjustments() {
+        let red = Rgba::rgb(255, 0, 0);
+        assert_eq!(red.lighten(0.25), Rgba::rgb(255, 128, 128));
+        assert_eq!(red.darken(0.25), Rgba::rgb(128, 0, 0));
+        assert_eq!(red.desaturate(1.0), Rgba::rgb(128, 128, 128));
+        assert_eq!(Rgba::rgb(191, 64, 64).saturate(0.5), Rgba::rgb(255, 0, 0));
+        assert_eq!(red.rotate_hue(120.0), Rgba::rgb(0, 255, 0));
+        assert_eq!(red.complement(), Rgba::rgb(0, 255, 255));
+        assert_eq!(red.with_alpha(0.5).adjust_alpha(-0.25), Rgba::new(255, 0, 0, 64));
+    }
+
+    #[test]
+    fn test_mix_spaces() {
+        let (red, blue) = (Rgba::rgb(255, 0, 0), Rgba::rgb(0, 0, 255));
+        assert_eq!(red.mix(blue, 0.5, InterpolationSpace::Srgb), Rgba::rgb(128, 0, 128));
+        assert_eq!(red.mix(blue, 0.0, InterpolationSpace::Oklab), red);
+        assert_eq!(red.mix(blue, 1.0, InterpolationSpace::Oklab), blue);
+        // Linear light keeps the blend brighter than plain sRGB
+        assert!(red.mix(blue, 0.5, InterpolationSpace::LinearSrgb).r > 128);
+        // The long way round the hue wheel passes through green
+        assert!(red.mix(blue, 0.5, InterpolationSpace::Hsl(HueDirection::Longer)).g > 200);
+    }
+
+    #[test]
+    fn test_color_adjusts_gradient_stops() {
+        let gradient = Color::linear_gradient()
+            .add_stop(0.0, Color::rgba(255, 0, 0, 255))
+            .add_stop(1.0, Color::named(crate::color::NamedColor::Blue))
+            .build();
+        let Color::LinearGradient(darker) = gradient.darken(0.25) else {
+            panic!("expected a gradient");
+        };
+        assert_eq!(darker.stops[0].color, Color::rgba(128, 0, 0, 255));
+        assert_eq!(darker.stops[1].color, Color::rgba(0, 0, 128, 255));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::color::conversions::conversions::{cmyk_to_rgb, rgb_to_cmyk};
+use crate::color::{check_component, Rgba};
+use crate::error::Result;
+
+/// CMYK color with floating-point components
+///
+/// Converted with the naive formulas, without a printer profile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cmyk {
+    pub c: f32, // Cyan: 0-1
+    pub m: f32, // Magenta: 0-1
+    pub y: f32, // Yellow: 0-1
+    pub k: f32, // Black: 0-1
+    pub alpha: f32, // Alpha: 0-1
+}
+
+impl Cmyk {
+    /// Create new CMYK color
+    pub fn new(c: f32, m: f32, y: f32, k: f32, alpha: f32) -> Result<Self> {
+        check_component("cyan", c, 0.0..=1.0)?;
+        check_component("magenta", m, 0.0..=1.0)?;
+        check_component("yellow", y, 0.0..=1.0)?;
+        check_component("black", k, 0.0..=1.0)?;
+        check_component("alpha", alpha, 0.0..=1.0)?;
+        Ok(Self { c, m, y, k, alpha })
+    }
+    
+    /// Convert from RGBA
+    pub fn from_rgba(rgba: Rgba) -> Self {
+        let (r, g, b, alpha) = rgba.as_f32();
+        let (c, m, y, k) = rgb_to_cmyk(r, g, b);
+        Self { c, m, y, k, alpha }
+    }
+    
+    /// Convert to RGBA
+    pub fn to_rgba(&self) -> Rgba {
+        let (r, g, b) = cmyk_to_rgb(self.c, self.m, self.y, self.k);
+        Rgba::from_f32(r, g, b, self.alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_cmyk_round_trip() {
+        assert_eq!(Cmyk::from_rgba(Rgba::rgb(0, 0, 0)), Cmyk { c: 0.0, m: 0.0, y: 0.0, k: 1.0, alpha: 1.0 });
+        assert!(Cmyk::new(0.0, 0.0, 0.0, 1.5, 1.0).is_err());
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgba = Rgba::new(r, g, b, 255);
+                    assert_eq!(Cmyk::from_rgba(rgba).to_rgba(), rgba);
+                }
+            }
+        }
+    }
+}
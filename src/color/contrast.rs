@@ -0,0 +1,125 @@
+use crate::color::{to_linear_srgb, Color, Rgba};
+
+/// Contrast is measured between opaque colors; alpha is ignored
+impl Rgba {
+    /// WCAG 2 relative luminance, from 0.0 for black to 1.0 for white
+    pub fn relative_luminance(&self) -> f32 {
+        let (r, g, b, _) = to_linear_srgb(*self);
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// WCAG 2 contrast ratio, from 1.0 for equal colors to 21.0 for black against white
+    ///
+    /// Body text needs at least 4.5 (AA) or 7.0 (AAA), large text 3.0 or 4.5.
+    pub fn contrast_ratio(&self, other: Rgba) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// APCA lightness contrast (Lc) of this text color on `background`
+    ///
+    /// Positive for dark text on a light background, negative for light on dark.
+    /// Around 75 in magnitude suits body text, 60 content text and 45 large headings.
+    pub fn apca_contrast(&self, background: Rgba) -> f32 {
+        let (text, background) = (apca_luminance(*self), apca_luminance(background));
+        if (background - text).abs() < 0.0005 {
+            return 0.0;
+        }
+        if background > text {
+            let contrast = (background.powf(0.56) - text.powf(0.57)) * 1.14;
+            if contrast < 0.1 { 0.0 } else { (contrast - 0.027) * 100.0 }
+        } else {
+            let contrast = (background.powf(0.65) - text.powf(0.62)) * 1.14;
+            if contrast > -0.1 { 0.0 } else { (contrast + 0.027) * 100.0 }
+        }
+    }
+
+    /// Black or white, whichever has more WCAG contrast on this background
+    pub fn readable_text_color(&self) -> Rgba {
+        let on_black = self.contrast_ratio(Rgba::BLACK);
+        let on_white = self.contrast_ratio(Rgba::WHITE);
+        if on_black >= on_white { Rgba::BLACK } else { Rgba::WHITE }
+    }
+
+    /// The candidate with the most WCAG contrast on this background
+    pub fn most_readable(&self, candidates: &[Rgba]) -> Option<Rgba> {
+        candidates.iter()
+            .copied()
+            .max_by(|a, b| self.contrast_ratio(*a).total_cmp(&self.contrast_ratio(*b)))
+    }
+}
+
+/// APCA screen luminance, with the soft clamp for near-black colors
+fn apca_luminance(rgba: Rgba) -> f32 {
+    let (r, g, b, _) = rgba.as_f32();
+    let luminance = 0.2126729 * r.powf(2.4) + 0.7151522 * g.powf(2.4) + 0.072175 * b.powf(2.4);
+    if luminance > 0.022 {
+        luminance
+    } else {
+        luminance + (0.022 - luminance).powf(1.414)
+    }
+}
+
+/// Contrast uses the solid color of gradients and patterns
+impl Color {
+    /// WCAG 2 relative luminance, from 0.0 for black to 1.0 for white
+    pub fn relative_luminance(&self) -> f32 {
+        self.to_rgba().relative_luminance()
+    }
+
+    /// WCAG 2 contrast ratio, from 1.0 to 21.0
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        self.to_rgba().contrast_ratio(other.to_rgba())
+    }
+
+    /// APCA lightness contrast (Lc) of this text color on `background`
+    pub fn apca_contrast(&self, background: &Color) -> f32 {
+        self.to_rgba().apca_contrast(background.to_rgba())
+    }
+
+    /// Black or white, whichever has more WCAG contrast on this background
+    pub fn readable_text_color(&self) -> Color {
+        Color::Rgba(self.to_rgba().readable_text_color())
+    }
+
+    /// The candidate with the most WCAG contrast on this background
+    pub fn most_readable(&self, candidates: &[Color]) -> Option<Color> {
+        let background = self.to_rgba();
+        candidates.iter()
+            .max_by(|a, b| background.contrast_ratio(a.to_rgba()).total_cmp(&background.contrast_ratio(b.to_rgba())))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wcag_contrast() {
+        assert_eq!(Rgba::WHITE.relative_luminance(), 1.0);
+        assert!((Rgba::BLACK.contrast_ratio(Rgba::WHITE) - 21.0).abs() < 1e-4);
+        assert_eq!(Rgba::WHITE.contrast_ratio(Rgba::WHITE), 1.0);
+        // #767676 is the lightest gray passing AA on white
+        assert!(Rgba::rgb(0x76, 0x76, 0x76).contrast_ratio(Rgba::WHITE) >= 4.5);
+        assert!(Rgba::rgb(0x77, 0x77, 0x77).contrast_ratio(Rgba::WHITE) < 4.5);
+    }
+
+    #[test]
+    fn test_apca_contrast() {
+        let close = |value: f32, expected: f32| assert!((value - expected).abs() < 0.1, "{value} != {expected}");
+        close(Rgba::BLACK.apca_contrast(Rgba::WHITE), 106.04);
+        close(Rgba::WHITE.apca_contrast(Rgba::BLACK), -107.88);
+        close(Rgba::rgb(0x88, 0x88, 0x88).apca_contrast(Rgba::WHITE), 63.06);
+        assert_eq!(Rgba::WHITE.apca_contrast(Rgba::WHITE), 0.0);
+    }
+
+    #[test]
+    fn test_readable_text_color() {
+        assert_eq!(Rgba::rgb(255, 255, 0).readable_text_color(), Rgba::BLACK);
+        assert_eq!(Rgba::rgb(0, 0, 128).readable_text_color(), Rgba::WHITE);
+        let candidates = [Color::rgba(200, 200, 200, 255), Color::rgba(30, 30, 30, 255)];
+        assert_eq!(Color::rgba(250, 250, 250, 255).most_readable(&candidates), Some(candidates[1].clone()));
+        assert_eq!(Color::default().most_readable(&[]), None);
+    }
+}
@@ -10,7 +10,7 @@ pub mod conversions {
         let h = if delta == 0.0 {
             0.0
         } else if max == r {
-            60.0 * (((g - b) / delta) % 6.0)
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
         } else if max == g {
             60.0 * (((b - r) / delta) + 2.0)
         } else {
@@ -25,6 +25,7 @@ pub mod conversions {
     
     /// Convert HSL to RGB
     pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (f32, f32, f32) {
+        let h = h.rem_euclid(360.0);
         let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
         let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
         let m = l - c / 2.0;
@@ -45,5 +46,148 @@ pub mod conversions {
         
         (r + m, g + m, b + m)
     }
+    
+    /// Convert an sRGB channel (0.0-1.0) to linear light
+    pub fn srgb_to_linear(c: f32) -> f32 {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    
+    /// Convert a linear light channel (0.0-1.0) to sRGB
+    pub fn linear_to_srgb(c: f32) -> f32 {
+        if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    }
+    
+    /// Convert linear sRGB to OKLab (L, a, b)
+    pub fn linear_srgb_to_oklab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+        let l = (0.41222147 * r + 0.53633254 * g + 0.051445993 * b).cbrt();
+        let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+        let s = (0.08830246 * r + 0.28171884 * g + 0.6299787 * b).cbrt();
+        
+        (
+            0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+            1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+        )
+    }
+    
+    /// Convert OKLab (L, a, b) to linear sRGB
+    pub fn oklab_to_linear_srgb(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+        let l_ = (l + 0.39633778 * a + 0.21580376 * b).powi(3);
+        let m_ = (l - 0.105561346 * a - 0.06385417 * b).powi(3);
+        let s_ = (l - 0.08948418 * a - 1.2914855 * b).powi(3);
+        
+        (
+            4.0767417 * l_ - 3.3077116 * m_ + 0.23096993 * s_,
+            -1.268438 * l_ + 2.6097574 * m_ - 0.34131938 * s_,
+            -0.0041960863 * l_ - 0.7034186 * m_ + 1.7076147 * s_,
+        )
+    }
+    
+    /// Convert CIE Lab (D50 white) to linear sRGB
+    pub fn lab_to_linear_srgb(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+        const EPSILON: f32 = 216.0 / 24389.0;
+        const KAPPA: f32 = 24389.0 / 27.0;
+        
+        let fy = (l + 16.0) / 116.0;
+        let fx = fy + a / 500.0;
+        let fz = fy - b / 200.0;
+        let inverse = |f: f32| if f.powi(3) > EPSILON { f.powi(3) } else { (116.0 * f - 16.0) / KAPPA };
+        let x = inverse(fx) * 0.9642957;
+        let y = if l > KAPPA * EPSILON { fy.powi(3) } else { l / KAPPA };
+        let z = inverse(fz) * 0.8251046;
+        
+        // XYZ D50 to linear sRGB, with Bradford adaptation to D65
+        (
+            3.134136 * x - 1.6173863 * y - 0.49066195 * z,
+            -0.9787955 * x + 1.9162546 * y + 0.033442732 * z,
+            0.07195538 * x - 0.22897682 * y + 1.4053861 * z,
+        )
+    }
+    
+    /// Convert linear sRGB to CIE Lab (D50 white)
+    pub fn linear_srgb_to_lab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+        const EPSILON: f32 = 216.0 / 24389.0;
+        const KAPPA: f32 = 24389.0 / 27.0;
+        
+        // Linear sRGB to XYZ D50, inverse of the matrix in `lab_to_linear_srgb`
+        let x = (0.43606576 * r + 0.38515154 * g + 0.14307837 * b) / 0.9642957;
+        let y = 0.22249316 * r + 0.716887 * g + 0.060619835 * b;
+        let z = (0.013923934 * r + 0.09708135 * g + 0.71409935 * b) / 0.8251046;
+        let forward = |t: f32| if t > EPSILON { t.cbrt() } else { (KAPPA * t + 16.0) / 116.0 };
+        let (fx, fy, fz) = (forward(x), forward(y), forward(z));
+        
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+    
+    /// Convert the `a`, `b` axes of Lab or OKLab to chroma and hue in degrees
+    pub fn ab_to_chroma_hue(a: f32, b: f32) -> (f32, f32) {
+        let chroma = (a * a + b * b).sqrt();
+        // Hue of a gray is meaningless, and rounding noise would otherwise pick one
+        let hue = if chroma < 1e-4 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+        (chroma, hue)
+    }
+    
+    /// Convert chroma and hue in degrees to the `a`, `b` axes of Lab or OKLab
+    pub fn chroma_hue_to_ab(chroma: f32, hue: f32) -> (f32, f32) {
+        let radians = hue.to_radians();
+        (chroma * radians.cos(), chroma * radians.sin())
+    }
+    
+    /// Convert RGB to HSV
+    pub fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+        let (h, _, _) = rgb_to_hsl(r, g, b);
+        let max = r.max(g.max(b));
+        let min = r.min(g.min(b));
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        
+        (h, s, max)
+    }
+    
+    /// Convert HSV to RGB
+    pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
+        let (r, g, b) = hsl_to_rgb(h, 1.0, 0.5);
+        let (scale, min) = (v * s, v * (1.0 - s));
+        
+        (r * scale + min, g * scale + min, b * scale + min)
+    }
+    
+    /// Convert RGB to HWB
+    pub fn rgb_to_hwb(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+        let (h, _, _) = rgb_to_hsl(r, g, b);
+        
+        (h, r.min(g.min(b)), 1.0 - r.max(g.max(b)))
+    }
+    
+    /// Convert HWB to RGB
+    pub fn hwb_to_rgb(h: f32, w: f32, b: f32) -> (f32, f32, f32) {
+        if w + b >= 1.0 {
+            let gray = w / (w + b);
+            return (gray, gray, gray);
+        }
+        hsv_to_rgb(h, 1.0 - w / (1.0 - b), 1.0 - b)
+    }
+    
+    /// Convert RGB to naive CMYK, without an ink profile
+    pub fn rgb_to_cmyk(r: f32, g: f32, b: f32) -> (f32, f32, f32, f32) {
+        let k = 1.0 - r.max(g.max(b));
+        if k >= 1.0 {
+            return (0.0, 0.0, 0.0, 1.0);
+        }
+        let ink = |channel: f32| (1.0 - channel - k) / (1.0 - k);
+        
+        (ink(r), ink(g), ink(b), k)
+    }
+    
+    /// Convert naive CMYK to RGB
+    pub fn cmyk_to_rgb(c: f32, m: f32, y: f32, k: f32) -> (f32, f32, f32) {
+        ((1.0 - c) * (1.0 - k), (1.0 - m) * (1.0 - k), (1.0 - y) * (1.0 - k))
+    }
 }
-
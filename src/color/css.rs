@@ -0,0 +1,304 @@
+use crate::color::{Color, Hsla, Hwba, Lab, Lch, NamedColor, Oklab, Oklch, Rgba};
+use crate::error::{CloveError, Result};
+use cssparser::color::{parse_hash_color, parse_named_color};
+use cssparser::{match_ignore_ascii_case, ParseError, Parser, ParserInput, Token};
+
+type ParseResult<'i, T> = std::result::Result<T, ParseError<'i, ()>>;
+
+/// Parse any CSS Color Level 4 color
+pub(crate) fn parse(text: &str) -> Result<Color> {
+    let mut input = ParserInput::new(text);
+    let mut parser = Parser::new(&mut input);
+    parser.parse_entirely(parse_color)
+        .map_err(|_| CloveError::InvalidCssColor(text.to_string()))
+}
+
+fn parse_color<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Color> {
+    let location = input.current_source_location();
+    let token = input.next()?.clone();
+    match &token {
+        Token::Hash(value) | Token::IDHash(value) => {
+            let (r, g, b, a) = parse_hash_color(value.as_bytes())
+                .map_err(|_| location.new_unexpected_token_error(token.clone()))?;
+            Ok(Color::Rgba(Rgba::new(r, g, b, to_u8(a))))
+        }
+        Token::Ident(name) => {
+            if name.eq_ignore_ascii_case("transparent") {
+                return Ok(Color::Rgba(Rgba::TRANSPARENT));
+            }
+            if let Some(named) = NamedColor::from_name(name) {
+                return Ok(Color::Named(named));
+            }
+            // Keywords the enum doesn't cover, like rebeccapurple
+            let (r, g, b) = parse_named_color(&name.to_ascii_lowercase())
+                .map_err(|_| location.new_unexpected_token_error(token.clone()))?;
+            Ok(Color::Rgba(Rgba::rgb(r, g, b)))
+        }
+        Token::Function(name) => {
+            let name = name.clone();
+            input.parse_nested_block(|input| parse_function(&name, input))
+        }
+        _ => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
+
+fn parse_function<'i>(name: &str, input: &mut Parser<'i, '_>) -> ParseResult<'i, Color> {
+    let location = input.current_source_location();
+    let ([first, second, third], alpha) = arguments(input)?;
+    let invalid = || location.new_custom_error(());
+    let alpha = match alpha {
+        Some(alpha) => alpha.value(1.0).ok_or_else(invalid)?.clamp(0.0, 1.0),
+        None => 1.0,
+    };
+
+    let color = match_ignore_ascii_case! { name,
+        "rgb" | "rgba" => {
+            let channel = |component: Component| component.value(255.0).map(|value| value.round().clamp(0.0, 255.0) as u8);
+            let (r, g, b) = (channel(first), channel(second), channel(third));
+            Color::Rgba(Rgba::new(r.ok_or_else(invalid)?, g.ok_or_else(invalid)?, b.ok_or_else(invalid)?, to_u8(alpha)))
+        },
+        "hsl" | "hsla" => {
+            let (h, s, l) = (first.hue(), second.value(100.0), third.value(100.0));
+            Color::Hsla(Hsla {
+                h: h.ok_or_else(invalid)?.rem_euclid(360.0),
+                s: (s.ok_or_else(invalid)? / 100.0).clamp(0.0, 1.0),
+                l: (l.ok_or_else(invalid)? / 100.0).clamp(0.0, 1.0),
+                a: alpha,
+            })
+        },
+        "hwb" => {
+            let (h, w, b) = (first.hue(), second.value(100.0), third.value(100.0));
+            Color::Hwba(Hwba {
+                h: h.ok_or_else(invalid)?.rem_euclid(360.0),
+                w: (w.ok_or_else(invalid)? / 100.0).clamp(0.0, 1.0),
+                b: (b.ok_or_else(invalid)? / 100.0).clamp(0.0, 1.0),
+                a: alpha,
+            })
+        },
+        "lab" => {
+            let (l, a, b) = (first.value(100.0), second.value(125.0), third.value(125.0));
+            Color::Lab(Lab {
+                l: l.ok_or_else(invalid)?.clamp(0.0, 100.0),
+                a: a.ok_or_else(invalid)?,
+                b: b.ok_or_else(invalid)?,
+                alpha,
+            })
+        },
+        "lch" => {
+            let (l, c, h) = (first.value(100.0), second.value(150.0), third.hue());
+            Color::Lch(Lch {
+                l: l.ok_or_else(invalid)?.clamp(0.0, 100.0),
+                c: c.ok_or_else(invalid)?.max(0.0),
+                h: h.ok_or_else(invalid)?.rem_euclid(360.0),
+                alpha,
+            })
+        },
+        "oklab" => {
+            let (l, a, b) = (first.value(1.0), second.value(0.4), third.value(0.4));
+            Color::Oklab(Oklab {
+                l: l.ok_or_else(invalid)?.clamp(0.0, 1.0),
+                a: a.ok_or_else(invalid)?,
+                b: b.ok_or_else(invalid)?,
+                alpha,
+            })
+        },
+        "oklch" => {
+            let (l, c, h) = (first.value(1.0), second.value(0.4), third.hue());
+            Color::Oklch(Oklch {
+                l: l.ok_or_else(invalid)?.clamp(0.0, 1.0),
+                c: c.ok_or_else(invalid)?.max(0.0),
+                h: h.ok_or_else(invalid)?.rem_euclid(360.0),
+                alpha,
+            })
+        },
+        _ => return Err(invalid()),
+    };
+    Ok(color)
+}
+
+/// One channel of a color function
+#[derive(Clone, Copy)]
+enum Component {
+    Number(f32),
+    /// Percentage as a fraction
+    Percentage(f32),
+    /// Angle in degrees
+    Angle(f32),
+    None,
+}
+
+impl Component {
+    /// Plain value, with 100% meaning `reference`
+    fn value(self, reference: f32) -> Option<f32> {
+        match self {
+            Component::Number(value) => Some(value),
+            Component::Percentage(fraction) => Some(fraction * reference),
+            Component::None => Some(0.0),
+            Component::Angle(_) => None,
+        }
+    }
+
+    /// Hue in degrees
+    fn hue(self) -> Option<f32> {
+        match self {
+            Component::Number(degrees) | Component::Angle(degrees) => Some(degrees),
+            Component::None => Some(0.0),
+            Component::Percentage(_) => None,
+        }
+    }
+}
+
+fn component<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Component> {
+    let location = input.current_source_location();
+    let token = input.next()?.clone();
+    let component = match &token {
+        Token::Number { value, .. } => Component::Number(*value),
+        Token::Percentage { unit_value, .. } => Component::Percentage(*unit_value),
+        Token::Dimension { value, unit, .. } => Component::Angle(match_ignore_ascii_case! { unit,
+            "deg" => *value,
+            "rad" => value.to_degrees(),
+            "grad" => value * 0.9,
+            "turn" => value * 360.0,
+            _ => return Err(location.new_unexpected_token_error(token.clone())),
+        }),
+        Token::Ident(name) if name.eq_ignore_ascii_case("none") => Component::None,
+        _ => return Err(location.new_unexpected_token_error(token.clone())),
+    };
+    Ok(component)
+}
+
+/// Three channels and an optional alpha, in modern (`1 2 3 / a`) or legacy (`1, 2, 3, a`) syntax
+fn arguments<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, ([Component; 3], Option<Component>)> {
+    let first = component(input)?;
+    let legacy = input.try_parse(|input| input.expect_comma()).is_ok();
+    let second = component(input)?;
+    if legacy {
+        input.expect_comma()?;
+    }
+    let third = component(input)?;
+    let separator = if legacy {
+        input.try_parse(|input| input.expect_comma())
+    } else {
+        input.try_parse(|input| input.expect_delim('/'))
+    };
+    let alpha = match separator {
+        Ok(()) => Some(component(input)?),
+        Err(_) => None,
+    };
+    Ok(([first, second, third], alpha))
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+/// Format a color as CSS
+///
+/// HSV, CMYK, gradients, meshes and patterns have no CSS syntax, so they're written as RGB.
+pub(crate) fn to_css_string(color: &Color) -> String {
+    match color {
+        Color::Named(named) => match named.css_name() {
+            Some(name) => name.to_string(),
+            None => rgba_to_css(named.to_rgba()),
+        },
+        Color::Hsla(hsla) => format!(
+            "hsl({} {}% {}%{})",
+            number(hsla.h),
+            number(hsla.s * 100.0),
+            number(hsla.l * 100.0),
+            alpha_suffix(hsla.a),
+        ),
+        Color::Hwba(hwba) => format!(
+            "hwb({} {}% {}%{})",
+            number(hwba.h),
+            number(hwba.w * 100.0),
+            number(hwba.b * 100.0),
+            alpha_suffix(hwba.a),
+        ),
+        Color::Lab(lab) => format!("lab({} {} {}{})", number(lab.l), number(lab.a), number(lab.b), alpha_suffix(lab.alpha)),
+        Color::Lch(lch) => format!("lch({} {} {}{})", number(lch.l), number(lch.c), number(lch.h), alpha_suffix(lch.alpha)),
+        Color::Oklab(oklab) => format!("oklab({} {} {}{})", number(oklab.l), number(oklab.a), number(oklab.b), alpha_suffix(oklab.alpha)),
+        Color::Oklch(oklch) => format!("oklch({} {} {}{})", number(oklch.l), number(oklch.c), number(oklch.h), alpha_suffix(oklch.alpha)),
+        color => rgba_to_css(color.to_rgba()),
+    }
+}
+
+fn rgba_to_css(rgba: Rgba) -> String {
+    if rgba.a == 255 {
+        format!("#{:02x}{:02x}{:02x}", rgba.r, rgba.g, rgba.b)
+    } else {
+        format!("rgb({} {} {}{})", rgba.r, rgba.g, rgba.b, alpha_suffix(rgba.a as f32 / 255.0))
+    }
+}
+
+fn alpha_suffix(alpha: f32) -> String {
+    if alpha >= 1.0 {
+        String::new()
+    } else {
+        format!(" / {}", number(alpha))
+    }
+}
+
+/// Shortest decimal up to four places
+fn number(value: f32) -> String {
+    let text = format!("{:.4}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba(text: &str) -> Rgba {
+        parse(text).unwrap().to_rgba()
+    }
+
+    #[test]
+    fn test_parse_hex_rgb_and_names() {
+        assert_eq!(rgba("#f00"), Rgba::rgb(255, 0, 0));
+        assert_eq!(rgba("#ff000080"), Rgba::new(255, 0, 0, 128));
+        assert_eq!(rgba("rgb(0 128 255 / 50%)"), Rgba::new(0, 128, 255, 128));
+        assert_eq!(rgba("rgba(0, 100%, 0, 0.25)"), Rgba::new(0, 255, 0, 64));
+        assert_eq!(parse("DarkGreen").unwrap(), Color::Named(NamedColor::DarkGreen));
+        assert_eq!(parse("grey").unwrap(), Color::Named(NamedColor::Gray));
+        assert_eq!(rgba("rebeccapurple"), Rgba::rgb(102, 51, 153));
+        assert_eq!(rgba("transparent"), Rgba::TRANSPARENT);
+
+        assert!(parse("rgb(1 2)").is_err());
+        assert!(parse("rgb(1, 2 3)").is_err());
+        assert!(parse("notacolor").is_err());
+        assert!(parse("#12345").is_err());
+    }
+
+    #[test]
+    fn test_parse_cylindrical_and_lab_spaces() {
+        assert_eq!(parse("hsl(120deg 100% 25%)").unwrap(), Color::Hsla(Hsla { h: 120.0, s: 1.0, l: 0.25, a: 1.0 }));
+        assert_eq!(rgba("hsla(0.5turn, 100%, 50%, 1)"), Rgba::rgb(0, 255, 255));
+        assert_eq!(rgba("hwb(0 0% 0%)"), Rgba::rgb(255, 0, 0));
+        assert_eq!(rgba("hwb(90 60% 60%)"), Rgba::rgb(128, 128, 128));
+        assert_eq!(rgba("oklch(62.8% 0.2577 29.23)"), Rgba::rgb(255, 0, 0));
+        assert_eq!(rgba("oklab(1 0 0)"), Rgba::rgb(255, 255, 255));
+        assert_eq!(parse("oklch(50% 0.1 -90)").unwrap(), Color::Oklch(Oklch { l: 0.5, c: 0.1, h: 270.0, alpha: 1.0 }));
+
+        let close = |text: &str, expected: Rgba| {
+            let color = rgba(text);
+            let channels = [(color.r, expected.r), (color.g, expected.g), (color.b, expected.b)];
+            assert!(channels.iter().all(|&(a, b)| a.abs_diff(b) <= 1), "{text}: {color:?}");
+        };
+        close("lab(54.29 80.8 69.89)", Rgba::rgb(255, 0, 0));
+        close("lch(54.29% 106.84 40.85deg)", Rgba::rgb(255, 0, 0));
+        close("lab(50% none none)", Rgba::rgb(119, 119, 119));
+    }
+
+    #[test]
+    fn test_css_string_round_trips() {
+        for text in ["#1a2b3c", "rgb(10 20 30 / 0.5)", "hsl(200 50% 40% / 0.25)", "rebeccapurple", "oklch(70% 0.1 200)", "lab(40 30 -20 / 0.5)", "hwb(30 10% 20%)"] {
+            let color = parse(text).unwrap();
+            let css = to_css_string(&color);
+            assert_eq!(parse(&css).unwrap().to_rgba(), color.to_rgba(), "{text} -> {css}");
+        }
+        assert_eq!(to_css_string(&Color::Named(NamedColor::Gray)), "gray");
+        assert_eq!(to_css_string(&Color::rgba(255, 0, 0, 255)), "#ff0000");
+    }
+}
@@ -1,17 +1,90 @@
+use crate::color::interpolation::{Dither, Easing, InterpolationSpace, Ramp};
 use crate::color::Color;
 use crate::geometry::Point;
 use tiny_skia::{Shader, Transform};
 
+/// How a gradient continues past its first and last stops
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SpreadMode {
+    /// End colors extend outward
+    #[default]
+    Pad,
+    /// Stops start over
+    Repeat,
+    /// Stops run back and forth
+    Reflect,
+}
+
+impl SpreadMode {
+    /// Fold a position outside 0.0-1.0 back onto the stops
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            SpreadMode::Pad => t.clamp(0.0, 1.0),
+            SpreadMode::Repeat => t.rem_euclid(1.0),
+            SpreadMode::Reflect => 1.0 - (t.rem_euclid(2.0) - 1.0).abs(),
+        }
+    }
+    
+    fn to_skia(self) -> tiny_skia::SpreadMode {
+        match self {
+            SpreadMode::Pad => tiny_skia::SpreadMode::Pad,
+            SpreadMode::Repeat => tiny_skia::SpreadMode::Repeat,
+            SpreadMode::Reflect => tiny_skia::SpreadMode::Reflect,
+        }
+    }
+}
+
+/// Coordinate system a gradient's points and radius are given in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GradientUnits {
+    /// Pixels in the shape's own space
+    #[default]
+    UserSpace,
+    /// Fractions of the filled shape's bounding box, so (0, 0) is its top left and (1, 1) its bottom right
+    ObjectBoundingBox,
+}
+
+impl GradientUnits {
+    /// Map gradient coordinates into the space of a shape with `bounds`
+    pub(crate) fn to_skia(self, bounds: tiny_skia::Rect) -> Transform {
+        match self {
+            GradientUnits::UserSpace => Transform::identity(),
+            GradientUnits::ObjectBoundingBox => {
+                Transform::from_row(bounds.width(), 0.0, 0.0, bounds.height(), bounds.x(), bounds.y())
+            }
+        }
+    }
+}
+
 /// Gradient stop with color and position
 #[derive(Debug, Clone, PartialEq)]
 pub struct GradientStop {
     pub color: Color,
     pub position: f32, // 0.0 to 1.0
+    /// Fraction of the way to the next stop where the two colors mix evenly
+    pub midpoint: f32,
+    /// Curve of the blend towards the next stop
+    pub easing: Easing,
 }
 
 impl GradientStop {
     pub fn new(color: Color, position: f32) -> Self {
-        Self { color, position }
+        Self {
+            color,
+            position,
+            midpoint: 0.5,
+            easing: Easing::Linear,
+        }
+    }
+    
+    pub fn midpoint(mut self, midpoint: f32) -> Self {
+        self.midpoint = midpoint;
+        self
+    }
+    
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
     }
 }
 
@@ -21,6 +94,11 @@ pub struct LinearGradientBuilder {
     start: Option<Point>,
     end: Option<Point>,
     stops: Vec<GradientStop>,
+    spread: SpreadMode,
+    units: GradientUnits,
+    transform: crate::transform::Transform,
+    interpolation: InterpolationSpace,
+    dither: Dither,
 }
 
 impl LinearGradientBuilder {
@@ -29,6 +107,11 @@ impl LinearGradientBuilder {
             start: None,
             end: None,
             stops: Vec::new(),
+            spread: SpreadMode::Pad,
+            units: GradientUnits::UserSpace,
+            transform: crate::transform::Transform::new(),
+            interpolation: InterpolationSpace::Srgb,
+            dither: Dither::None,
         }
     }
     
@@ -44,7 +127,43 @@ impl LinearGradientBuilder {
     
     pub fn add_stop(mut self, position: f32, color: Color) -> Self {
         self.stops.push(GradientStop::new(color, position));
-        self.stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        self
+    }
+    
+    /// Add a stop carrying its own midpoint and easing
+    pub fn add_gradient_stop(mut self, stop: GradientStop) -> Self {
+        self.stops.push(stop);
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        self
+    }
+    
+    /// Set the color space stops are blended in
+    pub fn interpolation(mut self, space: InterpolationSpace) -> Self {
+        self.interpolation = space;
+        self
+    }
+    
+    /// Dither when rasterizing, trading banding for fine noise
+    pub fn dither(mut self, dither: Dither) -> Self {
+        self.dither = dither;
+        self
+    }
+    
+    /// Set how the gradient continues past its end stops
+    pub fn spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+    
+    pub fn units(mut self, units: GradientUnits) -> Self {
+        self.units = units;
+        self
+    }
+    
+    /// Apply a `Transform` around the gradient's midpoint
+    pub fn transform(mut self, transform: crate::transform::Transform) -> Self {
+        self.transform = transform;
         self
     }
     
@@ -53,6 +172,11 @@ impl LinearGradientBuilder {
             start: self.start.unwrap_or(Point::ZERO),
             end: self.end.unwrap_or(Point::ZERO),
             stops: self.stops,
+            spread: self.spread,
+            units: self.units,
+            transform: self.transform,
+            interpolation: self.interpolation,
+            dither: self.dither,
         };
         crate::color::Color::LinearGradient(gradient)
     }
@@ -70,6 +194,12 @@ pub struct LinearGradient {
     pub start: Point,
     pub end: Point,
     pub stops: Vec<GradientStop>,
+    pub spread: SpreadMode,
+    pub units: GradientUnits,
+    /// Applied around the midpoint of `start` and `end`
+    pub transform: crate::transform::Transform,
+    pub interpolation: InterpolationSpace,
+    pub dither: Dither,
 }
 
 impl LinearGradient {
@@ -77,28 +207,39 @@ impl LinearGradient {
         LinearGradientBuilder::new()
     }
     
-    /// Convert to tiny-skia LinearGradient shader
-    pub fn to_skia_linear_gradient(&self, _transform: Transform) -> Option<Shader> {
+    /// Render dithered pixels covering `area` of a pixmap at device `origin`, for a shape with `bounds` drawn with `transform`
+    pub(crate) fn render(&self, area: tiny_skia::IntRect, origin: (u32, u32), bounds: tiny_skia::Rect, transform: Transform) -> Option<tiny_skia::Pixmap> {
+        let midpoint = Point::new((self.start.x + self.end.x) / 2.0, (self.start.y + self.end.y) / 2.0);
+        let placement = self.transform.to_skia_about(midpoint).post_concat(self.units.to_skia(bounds));
+        let (dx, dy) = (self.end.x - self.start.x, self.end.y - self.start.y);
+        let length_squared = dx * dx + dy * dy;
+        if length_squared == 0.0 {
+            return None;
+        }
+        let ramp = Ramp::new(&self.stops, self.interpolation);
+        render_area(area, origin, placement.post_concat(transform), &ramp, self.dither, |x, y| {
+            let t = ((x - self.start.x) * dx + (y - self.start.y) * dy) / length_squared;
+            Some(self.spread.apply(t))
+        })
+    }
+    
+    /// Convert to tiny-skia LinearGradient shader, with `transform` applied after the gradient's own
+    pub fn to_skia_linear_gradient(&self, transform: Transform) -> Option<Shader<'static>> {
         if self.stops.is_empty() {
             return None;
         }
         
-        // Convert stops to tiny-skia format
-        let skia_stops: Vec<tiny_skia::GradientStop> = self.stops.iter()
-            .map(|stop| {
-                let rgba = stop.color.to_rgba();
-                let pos = stop.position.clamp(0.0, 1.0);
-                tiny_skia::GradientStop::new(pos, tiny_skia::Color::from_rgba8(rgba.r, rgba.g, rgba.b, rgba.a))
-            })
-            .collect();
+        // Convert stops to tiny-skia format, sampling curved blends
+        let skia_stops = Ramp::new(&self.stops, self.interpolation).skia_stops();
         
         // Create linear gradient shader
+        let midpoint = Point::new((self.start.x + self.end.x) / 2.0, (self.start.y + self.end.y) / 2.0);
         tiny_skia::LinearGradient::new(
             tiny_skia::Point::from_xy(self.start.x, self.start.y),
             tiny_skia::Point::from_xy(self.end.x, self.end.y),
             skia_stops,
-            tiny_skia::SpreadMode::Pad,
-            Transform::identity(),
+            self.spread.to_skia(),
+            self.transform.to_skia_about(midpoint).post_concat(transform),
         )
     }
 }
@@ -107,16 +248,28 @@ impl LinearGradient {
 #[derive(Debug, Clone)]
 pub struct RadialGradientBuilder {
     center: Option<Point>,
+    focal: Option<Point>,
     radius: Option<f32>,
     stops: Vec<GradientStop>,
+    spread: SpreadMode,
+    units: GradientUnits,
+    transform: crate::transform::Transform,
+    interpolation: InterpolationSpace,
+    dither: Dither,
 }
 
 impl RadialGradientBuilder {
     pub fn new() -> Self {
         Self {
             center: None,
+            focal: None,
             radius: None,
             stops: Vec::new(),
+            spread: SpreadMode::Pad,
+            units: GradientUnits::UserSpace,
+            transform: crate::transform::Transform::new(),
+            interpolation: InterpolationSpace::Srgb,
+            dither: Dither::None,
         }
     }
     
@@ -130,17 +283,66 @@ impl RadialGradientBuilder {
         self
     }
     
+    /// Point the gradient radiates from, where the first stop sits; defaults to the center
+    pub fn focal(mut self, x: f32, y: f32) -> Self {
+        self.focal = Some(Point::new(x, y));
+        self
+    }
+    
     pub fn add_stop(mut self, position: f32, color: Color) -> Self {
         self.stops.push(GradientStop::new(color, position));
-        self.stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        self
+    }
+    
+    /// Add a stop carrying its own midpoint and easing
+    pub fn add_gradient_stop(mut self, stop: GradientStop) -> Self {
+        self.stops.push(stop);
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        self
+    }
+    
+    /// Set the color space stops are blended in
+    pub fn interpolation(mut self, space: InterpolationSpace) -> Self {
+        self.interpolation = space;
+        self
+    }
+    
+    /// Dither when rasterizing, trading banding for fine noise
+    pub fn dither(mut self, dither: Dither) -> Self {
+        self.dither = dither;
+        self
+    }
+    
+    /// Set how the gradient continues past its end stops
+    pub fn spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+    
+    pub fn units(mut self, units: GradientUnits) -> Self {
+        self.units = units;
+        self
+    }
+    
+    /// Apply a `Transform` around the gradient's center
+    pub fn transform(mut self, transform: crate::transform::Transform) -> Self {
+        self.transform = transform;
         self
     }
     
     pub fn build(self) -> crate::color::Color {
+        let center = self.center.unwrap_or(Point::ZERO);
         let gradient = RadialGradient {
-            center: self.center.unwrap_or(Point::ZERO),
+            center,
+            focal: self.focal.unwrap_or(center),
             radius: self.radius.unwrap_or(50.0),
             stops: self.stops,
+            spread: self.spread,
+            units: self.units,
+            transform: self.transform,
+            interpolation: self.interpolation,
+            dither: self.dither,
         };
         crate::color::Color::RadialGradient(gradient)
     }
@@ -156,8 +358,16 @@ impl Default for RadialGradientBuilder {
 #[derive(Debug, Clone, PartialEq)]
 pub struct RadialGradient {
     pub center: Point,
+    /// Where the first stop sits, equal to `center` for plain circular gradients
+    pub focal: Point,
     pub radius: f32,
     pub stops: Vec<GradientStop>,
+    pub spread: SpreadMode,
+    pub units: GradientUnits,
+    /// Applied around `center`, so a non-uniform scale makes the gradient elliptical
+    pub transform: crate::transform::Transform,
+    pub interpolation: InterpolationSpace,
+    pub dither: Dither,
 }
 
 impl RadialGradient {
@@ -165,31 +375,340 @@ impl RadialGradient {
         RadialGradientBuilder::new()
     }
     
-    /// Convert to tiny-skia RadialGradient shader
-    pub fn to_skia_radial_gradient(&self, _transform: Transform) -> Option<Shader> {
+    /// Render dithered pixels covering `area` of a pixmap at device `origin`, for a shape with `bounds` drawn with `transform`
+    pub(crate) fn render(&self, area: tiny_skia::IntRect, origin: (u32, u32), bounds: tiny_skia::Rect, transform: Transform) -> Option<tiny_skia::Pixmap> {
+        if self.radius <= 0.0 {
+            return None;
+        }
+        let placement = self.transform.to_skia_about(self.center).post_concat(self.units.to_skia(bounds));
+        let ramp = Ramp::new(&self.stops, self.interpolation);
+        let (fx, fy) = (self.focal.x - self.center.x, self.focal.y - self.center.y);
+        render_area(area, origin, placement.post_concat(transform), &ramp, self.dither, |x, y| {
+            let (px, py) = (x - self.focal.x, y - self.focal.y);
+            let distance = px.hypot(py);
+            if distance == 0.0 {
+                return Some(self.spread.apply(0.0));
+            }
+            // Walk from the focal point through the pixel until the ray meets the circle
+            let (ux, uy) = (px / distance, py / distance);
+            let along = ux * fx + uy * fy;
+            let discriminant = along * along - (fx * fx + fy * fy - self.radius * self.radius);
+            let reach = -along + discriminant.max(0.0).sqrt();
+            (discriminant >= 0.0 && reach > 0.0).then(|| self.spread.apply(distance / reach))
+        })
+    }
+    
+    /// Convert to tiny-skia RadialGradient shader, with `transform` applied after the gradient's own
+    pub fn to_skia_radial_gradient(&self, transform: Transform) -> Option<Shader<'static>> {
         if self.stops.is_empty() {
             return None;
         }
         
-        // Convert stops to tiny-skia format
-        let skia_stops: Vec<tiny_skia::GradientStop> = self.stops.iter()
-            .map(|stop| {
-                let rgba = stop.color.to_rgba();
-                let pos = stop.position.clamp(0.0, 1.0);
-                tiny_skia::GradientStop::new(pos, tiny_skia::Color::from_rgba8(rgba.r, rgba.g, rgba.b, rgba.a))
-            })
-            .collect();
+        // Convert stops to tiny-skia format, sampling curved blends
+        let skia_stops = Ramp::new(&self.stops, self.interpolation).skia_stops();
         
-        // Create radial gradient shader (circular)
-        // tiny-skia RadialGradient::new expects: (center, focal_point, radius, stops, spread_mode, transform)
-        // For circular gradient, center and focal_point are the same
+        // tiny-skia RadialGradient::new expects: (focal_point, center, radius, stops, spread_mode, transform)
         tiny_skia::RadialGradient::new(
-            tiny_skia::Point::from_xy(self.center.x, self.center.y),
+            tiny_skia::Point::from_xy(self.focal.x, self.focal.y),
             tiny_skia::Point::from_xy(self.center.x, self.center.y),
             self.radius,
             skia_stops,
-            tiny_skia::SpreadMode::Pad,
-            Transform::identity(),
+            self.spread.to_skia(),
+            self.transform.to_skia_about(self.center).post_concat(transform),
         )
     }
 }
+
+/// Conic gradient builder
+#[derive(Debug, Clone)]
+pub struct ConicGradientBuilder {
+    center: Option<Point>,
+    start_angle: f32,
+    stops: Vec<GradientStop>,
+    units: GradientUnits,
+    transform: crate::transform::Transform,
+    interpolation: InterpolationSpace,
+    dither: Dither,
+}
+
+impl ConicGradientBuilder {
+    pub fn new() -> Self {
+        Self {
+            center: None,
+            start_angle: 0.0,
+            stops: Vec::new(),
+            units: GradientUnits::UserSpace,
+            transform: crate::transform::Transform::new(),
+            interpolation: InterpolationSpace::Srgb,
+            dither: Dither::None,
+        }
+    }
+    
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center = Some(Point::new(x, y));
+        self
+    }
+    
+    /// Angle in degrees where position 0.0 lies, clockwise from the positive x axis
+    pub fn start_angle(mut self, degrees: f32) -> Self {
+        self.start_angle = degrees;
+        self
+    }
+    
+    pub fn add_stop(mut self, position: f32, color: Color) -> Self {
+        self.stops.push(GradientStop::new(color, position));
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        self
+    }
+    
+    /// Add a stop carrying its own midpoint and easing
+    pub fn add_gradient_stop(mut self, stop: GradientStop) -> Self {
+        self.stops.push(stop);
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        self
+    }
+    
+    /// Set the color space stops are blended in
+    pub fn interpolation(mut self, space: InterpolationSpace) -> Self {
+        self.interpolation = space;
+        self
+    }
+    
+    /// Dither when rasterizing, trading banding for fine noise
+    pub fn dither(mut self, dither: Dither) -> Self {
+        self.dither = dither;
+        self
+    }
+    
+    pub fn units(mut self, units: GradientUnits) -> Self {
+        self.units = units;
+        self
+    }
+    
+    /// Apply a `Transform` around the gradient's center
+    pub fn transform(mut self, transform: crate::transform::Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+    
+    pub fn build(self) -> crate::color::Color {
+        let gradient = ConicGradient {
+            center: self.center.unwrap_or(Point::ZERO),
+            start_angle: self.start_angle,
+            stops: self.stops,
+            units: self.units,
+            transform: self.transform,
+            interpolation: self.interpolation,
+            dither: self.dither,
+        };
+        crate::color::Color::ConicGradient(gradient)
+    }
+}
+
+impl Default for ConicGradientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Conic (sweep) gradient
+///
+/// Stop positions run clockwise around the center, one full turn from 0.0 to 1.0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConicGradient {
+    pub center: Point,
+    /// Degrees clockwise from the positive x axis
+    pub start_angle: f32,
+    pub stops: Vec<GradientStop>,
+    pub units: GradientUnits,
+    /// Applied around `center`
+    pub transform: crate::transform::Transform,
+    pub interpolation: InterpolationSpace,
+    pub dither: Dither,
+}
+
+impl ConicGradient {
+    /// Render pixels covering `area` of a pixmap at device `origin`, for a shape with `bounds` drawn with `transform`
+    pub(crate) fn render(&self, area: tiny_skia::IntRect, origin: (u32, u32), bounds: tiny_skia::Rect, transform: Transform) -> Option<tiny_skia::Pixmap> {
+        let placement = self.transform.to_skia_about(self.center).post_concat(self.units.to_skia(bounds));
+        let ramp = Ramp::new(&self.stops, self.interpolation);
+        render_area(area, origin, placement.post_concat(transform), &ramp, self.dither, |x, y| {
+            let angle = (y - self.center.y).atan2(x - self.center.x).to_degrees() - self.start_angle;
+            Some(angle.rem_euclid(360.0) / 360.0)
+        })
+    }
+}
+
+/// Render a gradient as premultiplied pixels covering `area` of a pixmap
+///
+/// `to_device` maps gradient space to the pixmap, so each pixel center is mapped back and
+/// placed along the stops by `position_at`, which gives `None` where the gradient is undefined.
+/// Dither patterns are aligned to the device, whose pixel the pixmap's top left is at `origin`.
+fn render_area(
+    area: tiny_skia::IntRect,
+    origin: (u32, u32),
+    to_device: Transform,
+    ramp: &Ramp,
+    dither: Dither,
+    position_at: impl Fn(f32, f32) -> Option<f32>,
+) -> Option<tiny_skia::Pixmap> {
+    let inverse = to_device.invert()?;
+    let mut pixmap = tiny_skia::Pixmap::new(area.width(), area.height())?;
+    let width = area.width() as usize;
+    for (index, pixel) in pixmap.pixels_mut().iter_mut().enumerate() {
+        let (x, y) = (area.x() as u32 + (index % width) as u32, area.y() as u32 + (index / width) as u32);
+        let mut point = [tiny_skia::Point::from_xy(x as f32 + 0.5, y as f32 + 0.5)];
+        inverse.map_points(&mut point);
+        let Some(position) = position_at(point[0].x, point[0].y) else {
+            continue;
+        };
+
+        let [r, g, b, a] = ramp.sample(position);
+        let offset = dither.offset(origin.0 + x, origin.1 + y);
+        let alpha = (a * 255.0 + offset).round().clamp(0.0, 255.0);
+        let channel = |c: f32| (c * a * 255.0 + offset).round().clamp(0.0, alpha) as u8;
+        if let Some(color) = tiny_skia::PremultipliedColorU8::from_rgba(channel(r), channel(g), channel(b), alpha as u8) {
+            *pixel = color;
+        }
+    }
+    Some(pixmap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::{Layer, LayerId};
+
+    fn conic() -> Color {
+        Color::conic_gradient()
+            .center(20.0, 20.0)
+            .add_stop(0.0, Color::rgba(255, 0, 0, 255))
+            .add_stop(1.0, Color::rgba(0, 0, 255, 255))
+            .build()
+    }
+
+    #[test]
+    fn test_conic_sweeps_clockwise() {
+        let mut layer = Layer::new(LayerId::new(), "conic", 40, 40);
+        layer.draw_rect().position(0.0, 0.0).size(40.0, 40.0).fill(conic()).draw().unwrap();
+        let pixel = |x: u32, y: u32| layer.content.get_pixel(x, y).0;
+
+        // Just past the start, a quarter turn down, and half way round
+        assert!(pixel(35, 20)[0] > 250);
+        let quarter = pixel(20, 35);
+        assert!((quarter[0] as i32 - 191).abs() <= 3 && (quarter[2] as i32 - 64).abs() <= 3);
+        let half = pixel(4, 19);
+        assert!((half[0] as i32 - half[2] as i32).abs() <= 6);
+    }
+
+    #[test]
+    fn test_nan_stop_position_does_not_panic() {
+        let red = Color::rgba(255, 0, 0, 255);
+        Color::linear_gradient().add_stop(0.0, red.clone()).add_stop(f32::NAN, red.clone());
+        Color::radial_gradient().add_stop(f32::NAN, red.clone()).add_stop(1.0, red.clone());
+        Color::conic_gradient().add_stop(f32::NAN, red.clone()).add_stop(0.5, red);
+    }
+
+    #[test]
+    fn test_conic_follows_shape_transform() {
+        let mut layer = Layer::new(LayerId::new(), "conic", 40, 40);
+        layer.draw_rect()
+            .position(0.0, 0.0)
+            .size(40.0, 40.0)
+            .fill(conic())
+            .rotate(90.0)
+            .draw()
+            .unwrap();
+        // The start of the sweep now points down
+        assert!(layer.content.get_pixel(19, 35).0[0] > 250);
+    }
+
+    #[test]
+    fn test_linear_repeat_and_bounding_box_units() {
+        let stripes = Color::linear_gradient()
+            .start(0.0, 0.0)
+            .end(0.25, 0.0)
+            .add_stop(0.0, Color::rgba(0, 0, 0, 255))
+            .add_stop(1.0, Color::rgba(255, 255, 255, 255))
+            .spread(SpreadMode::Repeat)
+            .units(GradientUnits::ObjectBoundingBox)
+            .build();
+
+        // The same gradient repeats four times across each shape, whatever its size
+        let mut layer = Layer::new(LayerId::new(), "gradient", 100, 40);
+        layer.draw_rect().position(0.0, 0.0).size(40.0, 10.0).fill(stripes.clone()).draw().unwrap();
+        layer.draw_rect().position(0.0, 20.0).size(80.0, 10.0).fill(stripes).draw().unwrap();
+        let red = |x: u32, y: u32| layer.content.get_pixel(x, y).0[0] as i32;
+        assert!(red(0, 5) < 20 && red(10, 5) < 20 && red(9, 5) > 230);
+        assert!(red(0, 25) < 10 && red(20, 25) < 10 && red(19, 25) > 240);
+    }
+
+    #[test]
+    fn test_radial_focal_and_ellipse() {
+        let mut layer = Layer::new(LayerId::new(), "gradient", 40, 40);
+        let focal = Color::radial_gradient()
+            .center(20.0, 20.0)
+            .focal(10.0, 20.0)
+            .radius(20.0)
+            .add_stop(0.0, Color::rgba(255, 255, 255, 255))
+            .add_stop(1.0, Color::rgba(0, 0, 0, 255))
+            .build();
+        layer.draw_rect().position(0.0, 0.0).size(40.0, 40.0).fill(focal).draw().unwrap();
+        let red = |layer: &Layer, x: u32, y: u32| layer.content.get_pixel(x, y).0[0] as i32;
+        // Brightest at the focal point rather than the center
+        assert!(red(&layer, 10, 20) > red(&layer, 20, 20) && red(&layer, 20, 20) > red(&layer, 30, 20));
+
+        let ellipse = Color::radial_gradient()
+            .center(20.0, 20.0)
+            .radius(20.0)
+            .add_stop(0.0, Color::rgba(255, 255, 255, 255))
+            .add_stop(1.0, Color::rgba(0, 0, 0, 255))
+            .transform(crate::transform::Transform::new().scale(1.0, 0.5))
+            .build();
+        layer.draw_rect().position(0.0, 0.0).size(40.0, 40.0).fill(ellipse).draw().unwrap();
+        // Squashed vertically, so it fades twice as fast going down as going right
+        assert!((red(&layer, 30, 19) - red(&layer, 19, 25)).abs() <= 8);
+    }
+
+    #[test]
+    fn test_dithered_gradient_keeps_average() {
+        let dark = |dither: Dither| Color::linear_gradient()
+            .start(0.0, 0.0)
+            .end(64.0, 0.0)
+            .add_stop(0.0, Color::rgba(0, 0, 0, 255))
+            .add_stop(1.0, Color::rgba(8, 8, 8, 255))
+            .dither(dither)
+            .build();
+        let mut layer = Layer::new(LayerId::new(), "gradient", 64, 16);
+        layer.draw_rect().position(0.0, 0.0).size(64.0, 8.0).fill(dark(Dither::Ordered)).draw().unwrap();
+        layer.draw_rect().position(0.0, 8.0).size(64.0, 8.0).fill(dark(Dither::BlueNoise)).draw().unwrap();
+
+        for top in [0, 8] {
+            // Each 8x8 block averages out to the exact gradient value, not a rounded step
+            for block in 0..8 {
+                let sum: u32 = (0..8)
+                    .flat_map(|y| (0..8).map(move |x| (block * 8 + x, top + y)))
+                    .map(|(x, y)| layer.content.get_pixel(x, y).0[0] as u32)
+                    .sum();
+                let expected = 8.0 * (block as f32 * 8.0 + 4.0) / 64.0;
+                assert!((sum as f32 / 64.0 - expected).abs() < 0.35, "block {} averages {}", block, sum as f32 / 64.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_oklab_gradient_shader() {
+        let mut layer = Layer::new(LayerId::new(), "gradient", 64, 4);
+        let gradient = Color::linear_gradient()
+            .start(0.0, 0.0)
+            .end(64.0, 0.0)
+            .add_stop(0.0, Color::rgba(0, 0, 255, 255))
+            .add_stop(1.0, Color::rgba(255, 255, 0, 255))
+            .interpolation(InterpolationSpace::Oklab)
+            .build();
+        layer.draw_rect().position(0.0, 0.0).size(64.0, 4.0).fill(gradient).draw().unwrap();
+        // Mid blue-to-yellow in OKLab is a light teal rather than the sRGB (128, 128, 128)
+        let middle = layer.content.get_pixel(32, 2).0;
+        assert!(middle[1] > 150 && middle[2] > 180);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::color::conversions::conversions::{hsl_to_rgb, rgb_to_hsl};
 use crate::error::{Result, CloveError};
 
 /// HSLA color with floating-point components
@@ -40,32 +41,17 @@ impl Hsla {
         Ok(Self { h, s, l, a })
     }
     
+    /// Convert from RGBA
+    pub fn from_rgba(rgba: crate::color::Rgba) -> Self {
+        let (r, g, b, a) = rgba.as_f32();
+        let (h, s, l) = rgb_to_hsl(r, g, b);
+        Self { h, s, l, a }
+    }
+    
     /// Convert to RGBA
     pub fn to_rgba(&self) -> crate::color::Rgba {
-        let c = (1.0 - (2.0 * self.l - 1.0).abs()) * self.s;
-        let x = c * (1.0 - ((self.h / 60.0) % 2.0 - 1.0).abs());
-        let m = self.l - c / 2.0;
-        
-        let (r, g, b) = if self.h < 60.0 {
-            (c, x, 0.0)
-        } else if self.h < 120.0 {
-            (x, c, 0.0)
-        } else if self.h < 180.0 {
-            (0.0, c, x)
-        } else if self.h < 240.0 {
-            (0.0, x, c)
-        } else if self.h < 300.0 {
-            (x, 0.0, c)
-        } else {
-            (c, 0.0, x)
-        };
-        
-        crate::color::Rgba::new(
-            ((r + m) * 255.0) as u8,
-            ((g + m) * 255.0) as u8,
-            ((b + m) * 255.0) as u8,
-            (self.a * 255.0) as u8,
-        )
+        let (r, g, b) = hsl_to_rgb(self.h, self.s, self.l);
+        crate::color::Rgba::from_f32(r, g, b, self.a)
     }
 }
 
@@ -0,0 +1,58 @@
+use crate::color::conversions::conversions::{hsv_to_rgb, rgb_to_hsv};
+use crate::color::{check_component, Rgba};
+use crate::error::Result;
+
+/// HSVA color with floating-point components
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsva {
+    pub h: f32, // Hue: 0-360
+    pub s: f32, // Saturation: 0-1
+    pub v: f32, // Value: 0-1
+    pub a: f32, // Alpha: 0-1
+}
+
+impl Hsva {
+    /// Create new HSVA color
+    pub fn new(h: f32, s: f32, v: f32, a: f32) -> Result<Self> {
+        check_component("hue", h, 0.0..=360.0)?;
+        check_component("saturation", s, 0.0..=1.0)?;
+        check_component("value", v, 0.0..=1.0)?;
+        check_component("alpha", a, 0.0..=1.0)?;
+        Ok(Self { h, s, v, a })
+    }
+    
+    /// Convert from RGBA
+    pub fn from_rgba(rgba: Rgba) -> Self {
+        let (r, g, b, a) = rgba.as_f32();
+        let (h, s, v) = rgb_to_hsv(r, g, b);
+        Self { h, s, v, a }
+    }
+    
+    /// Convert to RGBA
+    pub fn to_rgba(&self) -> Rgba {
+        let (r, g, b) = hsv_to_rgb(self.h, self.s, self.v);
+        Rgba::from_f32(r, g, b, self.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Hsla;
+    
+    #[test]
+    fn test_hsv_and_hsl_round_trip() {
+        assert_eq!(Hsva::from_rgba(Rgba::rgb(0, 128, 0)), Hsva { h: 120.0, s: 1.0, v: 128.0 / 255.0, a: 1.0 });
+        assert!(Hsva::new(400.0, 0.5, 0.5, 1.0).is_err());
+        
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgba = Rgba::new(r, g, b, 255);
+                    assert_eq!(Hsla::from_rgba(rgba).to_rgba(), rgba);
+                    assert_eq!(Hsva::from_rgba(rgba).to_rgba(), rgba);
+                }
+            }
+        }
+    }
+}
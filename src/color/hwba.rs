@@ -0,0 +1,56 @@
+use crate::color::conversions::conversions::{hwb_to_rgb, rgb_to_hwb};
+use crate::color::{check_component, Rgba};
+use crate::error::Result;
+
+/// HWBA color with floating-point components
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hwba {
+    pub h: f32, // Hue: 0-360
+    pub w: f32, // Whiteness: 0-1
+    pub b: f32, // Blackness: 0-1
+    pub a: f32, // Alpha: 0-1
+}
+
+impl Hwba {
+    /// Create new HWBA color
+    pub fn new(h: f32, w: f32, b: f32, a: f32) -> Result<Self> {
+        check_component("hue", h, 0.0..=360.0)?;
+        check_component("whiteness", w, 0.0..=1.0)?;
+        check_component("blackness", b, 0.0..=1.0)?;
+        check_component("alpha", a, 0.0..=1.0)?;
+        Ok(Self { h, w, b, a })
+    }
+    
+    /// Convert from RGBA
+    pub fn from_rgba(rgba: Rgba) -> Self {
+        let (r, g, b, a) = rgba.as_f32();
+        let (h, w, b) = rgb_to_hwb(r, g, b);
+        Self { h, w, b, a }
+    }
+    
+    /// Convert to RGBA
+    pub fn to_rgba(&self) -> Rgba {
+        let (r, g, b) = hwb_to_rgb(self.h, self.w, self.b);
+        Rgba::from_f32(r, g, b, self.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_hwb_round_trip() {
+        // Whiteness and blackness summing past 1 give gray
+        assert_eq!(Hwba::new(0.0, 0.6, 0.6, 1.0).unwrap().to_rgba(), Rgba::rgb(128, 128, 128));
+        assert!(Hwba::new(0.0, 1.5, 0.0, 1.0).is_err());
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgba = Rgba::new(r, g, b, 255);
+                    assert_eq!(Hwba::from_rgba(rgba).to_rgba(), rgba);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use crate::color::conversions::conversions::{hsl_to_rgb, linear_srgb_to_oklab, linear_to_srgb, oklab_to_linear_srgb, rgb_to_hsl, srgb_to_linear};
+use crate::color::GradientStop;
+use std::sync::OnceLock;
+
+/// Color space gradient stops are blended in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum InterpolationSpace {
+    /// Blend encoded sRGB values, as browsers do by default
+    #[default]
+    Srgb,
+    /// Blend physical light intensities, keeping mixes bright
+    LinearSrgb,
+    /// Perceptually uniform, avoiding the gray middle of complementary colors
+    Oklab,
+    /// OKLab lightness and chroma with hue taken around the color wheel
+    Oklch(HueDirection),
+    /// Hue, saturation and lightness with hue taken around the color wheel
+    Hsl(HueDirection),
+}
+
+/// Way around the color wheel between two hues
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HueDirection {
+    /// At most half a turn
+    #[default]
+    Shorter,
+    /// At least half a turn
+    Longer,
+    /// Clockwise, with hue increasing
+    Increasing,
+    /// Counterclockwise, with hue decreasing
+    Decreasing,
+}
+
+/// Curve the blend takes between one stop and the next
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Noise added before rounding to 8 bits, hiding bands in smooth gradients
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Dither {
+    #[default]
+    None,
+    /// 8x8 Bayer matrix, regular and cheap
+    Ordered,
+    /// Blue noise, with no visible grid
+    BlueNoise,
+}
+
+impl Dither {
+    /// Offset in 8-bit steps for device pixel `(x, y)`, in -0.5..0.5
+    pub(crate) fn offset(self, x: u32, y: u32) -> f32 {
+        match self {
+            Dither::None => 0.0,
+            Dither::Ordered => {
+                let (x, y) = (x % 8, y % 8);
+                // Interleave bits of x ^ y and y to index the Bayer matrix
+                let (a, b) = (x ^ y, y);
+                let rank = ((a & 1) << 5) | ((b & 1) << 4) | ((a & 2) << 2) | ((b & 2) << 1) | ((a & 4) >> 1) | ((b & 4) >> 2);
+                (rank as f32 + 0.5) / 64.0 - 0.5
+            }
+            Dither::BlueNoise => {
+                let noise = blue_noise();
+                let index = (y % NOISE_SIZE) * NOISE_SIZE + x % NOISE_SIZE;
+                (noise[index as usize] as f32 + 0.5) / (NOISE_SIZE * NOISE_SIZE) as f32 - 0.5
+            }
+        }
+    }
+}
+
+/// Samples per stop-to-stop span when approximating a curved blend with sRGB stops
+const SPAN_SAMPLES: usize = 16;
+
+/// One stop converted into the blending space
+#[derive(Debug, Clone)]
+struct RampStop {
+    position: f32,
+    components: [f32; 3],
+    alpha: f32,
+    midpoint: f32,
+    easing: Easing,
+}
+
+/// Gradient stops prepared for sampling in an interpolation space
+#[derive(Debug, Clone)]
+pub(crate) struct Ramp {
+    stops: Vec<RampStop>,
+    space: InterpolationSpace,
+}
+
+impl Ramp {
+    pub(crate) fn new(stops: &[GradientStop], space: InterpolationSpace) -> Self {
+        let stops = stops.iter()
+            .map(|stop| {
+                let rgba = stop.color.to_rgba();
+                let (r, g, b) = (rgba.r as f32 / 255.0, rgba.g as f32 / 255.0, rgba.b as f32 / 255.0);
+                RampStop {
+                    position: stop.position.clamp(0.0, 1.0),
+                    components: to_space(space, [r, g, b]),
+                    alpha: rgba.a as f32 / 255.0,
+                    midpoint: stop.midpoint.clamp(0.001, 0.999),
+                    easing: stop.easing,
+                }
+            })
+            .collect();
+        Self { stops, space }
+    }
+
+    /// Whether tiny-skia's own sRGB blend between the stops already matches
+    fn is_plain(&self) -> bool {
+        self.space == InterpolationSpace::Srgb
+            && self.stops.iter().all(|stop| stop.easing == Easing::Linear && stop.midpoint == 0.5)
+    }
+
+    /// Straight-alpha sRGB color at `position`, components in 0.0-1.0
+    pub(crate) fn sample(&self, position: f32) -> [f32; 4] {
+        let after = self.stops.iter().position(|stop| stop.position > position);
+        let (from, to) = match after {
+            None if self.stops.is_empty() => return [0.0; 4],
+            Some(0) => return self.color_of(&self.stops[0]),
+            None => return self.color_of(&self.stops[self.stops.len() - 1]),
+            Some(index) => (&self.stops[index - 1], &self.stops[index]),
+        };
+
+        let mut t = (position - from.position) / (to.position - from.position);
+        if from.midpoint != 0.5 {
+            t = t.powf(0.5f32.ln() / from.midpoint.ln());
+        }
+        let t = from.easing.apply(t);
+
+        let (mut start, mut end) = (from.components, to.components);
+        if let Some(direction) = self.hue_direction() {
+            // A gray has no hue of its own, so it takes the other end's
+            if is_achromatic(from) {
+                start[2] = end[2];
+            } else if is_achromatic(to) {
+                end[2] = start[2];
+            } else {
+                end[2] = start[2] + hue_delta(start[2], end[2], direction);
+            }
+        }
+        self.mix(&start, &end, from.alpha, to.alpha, t)
+    }
+
+    fn mix(&self, from: &[f32; 3], to: &[f32; 3], from_alpha: f32, to_alpha: f32, t: f32) -> [f32; 4] {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let components = [lerp(from[0], to[0]), lerp(from[1], to[1]), lerp(from[2], to[2])];
+        let [r, g, b] = from_space(self.space, components);
+        [r, g, b, lerp(from_alpha, to_alpha)]
+    }
+
+    fn color_of(&self, stop: &RampStop) -> [f32; 4] {
+        let [r, g, b] = from_space(self.space, stop.components);
+        [r, g, b, stop.alpha]
+    }
+
+    fn hue_direction(&self) -> Option<HueDirection> {
+        match self.space {
+            InterpolationSpace::Oklch(direction) | InterpolationSpace::Hsl(direction) => Some(direction),
+            _ => None,
+        }
+    }
+
+    /// Stops for a tiny-skia shader, adding samples along curved blends
+    pub(crate) fn skia_stops(&self) -> Vec<tiny_skia::GradientStop> {
+        let to_skia = |position: f32, [r, g, b, a]: [f32; 4]| {
+            let color = tiny_skia::Color::from_rgba(r, g, b, a).unwrap_or(tiny_skia::Color::TRANSPARENT);
+            tiny_skia::GradientStop::new(position, color)
+        };
+        if self.is_plain() {
+            return self.stops.iter().map(|stop| to_skia(stop.position, self.color_of(stop))).collect();
+        }
+
+        let mut stops = Vec::new();
+        for (index, stop) in self.stops.iter().enumerate() {
+            stops.push(to_skia(stop.position, self.color_of(stop)));
+            let Some(next) = self.stops.get(index + 1) else {
+                continue;
+            };
+            let span = next.position - stop.position;
+            if span <= 0.0 {
+                continue;
+            }
+            for step in 1..SPAN_SAMPLES {
+                let position = stop.position + span * step as f32 / SPAN_SAMPLES as f32;
+                stops.push(to_skia(position, self.sample(position)));
+            }
+        }
+        stops
+    }
+}
+
+fn to_space(space: InterpolationSpace, [r, g, b]: [f32; 3]) -> [f32; 3] {
+    match space {
+        InterpolationSpace::Srgb => [r, g, b],
+        InterpolationSpace::LinearSrgb => [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b)],
+        InterpolationSpace::Oklab => {
+            let (l, a, b) = linear_srgb_to_oklab(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+            [l, a, b]
+        }
+        InterpolationSpace::Oklch(_) => {
+            let (l, a, b) = linear_srgb_to_oklab(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+            [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+        }
+        InterpolationSpace::Hsl(_) => {
+            // Hue last so every hue space keeps it in the same slot
+            let (h, s, l) = rgb_to_hsl(r, g, b);
+            [l, s, h.rem_euclid(360.0)]
+        }
+    }
+}
+
+fn from_space(space: InterpolationSpace, [x, y, z]: [f32; 3]) -> [f32; 3] {
+    let (r, g, b) = match space {
+        InterpolationSpace::Srgb => (x, y, z),
+        InterpolationSpace::LinearSrgb => (linear_to_srgb(x), linear_to_srgb(y), linear_to_srgb(z)),
+        InterpolationSpace::Oklab => encode(oklab_to_linear_srgb(x, y, z)),
+        InterpolationSpace::Oklch(_) => {
+            let hue = z.to_radians();
+            encode(oklab_to_linear_srgb(x, y * hue.cos(), y * hue.sin()))
+        }
+        InterpolationSpace::Hsl(_) => hsl_to_rgb(z.rem_euclid(360.0), y, x),
+    };
+    [r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0)]
+}
+
+fn encode((r, g, b): (f32, f32, f32)) -> (f32, f32, f32) {
+    (linear_to_srgb(r.max(0.0)), linear_to_srgb(g.max(0.0)), linear_to_srgb(b.max(0.0)))
+}
+
+/// Check if a stop has no chroma, so its hue is meaningless
+fn is_achromatic(stop: &RampStop) -> bool {
+    stop.components[1] < 1e-4
+}
+
+/// Signed change from one hue to another in degrees, going the given way round
+fn hue_delta(from: f32, to: f32, direction: HueDirection) -> f32 {
+    let delta = to - from;
+    match direction {
+        HueDirection::Shorter if delta > 180.0 => delta - 360.0,
+        HueDirection::Shorter if delta < -180.0 => delta + 360.0,
+        HueDirection::Longer if delta > 0.0 && delta < 180.0 => delta - 360.0,
+        HueDirection::Longer if delta <= 0.0 && delta > -180.0 => delta + 360.0,
+        HueDirection::Increasing if delta < 0.0 => delta + 360.0,
+        HueDirection::Decreasing if delta > 0.0 => delta - 360.0,
+        _ => delta,
+    }
+}
+
+/// Edge of the square blue noise tile
+const NOISE_SIZE: u32 = 32;
+
+/// Threshold ranks 0..NOISE_SIZE² laid out as blue noise, built once by void-and-cluster
+fn blue_noise() -> &'static [u16] {
+    static NOISE: OnceLock<Vec<u16>> = OnceLock::new();
+    NOISE.get_or_init(|| {
+        let size = NOISE_SIZE as usize;
+        let count = size * size;
+        // Toroidal Gaussian energy each set pixel spreads to the others
+        let kernel: Vec<f32> = (0..count)
+            .map(|index| {
+                let wrap = |d: usize| d.min(size - d) as f32;
+                let (dx, dy) = (wrap(index % size), wrap(index / size));
+                (-(dx * dx + dy * dy) / (2.0 * 1.5 * 1.5)).exp()
+            })
+            .collect();
+        let mut energy = vec![0.0f32; count];
+        let mut set = vec![false; count];
+        let toggle = |set: &mut Vec<bool>, energy: &mut Vec<f32>, index: usize| {
+            set[index] = !set[index];
+            let sign = if set[index] { 1.0 } else { -1.0 };
+            let (x, y) = (index % size, index / size);
+            for (other, value) in energy.iter_mut().enumerate() {
+                let dx = (other % size + size - x) % size;
+                let dy = (other / size + size - y) % size;
+                *value += sign * kernel[dy * size + dx];
+            }
+        };
+        let tightest = |set: &[bool], energy: &[f32]| (0..count).filter(|&i| set[i]).max_by(|&a, &b| energy[a].total_cmp(&energy[b]));
+        let largest_void = |set: &[bool], energy: &[f32]| (0..count).filter(|&i| !set[i]).min_by(|&a, &b| energy[a].total_cmp(&energy[b]));
+
+        // Seed a tenth of the pixels with a fixed pseudo-random pattern, then even it out
+        let mut state = 0x2545f491u32;
+        let mut seeded = 0;
+        while seeded < count / 10 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let index = state as usize % count;
+            if !set[index] {
+                toggle(&mut set, &mut energy, index);
+                seeded += 1;
+            }
+        }
+        loop {
+            let cluster = tightest(&set, &energy).expect("seeded pixels");
+            toggle(&mut set, &mut energy, cluster);
+            let void = largest_void(&set, &energy).expect("unset pixels");
+            if void == cluster {
+                toggle(&mut set, &mut energy, cluster);
+                break;
+            }
+            toggle(&mut set, &mut energy, void);
+        }
+
+        let mut ranks = vec![0u16; count];
+        let (initial_set, initial_energy) = (set.clone(), energy.clone());
+        // Seed pixels rank below the seed count, tightest clusters highest
+        for rank in (0..seeded).rev() {
+            let cluster = tightest(&set, &energy).expect("seeded pixels");
+            ranks[cluster] = rank as u16;
+            toggle(&mut set, &mut energy, cluster);
+        }
+        // The rest fill the largest remaining voids in order
+        let (mut set, mut energy) = (initial_set, initial_energy);
+        for rank in seeded..count {
+            let void = largest_void(&set, &energy).expect("unset pixels");
+            ranks[void] = rank as u16;
+            toggle(&mut set, &mut energy, void);
+        }
+        ranks
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+
+    fn ramp(space: InterpolationSpace) -> Ramp {
+        let stops = [
+            GradientStop::new(Color::rgba(0, 0, 255, 255), 0.0),
+            GradientStop::new(Color::rgba(255, 255, 0, 255), 1.0),
+        ];
+        Ramp::new(&stops, space)
+    }
+
+    #[test]
+    fn test_blue_to_yellow_spaces() {
+        // Plain sRGB passes through gray, OKLab stays light and OKLCH keeps chroma
+        let [r, g, b, _] = ramp(InterpolationSpace::Srgb).sample(0.5);
+        assert!((r - 0.5).abs() < 1e-3 && (g - 0.5).abs() < 1e-3 && (b - 0.5).abs() < 1e-3);
+        let oklab = ramp(InterpolationSpace::Oklab).sample(0.5);
+        assert!(oklab[1] > 0.6 && oklab[2] > 0.7);
+        let oklch = ramp(InterpolationSpace::Oklch(HueDirection::Shorter)).sample(0.5);
+        let spread = |c: [f32; 4]| c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2]);
+        assert!(spread(oklch) > spread(oklab) + 0.2);
+    }
+
+    #[test]
+    fn test_hue_direction_and_midpoint() {
+        let stops = [
+            GradientStop::new(Color::rgba(255, 0, 0, 255), 0.0),
+            GradientStop::new(Color::rgba(0, 255, 0, 255), 1.0),
+        ];
+        // Red to green through yellow one way and through blue and magenta the other
+        let shorter = Ramp::new(&stops, InterpolationSpace::Hsl(HueDirection::Shorter)).sample(0.5);
+        assert!(shorter[0] > 0.9 && shorter[1] > 0.9 && shorter[2] < 0.1);
+        let longer = Ramp::new(&stops, InterpolationSpace::Hsl(HueDirection::Longer)).sample(0.5);
+        assert!(longer[2] > 0.9 && longer[1] < 0.1);
+
+        let hinted = [stops[0].clone().midpoint(0.2), stops[1].clone()];
+        let at_hint = Ramp::new(&hinted, InterpolationSpace::Srgb).sample(0.2);
+        assert!((at_hint[0] - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_dither_offsets_are_balanced() {
+        for dither in [Dither::Ordered, Dither::BlueNoise] {
+            let size = if dither == Dither::Ordered { 8 } else { NOISE_SIZE };
+            let mut offsets: Vec<f32> = (0..size * size).map(|i| dither.offset(i % size, i / size)).collect();
+            offsets.sort_by(f32::total_cmp);
+            // Every threshold appears exactly once
+            for (rank, offset) in offsets.iter().enumerate() {
+                assert!((offset - ((rank as f32 + 0.5) / (size * size) as f32 - 0.5)).abs() < 1e-6);
+            }
+        }
+    }
+}
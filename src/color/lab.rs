@@ -0,0 +1,105 @@
+use crate::color::conversions::conversions::{ab_to_chroma_hue, chroma_hue_to_ab, lab_to_linear_srgb, linear_srgb_to_lab};
+use crate::color::{check_component, from_linear_srgb, to_linear_srgb, Rgba};
+use crate::error::Result;
+
+/// CIE Lab color, D50 white as in CSS
+///
+/// Colors outside the sRGB gamut are clipped when converted to RGBA.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub l: f32, // Lightness: 0-100
+    pub a: f32, // Green to red, about -125 to 125
+    pub b: f32, // Blue to yellow, about -125 to 125
+    pub alpha: f32, // Alpha: 0-1
+}
+
+impl Lab {
+    /// Create new Lab color
+    pub fn new(l: f32, a: f32, b: f32, alpha: f32) -> Result<Self> {
+        check_component("lightness", l, 0.0..=100.0)?;
+        check_component("alpha", alpha, 0.0..=1.0)?;
+        Ok(Self { l, a, b, alpha })
+    }
+    
+    /// Convert from RGBA
+    pub fn from_rgba(rgba: Rgba) -> Self {
+        let (r, g, b, alpha) = to_linear_srgb(rgba);
+        let (l, a, b) = linear_srgb_to_lab(r, g, b);
+        Self { l, a, b, alpha }
+    }
+    
+    /// Convert to RGBA
+    pub fn to_rgba(&self) -> Rgba {
+        let (r, g, b) = lab_to_linear_srgb(self.l, self.a, self.b);
+        from_linear_srgb(r, g, b, self.alpha)
+    }
+    
+    /// Convert to the polar form
+    pub fn to_lch(&self) -> Lch {
+        let (c, h) = ab_to_chroma_hue(self.a, self.b);
+        Lch { l: self.l, c, h, alpha: self.alpha }
+    }
+}
+
+/// CIE LCH color, the polar form of [`Lab`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lch {
+    pub l: f32, // Lightness: 0-100
+    pub c: f32, // Chroma: 0 up, about 150 at most in practice
+    pub h: f32, // Hue: 0-360
+    pub alpha: f32, // Alpha: 0-1
+}
+
+impl Lch {
+    /// Create new LCH color
+    pub fn new(l: f32, c: f32, h: f32, alpha: f32) -> Result<Self> {
+        check_component("lightness", l, 0.0..=100.0)?;
+        check_component("chroma", c, 0.0..=f32::MAX)?;
+        check_component("hue", h, 0.0..=360.0)?;
+        check_component("alpha", alpha, 0.0..=1.0)?;
+        Ok(Self { l, c, h, alpha })
+    }
+    
+    /// Convert from RGBA
+    pub fn from_rgba(rgba: Rgba) -> Self {
+        Lab::from_rgba(rgba).to_lch()
+    }
+    
+    /// Convert to RGBA
+    pub fn to_rgba(&self) -> Rgba {
+        self.to_lab().to_rgba()
+    }
+    
+    /// Convert to the rectangular form
+    pub fn to_lab(&self) -> Lab {
+        let (a, b) = chroma_hue_to_ab(self.c, self.h);
+        Lab { l: self.l, a, b, alpha: self.alpha }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_lab_matches_css_reference() {
+        let lab = Lab::from_rgba(Rgba::rgb(255, 0, 0));
+        assert!((lab.l - 54.29).abs() < 0.05 && (lab.a - 80.8).abs() < 0.1 && (lab.b - 69.89).abs() < 0.1, "{lab:?}");
+        let lch = lab.to_lch();
+        assert!((lch.c - 106.84).abs() < 0.1 && (lch.h - 40.85).abs() < 0.1, "{lch:?}");
+        assert_eq!(Lch::from_rgba(Rgba::rgb(128, 128, 128)).h, 0.0);
+    }
+    
+    #[test]
+    fn test_lab_and_lch_round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgba = Rgba::new(r, g, b, 200);
+                    assert_eq!(Lab::from_rgba(rgba).to_rgba(), rgba);
+                    assert_eq!(Lch::from_rgba(rgba).to_rgba(), rgba);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,485 @@
+use crate::color::{Color, Rgba};
+use crate::geometry::{Point, Rect};
+use tiny_skia::{IntRect, Pixmap, PremultipliedColorU8, Transform};
+
+/// Colored point of a mesh or freeform gradient
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshPoint {
+    pub position: Point,
+    pub color: Color,
+}
+
+/// Mesh gradient builder
+///
+/// Starts as a regular grid spread over `bounds`, every point transparent.
+#[derive(Debug, Clone)]
+pub struct MeshGradientBuilder {
+    columns: usize,
+    rows: usize,
+    bounds: Rect,
+    moved: Vec<(usize, usize, Point)>,
+    colors: Vec<(usize, usize, Color)>,
+}
+
+impl MeshGradientBuilder {
+    /// Grid of `columns` x `rows` points, at least 2 x 2
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns: columns.max(2),
+            rows: rows.max(2),
+            bounds: Rect::new(0.0, 0.0, 100.0, 100.0),
+            moved: Vec::new(),
+            colors: Vec::new(),
+        }
+    }
+
+    /// Area the grid is spread evenly over before any point is moved
+    pub fn bounds(mut self, x: f32, y: f32, width: f32, height: f32) -> Self {
+        self.bounds = Rect::new(x, y, width, height);
+        self
+    }
+
+    /// Set the color of the point at `column`, `row`
+    pub fn color(mut self, column: usize, row: usize, color: Color) -> Self {
+        self.colors.push((column, row, color));
+        self
+    }
+
+    /// Move the point at `column`, `row` to `(x, y)`, bending the patches around it
+    pub fn point(mut self, column: usize, row: usize, x: f32, y: f32) -> Self {
+        self.moved.push((column, row, Point::new(x, y)));
+        self
+    }
+
+    pub fn build(self) -> Color {
+        let (columns, rows) = (self.columns, self.rows);
+        let mut points: Vec<MeshPoint> = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (column, row)))
+            .map(|(column, row)| MeshPoint {
+                position: Point::new(
+                    self.bounds.x + self.bounds.width * column as f32 / (columns - 1) as f32,
+                    self.bounds.y + self.bounds.height * row as f32 / (rows - 1) as f32,
+                ),
+                color: Color::Rgba(Rgba::TRANSPARENT),
+            })
+            .collect();
+        let in_grid = |column: usize, row: usize| column < columns && row < rows;
+        for (column, row, position) in self.moved {
+            if in_grid(column, row) {
+                points[row * columns + column].position = position;
+            }
+        }
+        for (column, row, color) in self.colors {
+            if in_grid(column, row) {
+                points[row * columns + column].color = color;
+            }
+        }
+        Color::MeshGradient(MeshGradient { columns, rows, points })
+    }
+}
+
+/// Mesh gradient
+///
+/// A grid of colored points, each cell a Coons patch whose edges curve smoothly through
+/// neighboring points. Colors blend smoothly across patch borders as well as within them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshGradient {
+    pub columns: usize,
+    pub rows: usize,
+    /// Row by row, `columns * rows` points
+    pub points: Vec<MeshPoint>,
+}
+
+/// Pixels per subdivided patch cell when rasterizing
+const CELL_SIZE: f32 = 4.0;
+
+/// Most cells a patch is split into along each side
+const MAX_CELLS: usize = 64;
+
+impl MeshGradient {
+    fn point(&self, column: usize, row: usize) -> &MeshPoint {
+        let column = column.min(self.columns - 1);
+        let row = row.min(self.rows - 1);
+        &self.points[row * self.columns + column]
+    }
+
+    /// Average color of all points
+    pub(crate) fn average(&self) -> Rgba {
+        average(self.points.iter().map(|point| &point.color))
+    }
+
+    /// Cubic edge from one grid point to the next along a row or column
+    ///
+    /// Control points follow Catmull-Rom tangents so neighboring edges join smoothly.
+    fn edge(&self, column: usize, row: usize, horizontal: bool) -> [Point; 4] {
+        let at = |step: isize| {
+            let index = |base: usize| (base as isize + step).max(0) as usize;
+            let point = if horizontal {
+                self.point(index(column), row)
+            } else {
+                self.point(column, index(row))
+            };
+            point.position
+        };
+        let (before, from, to, after) = (at(-1), at(0), at(1), at(2));
+        [
+            from,
+            Point::new(from.x + (to.x - before.x) / 6.0, from.y + (to.y - before.y) / 6.0),
+            Point::new(to.x - (after.x - from.x) / 6.0, to.y - (after.y - from.y) / 6.0),
+            to,
+        ]
+    }
+
+    /// Straight-alpha color at grid coordinates, blended bicubically across patches
+    fn color_at(&self, x: f32, y: f32) -> [f32; 4] {
+        let (column, row) = (x.floor() as isize, y.floor() as isize);
+        let (u, v) = (x - column as f32, y - row as f32);
+        let color = |dx: isize, dy: isize| {
+            let c = (column + dx).clamp(0, self.columns as isize - 1) as usize;
+            let r = (row + dy).clamp(0, self.rows as isize - 1) as usize;
+            premultiplied(&self.point(c, r).color)
+        };
+        let mut result = [0.0; 4];
+        for (dy, wy) in (-1..=2).zip(catmull_rom_weights(v)) {
+            for (dx, wx) in (-1..=2).zip(catmull_rom_weights(u)) {
+                let sample = color(dx, dy);
+                for (channel, value) in result.iter_mut().zip(sample) {
+                    *channel += value * wx * wy;
+                }
+            }
+        }
+        result.map(|value| value.clamp(0.0, 1.0))
+    }
+
+    /// Render pixels covering `area` of a device, drawn with `transform`
+    pub(crate) fn render(&self, area: IntRect, transform: Transform) -> Option<Pixmap> {
+        if self.points.len() != self.columns * self.rows || self.columns < 2 || self.rows < 2 {
+            return None;
+        }
+        let mut target = Raster::new(area)?;
+        for row in 0..self.rows - 1 {
+            for column in 0..self.columns - 1 {
+                self.render_patch(column, row, transform, &mut target);
+            }
+        }
+        target.into_pixmap()
+    }
+
+    fn render_patch(&self, column: usize, row: usize, transform: Transform, target: &mut Raster) {
+        let top = self.edge(column, row, true);
+        let bottom = self.edge(column, row + 1, true);
+        let left = self.edge(column, row, false);
+        let right = self.edge(column + 1, row, false);
+        let corners = [top[0], top[3], bottom[0], bottom[3]];
+
+        let surface = |u: f32, v: f32| {
+            let (t, b, l, r) = (bezier(&top, u), bezier(&bottom, u), bezier(&left, v), bezier(&right, v));
+            let bilinear = |pick: fn(&Point) -> f32| {
+                let [p00, p10, p01, p11] = corners.map(|corner| pick(&corner));
+                (1.0 - u) * (1.0 - v) * p00 + u * (1.0 - v) * p10 + (1.0 - u) * v * p01 + u * v * p11
+            };
+            let mut point = [tiny_skia::Point::from_xy(
+                (1.0 - v) * t.x + v * b.x + (1.0 - u) * l.x + u * r.x - bilinear(|p| p.x),
+                (1.0 - v) * t.y + v * b.y + (1.0 - u) * l.y + u * r.y - bilinear(|p| p.y),
+            )];
+            transform.map_points(&mut point);
+            point[0]
+        };
+
+        // Split finely enough that each cell spans a few device pixels
+        let outline: f32 = [top, bottom, left, right].iter()
+            .map(|edge| {
+                let mut points = edge.map(|p| tiny_skia::Point::from_xy(p.x, p.y));
+                transform.map_points(&mut points);
+                points.windows(2).map(|pair| pair[0].distance(pair[1])).sum::<f32>()
+            })
+            .fold(0.0, f32::max);
+        let cells = ((outline / CELL_SIZE).ceil() as usize).clamp(1, MAX_CELLS);
+
+        let step = 1.0 / cells as f32;
+        let grid: Vec<(tiny_skia::Point, [f32; 4])> = (0..=cells)
+            .flat_map(|j| (0..=cells).map(move |i| (i as f32 * step, j as f32 * step)))
+            .map(|(u, v)| (surface(u, v), self.color_at(column as f32 + u, row as f32 + v)))
+            .collect();
+        let vertex = |i: usize, j: usize| grid[j * (cells + 1) + i];
+        for j in 0..cells {
+            for i in 0..cells {
+                let (a, b, c, d) = (vertex(i, j), vertex(i + 1, j), vertex(i, j + 1), vertex(i + 1, j + 1));
+                target.triangle(a, b, d);
+                target.triangle(a, d, c);
+            }
+        }
+    }
+}
+
+/// Freeform gradient builder
+#[derive(Debug, Clone)]
+pub struct FreeformGradientBuilder {
+    points: Vec<MeshPoint>,
+    power: f32,
+}
+
+impl FreeformGradientBuilder {
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            power: 2.0,
+        }
+    }
+
+    pub fn add_point(mut self, x: f32, y: f32, color: Color) -> Self {
+        self.points.push(MeshPoint { position: Point::new(x, y), color });
+        self
+    }
+
+    /// How quickly a point's influence falls off with distance; higher keeps colors more distinct
+    pub fn power(mut self, power: f32) -> Self {
+        self.power = power.max(0.1);
+        self
+    }
+
+    pub fn build(self) -> Color {
+        Color::FreeformGradient(FreeformGradient {
+            points: self.points,
+            power: self.power,
+        })
+    }
+}
+
+impl Default for FreeformGradientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Freeform gradient
+///
+/// Colors blend from scattered points, each pixel weighted towards the points nearest it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeformGradient {
+    pub points: Vec<MeshPoint>,
+    /// Falloff exponent of each point's influence with distance
+    pub power: f32,
+}
+
+impl FreeformGradient {
+    /// Average color of all points
+    pub(crate) fn average(&self) -> Rgba {
+        average(self.points.iter().map(|point| &point.color))
+    }
+
+    /// Render pixels covering `area` of a device, drawn with `transform`
+    pub(crate) fn render(&self, area: IntRect, transform: Transform) -> Option<Pixmap> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let inverse = transform.invert()?;
+        let colors: Vec<[f32; 4]> = self.points.iter().map(|point| premultiplied(&point.color)).collect();
+        let mut target = Raster::new(area)?;
+        let width = area.width() as usize;
+        for index in 0..target.pixels.len() {
+            let mut point = [tiny_skia::Point::from_xy(
+                area.x() as f32 + (index % width) as f32 + 0.5,
+                area.y() as f32 + (index / width) as f32 + 0.5,
+            )];
+            inverse.map_points(&mut point);
+
+            let mut total = [0.0f32; 4];
+            let mut weights = 0.0;
+            for (mesh_point, color) in self.points.iter().zip(&colors) {
+                let (dx, dy) = (point[0].x - mesh_point.position.x, point[0].y - mesh_point.position.y);
+                let distance_squared = dx * dx + dy * dy;
+                if distance_squared < 1e-6 {
+                    total = *color;
+                    weights = 1.0;
+                    break;
+                }
+                let weight = distance_squared.powf(-self.power / 2.0);
+                for (channel, value) in total.iter_mut().zip(color) {
+                    *channel += value * weight;
+                }
+                weights += weight;
+            }
+            target.pixels[index] = Some(total.map(|channel| channel / weights));
+        }
+        target.into_pixmap()
+    }
+}
+
+/// Premultiplied float pixels for part of a device, unset outside the gradient
+struct Raster {
+    area: IntRect,
+    pixels: Vec<Option<[f32; 4]>>,
+}
+
+impl Raster {
+    fn new(area: IntRect) -> Option<Self> {
+        let count = area.width() as usize * area.height() as usize;
+        (count > 0).then(|| Self { area, pixels: vec![None; count] })
+    }
+
+    /// Fill the pixel centers inside a triangle, blending its vertex colors
+    fn triangle(&mut self, a: (tiny_skia::Point, [f32; 4]), b: (tiny_skia::Point, [f32; 4]), c: (tiny_skia::Point, [f32; 4])) {
+        let (pa, pb, pc) = (a.0, b.0, c.0);
+        let area = (pb.x - pa.x) * (pc.y - pa.y) - (pc.x - pa.x) * (pb.y - pa.y);
+        if area.abs() < 1e-9 {
+            return;
+        }
+        let (left, top) = (self.area.x() as f32, self.area.y() as f32);
+        let min_x = (pa.x.min(pb.x).min(pc.x) - left - 0.5).ceil().max(0.0) as u32;
+        let max_x = (pa.x.max(pb.x).max(pc.x) - left - 0.5).floor().min(self.area.width() as f32 - 1.0);
+        let min_y = (pa.y.min(pb.y).min(pc.y) - top - 0.5).ceil().max(0.0) as u32;
+        let max_y = (pa.y.max(pb.y).max(pc.y) - top - 0.5).floor().min(self.area.height() as f32 - 1.0);
+        if max_x < 0.0 || max_y < 0.0 {
+            return;
+        }
+
+        // Small slack so pixel centers on shared edges aren't dropped by rounding
+        let slack = -1e-4;
+        for y in min_y..=max_y as u32 {
+            for x in min_x..=max_x as u32 {
+                let (px, py) = (left + x as f32 + 0.5, top + y as f32 + 0.5);
+                let wa = ((pb.x - px) * (pc.y - py) - (pc.x - px) * (pb.y - py)) / area;
+                let wb = ((pc.x - px) * (pa.y - py) - (pa.x - px) * (pc.y - py)) / area;
+                let wc = 1.0 - wa - wb;
+                if wa < slack || wb < slack || wc < slack {
+                    continue;
+                }
+                let mut color = [0.0; 4];
+                for (channel, ((va, vb), vc)) in color.iter_mut().zip(a.1.iter().zip(&b.1).zip(&c.1)) {
+                    *channel = va * wa + vb * wb + vc * wc;
+                }
+                self.pixels[(y * self.area.width() + x) as usize] = Some(color);
+            }
+        }
+    }
+
+    fn into_pixmap(self) -> Option<Pixmap> {
+        let mut pixmap = Pixmap::new(self.area.width(), self.area.height())?;
+        for (pixel, color) in pixmap.pixels_mut().iter_mut().zip(self.pixels) {
+            let Some([r, g, b, a]) = color else {
+                continue;
+            };
+            let alpha = (a.clamp(0.0, 1.0) * 255.0).round();
+            let channel = |c: f32| (c * 255.0).round().clamp(0.0, alpha) as u8;
+            if let Some(color) = PremultipliedColorU8::from_rgba(channel(r), channel(g), channel(b), alpha as u8) {
+                *pixel = color;
+            }
+        }
+        Some(pixmap)
+    }
+}
+
+fn bezier(points: &[Point; 4], t: f32) -> Point {
+    let s = 1.0 - t;
+    let (w0, w1, w2, w3) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+    Point::new(
+        w0 * points[0].x + w1 * points[1].x + w2 * points[2].x + w3 * points[3].x,
+        w0 * points[0].y + w1 * points[1].y + w2 * points[2].y + w3 * points[3].y,
+    )
+}
+
+/// Weights of the four samples around `t` for Catmull-Rom interpolation
+fn catmull_rom_weights(t: f32) -> [f32; 4] {
+    let (t2, t3) = (t * t, t * t * t);
+    [
+        (-t3 + 2.0 * t2 - t) / 2.0,
+        (3.0 * t3 - 5.0 * t2 + 2.0) / 2.0,
+        (-3.0 * t3 + 4.0 * t2 + t) / 2.0,
+        (t3 - t2) / 2.0,
+    ]
+}
+
+fn premultiplied(color: &Color) -> [f32; 4] {
+    let rgba = color.to_rgba();
+    let alpha = rgba.a as f32 / 255.0;
+    let channel = |c: u8| c as f32 / 255.0 * alpha;
+    [channel(rgba.r), channel(rgba.g), channel(rgba.b), alpha]
+}
+
+fn average<'a>(colors: impl Iterator<Item = &'a Color>) -> Rgba {
+    let (mut total, mut count) = ([0.0f32; 4], 0);
+    for color in colors {
+        for (channel, value) in total.iter_mut().zip(premultiplied(color)) {
+            *channel += value;
+        }
+        count += 1;
+    }
+    if count == 0 || total[3] == 0.0 {
+        return Rgba::TRANSPARENT;
+    }
+    let channel = |value: f32| (value / total[3] * 255.0).round() as u8;
+    Rgba::new(channel(total[0]), channel(total[1]), channel(total[2]), (total[3] / count as f32 * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::{Layer, LayerId};
+
+    #[test]
+    fn test_mesh_gradient_blends_corners() {
+        let mesh = Color::mesh_gradient(2, 2)
+            .bounds(0.0, 0.0, 40.0, 40.0)
+            .color(0, 0, Color::rgba(255, 0, 0, 255))
+            .color(1, 0, Color::rgba(0, 255, 0, 255))
+            .color(0, 1, Color::rgba(0, 0, 255, 255))
+            .color(1, 1, Color::rgba(255, 255, 255, 255))
+            .build();
+        let mut layer = Layer::new(LayerId::new(), "mesh", 40, 40);
+        layer.fill(mesh).unwrap();
+
+        let pixel = |x: u32, y: u32| layer.content.get_pixel(x, y).0;
+        let [r, g, b, a] = pixel(0, 0);
+        assert!(r > 240 && g < 15 && b < 15 && a == 255);
+        let [r, g, b, _] = pixel(39, 39);
+        assert!(r > 240 && g > 240 && b > 240);
+        // The middle is an even mix of all four corners
+        let [r, g, b, _] = pixel(20, 20);
+        for channel in [r, g, b] {
+            assert!((110..=145).contains(&channel), "{:?}", pixel(20, 20));
+        }
+    }
+
+    #[test]
+    fn test_mesh_points_bend_patches() {
+        let mesh = Color::mesh_gradient(3, 2)
+            .bounds(0.0, 0.0, 40.0, 20.0)
+            .point(1, 0, 30.0, 0.0)
+            .point(1, 1, 30.0, 20.0)
+            .color(0, 0, Color::rgba(255, 0, 0, 255))
+            .color(0, 1, Color::rgba(255, 0, 0, 255))
+            .color(1, 0, Color::rgba(0, 0, 255, 255))
+            .color(1, 1, Color::rgba(0, 0, 255, 255))
+            .color(2, 0, Color::rgba(0, 0, 255, 255))
+            .color(2, 1, Color::rgba(0, 0, 255, 255))
+            .build();
+        let mut layer = Layer::new(LayerId::new(), "mesh", 40, 20);
+        layer.fill(mesh).unwrap();
+        // Moving the middle column right stretches the red side; on an even grid blue wins here
+        let [r, _, b, _] = layer.content.get_pixel(12, 10).0;
+        assert!(r > b);
+    }
+
+    #[test]
+    fn test_freeform_gradient_in_shape() {
+        let freeform = Color::freeform_gradient()
+            .add_point(5.0, 5.0, Color::rgba(255, 0, 0, 255))
+            .add_point(35.0, 35.0, Color::rgba(0, 0, 255, 255))
+            .build();
+        assert_eq!(freeform.to_rgba(), Rgba::new(128, 0, 128, 255));
+
+        let mut layer = Layer::new(LayerId::new(), "freeform", 40, 40);
+        layer.draw_circle()
+            .center(20.0, 20.0)
+            .radius(18.0)
+            .fill(freeform)
+            .draw()
+            .unwrap();
+        let [r, _, b, _] = layer.content.get_pixel(8, 8).0;
+        assert!(r > 200 && b < 55);
+        let [r, _, b, _] = layer.content.get_pixel(32, 32).0;
+        assert!(b > 200 && r < 55);
+        assert_eq!(layer.alpha_at(1.0, 1.0), 0);
+    }
+}
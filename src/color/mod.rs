@@ -1,15 +1,32 @@
 pub mod rgba;
 pub mod hsla;
+pub mod hsva;
+pub mod hwba;
+pub mod lab;
+pub mod oklab;
+pub mod cmyk;
 pub mod named;
 pub mod gradient;
 pub mod pattern;
+pub mod mesh;
+mod css;
+mod adjust;
+mod contrast;
+pub mod interpolation;
 pub mod conversions;
 
 pub use rgba::Rgba;
 pub use hsla::Hsla;
+pub use hsva::Hsva;
+pub use hwba::Hwba;
+pub use lab::{Lab, Lch};
+pub use oklab::{Oklab, Oklch};
+pub use cmyk::Cmyk;
 pub use named::NamedColor;
-pub use gradient::{LinearGradient, RadialGradient, LinearGradientBuilder, RadialGradientBuilder, GradientStop};
-pub use pattern::Pattern;
+pub use gradient::{LinearGradient, RadialGradient, ConicGradient, LinearGradientBuilder, RadialGradientBuilder, ConicGradientBuilder, GradientStop, GradientUnits, SpreadMode};
+pub use pattern::{Pattern, PatternMode};
+pub use mesh::{MeshGradient, MeshGradientBuilder, FreeformGradient, FreeformGradientBuilder, MeshPoint};
+pub use interpolation::{Dither, Easing, HueDirection, InterpolationSpace};
 
 use crate::error::Result;
 
@@ -18,9 +35,19 @@ use crate::error::Result;
 pub enum Color {
     Rgba(Rgba),
     Hsla(Hsla),
+    Hsva(Hsva),
+    Hwba(Hwba),
+    Lab(Lab),
+    Lch(Lch),
+    Oklab(Oklab),
+    Oklch(Oklch),
+    Cmyk(Cmyk),
     Named(NamedColor),
     LinearGradient(LinearGradient),
     RadialGradient(RadialGradient),
+    ConicGradient(ConicGradient),
+    MeshGradient(MeshGradient),
+    FreeformGradient(FreeformGradient),
     Pattern(Pattern),
 }
 
@@ -30,6 +57,17 @@ impl Color {
         Rgba::from_hex(hex).map(Color::Rgba)
     }
     
+    /// Parse any CSS color: hex, `rgb()`, `hsl()`, `hwb()`, `lab()`, `lch()`, `oklab()`, `oklch()`,
+    /// named colors and `transparent`
+    pub fn parse(css: &str) -> Result<Self> {
+        css::parse(css)
+    }
+    
+    /// Format as a CSS color string that [`Color::parse`] reads back
+    pub fn to_css_string(&self) -> String {
+        css::to_css_string(self)
+    }
+    
     /// Create color from named color enum
     pub fn named(name: NamedColor) -> Self {
         Color::Named(name)
@@ -50,11 +88,47 @@ impl Color {
         RadialGradientBuilder::new()
     }
     
+    /// Create conic gradient builder
+    pub fn conic_gradient() -> ConicGradientBuilder {
+        ConicGradientBuilder::new()
+    }
+    
+    /// Create mesh gradient builder for a grid of `columns` x `rows` points
+    pub fn mesh_gradient(columns: usize, rows: usize) -> MeshGradientBuilder {
+        MeshGradientBuilder::new(columns, rows)
+    }
+    
+    /// Create freeform gradient builder
+    pub fn freeform_gradient() -> FreeformGradientBuilder {
+        FreeformGradientBuilder::new()
+    }
+    
+    /// Create pattern fill
+    pub fn pattern(pattern: Pattern) -> Self {
+        Color::Pattern(pattern)
+    }
+    
+    /// Whether this is a single color rather than a gradient or pattern
+    pub fn is_solid(&self) -> bool {
+        !matches!(
+            self,
+            Color::LinearGradient(_) | Color::RadialGradient(_) | Color::ConicGradient(_)
+                | Color::MeshGradient(_) | Color::FreeformGradient(_) | Color::Pattern(_)
+        )
+    }
+    
     /// Convert to RGBA
     pub fn to_rgba(&self) -> Rgba {
         match self {
             Color::Rgba(rgba) => *rgba,
             Color::Hsla(hsla) => hsla.to_rgba(),
+            Color::Hsva(hsva) => hsva.to_rgba(),
+            Color::Hwba(hwba) => hwba.to_rgba(),
+            Color::Lab(lab) => lab.to_rgba(),
+            Color::Lch(lch) => lch.to_rgba(),
+            Color::Oklab(oklab) => oklab.to_rgba(),
+            Color::Oklch(oklch) => oklch.to_rgba(),
+            Color::Cmyk(cmyk) => cmyk.to_rgba(),
             Color::Named(named) => named.to_rgba(),
             Color::LinearGradient(grad) => grad.stops.first()
                 .map(|stop| stop.color.to_rgba())
@@ -62,11 +136,50 @@ impl Color {
             Color::RadialGradient(grad) => grad.stops.first()
                 .map(|stop| stop.color.to_rgba())
                 .unwrap_or(Rgba::TRANSPARENT),
+            Color::ConicGradient(grad) => grad.stops.first()
+                .map(|stop| stop.color.to_rgba())
+                .unwrap_or(Rgba::TRANSPARENT),
+            Color::MeshGradient(grad) => grad.average(),
+            Color::FreeformGradient(grad) => grad.average(),
             Color::Pattern(pattern) => pattern.color,
         }
     }
 }
 
+/// Error unless a color component lies in `range`
+pub(crate) fn check_component(component: &str, value: f32, range: std::ops::RangeInclusive<f32>) -> Result<()> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(crate::error::CloveError::InvalidColorValue {
+            component: component.to_string(),
+            value,
+        })
+    }
+}
+
+/// Linear-light channels of an RGBA color, alpha unchanged
+pub(crate) fn to_linear_srgb(rgba: Rgba) -> (f32, f32, f32, f32) {
+    use conversions::conversions::srgb_to_linear;
+    let (r, g, b, a) = rgba.as_f32();
+    (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a)
+}
+
+/// RGBA color from linear-light channels, clipping anything outside the sRGB gamut
+pub(crate) fn from_linear_srgb(r: f32, g: f32, b: f32, alpha: f32) -> Rgba {
+    use conversions::conversions::linear_to_srgb;
+    let encode = |c: f32| linear_to_srgb(c.clamp(0.0, 1.0));
+    Rgba::from_f32(encode(r), encode(g), encode(b), alpha)
+}
+
+impl std::str::FromStr for Color {
+    type Err = crate::error::CloveError;
+    
+    fn from_str(css: &str) -> Result<Self> {
+        Self::parse(css)
+    }
+}
+
 impl Default for Color {
     fn default() -> Self {
         Color::Rgba(Rgba::TRANSPARENT)
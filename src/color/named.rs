@@ -1,4 +1,6 @@
 use crate::color::Rgba;
+use crate::error::{CloveError, Result};
+use std::str::FromStr;
 
 /// Named colors enum with 140+ web colors
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -323,6 +325,20 @@ pub enum NamedColor {
 }
 
 impl NamedColor {
+    /// Look up a CSS color keyword, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        CSS_NAMES.iter()
+            .find(|(css_name, _)| css_name.eq_ignore_ascii_case(name))
+            .map(|&(_, color)| color)
+    }
+    
+    /// CSS keyword for this color, if it has one
+    pub fn css_name(&self) -> Option<&'static str> {
+        CSS_NAMES.iter()
+            .find(|(_, color)| color == self)
+            .map(|&(css_name, _)| css_name)
+    }
+    
     /// Convert named color to RGBA
     pub fn to_rgba(&self) -> Rgba {
         match self {
@@ -385,7 +401,7 @@ impl NamedColor {
             NamedColor::MediumSeaGreen => Rgba::rgb(60, 179, 113),
             NamedColor::SeaGreen => Rgba::rgb(46, 139, 87),
             NamedColor::ForestGreen => Rgba::rgb(34, 139, 34),
-            NamedColor::DarkGreen => Rgba::rgb(0, 128, 0),
+            NamedColor::DarkGreen => Rgba::rgb(0, 100, 0),
             NamedColor::MediumAquamarine => Rgba::rgb(102, 205, 170),
             NamedColor::DarkSeaGreen => Rgba::rgb(143, 188, 143),
             NamedColor::LightSeaGreen => Rgba::rgb(32, 178, 170),
@@ -651,3 +667,174 @@ impl NamedColor {
     }
 }
 
+
+impl FromStr for NamedColor {
+    type Err = CloveError;
+    
+    fn from_str(name: &str) -> Result<Self> {
+        Self::from_name(name.trim()).ok_or_else(|| CloveError::InvalidCssColor(name.to_string()))
+    }
+}
+
+/// CSS keywords for the named colors, gray spellings before grey
+const CSS_NAMES: &[(&str, NamedColor)] = &[
+    ("aliceblue", NamedColor::AliceBlue),
+    ("antiquewhite", NamedColor::AntiqueWhite),
+    ("aqua", NamedColor::Aqua),
+    ("aquamarine", NamedColor::Aquamarine),
+    ("azure", NamedColor::Azure),
+    ("beige", NamedColor::Beige),
+    ("bisque", NamedColor::Bisque),
+    ("black", NamedColor::Black),
+    ("blanchedalmond", NamedColor::BlanchedAlmond),
+    ("blue", NamedColor::Blue),
+    ("blueviolet", NamedColor::BlueViolet),
+    ("brown", NamedColor::Brown),
+    ("burlywood", NamedColor::Burlywood),
+    ("cadetblue", NamedColor::CadetBlue),
+    ("chartreuse", NamedColor::Chartreuse),
+    ("chocolate", NamedColor::Chocolate),
+    ("coral", NamedColor::Coral),
+    ("cornflowerblue", NamedColor::CornflowerBlue),
+    ("cornsilk", NamedColor::Cornsilk),
+    ("crimson", NamedColor::Crimson),
+    ("cyan", NamedColor::Cyan),
+    ("darkblue", NamedColor::DarkBlue),
+    ("darkcyan", NamedColor::DarkCyan),
+    ("darkgoldenrod", NamedColor::DarkGoldenrod),
+    ("darkgray", NamedColor::DarkGray),
+    ("darkgreen", NamedColor::DarkGreen),
+    ("darkgrey", NamedColor::DarkGray),
+    ("darkkhaki", NamedColor::DarkKhaki),
+    ("darkmagenta", NamedColor::DarkMagenta),
+    ("darkolivegreen", NamedColor::DarkOliveGreen),
+    ("darkorange", NamedColor::DarkOrange),
+    ("darkorchid", NamedColor::DarkOrchid),
+    ("darkred", NamedColor::DarkRed),
+    ("darksalmon", NamedColor::DarkSalmon),
+    ("darkseagreen", NamedColor::DarkSeaGreen),
+    ("darkslateblue", NamedColor::DarkSlateBlue),
+    ("darkslategray", NamedColor::DarkSlateGray),
+    ("darkslategrey", NamedColor::DarkSlateGray),
+    ("darkturquoise", NamedColor::DarkTurquoise),
+    ("darkviolet", NamedColor::DarkViolet),
+    ("deeppink", NamedColor::DeepPink),
+    ("deepskyblue", NamedColor::DeepSkyBlue),
+    ("dimgray", NamedColor::DimGray),
+    ("dimgrey", NamedColor::DimGray),
+    ("dodgerblue", NamedColor::DodgerBlue),
+    ("firebrick", NamedColor::FireBrick),
+    ("floralwhite", NamedColor::FloralWhite),
+    ("forestgreen", NamedColor::ForestGreen),
+    ("fuchsia", NamedColor::Fuchsia),
+    ("gainsboro", NamedColor::Gainsboro),
+    ("ghostwhite", NamedColor::GhostWhite),
+    ("gold", NamedColor::Gold),
+    ("goldenrod", NamedColor::Goldenrod),
+    ("gray", NamedColor::Gray),
+    ("green", NamedColor::Green),
+    ("greenyellow", NamedColor::GreenYellow),
+    ("grey", NamedColor::Gray),
+    ("honeydew", NamedColor::Honeydew),
+    ("hotpink", NamedColor::HotPink),
+    ("indianred", NamedColor::IndianRed),
+    ("indigo", NamedColor::Indigo),
+    ("ivory", NamedColor::Ivory),
+    ("khaki", NamedColor::Khaki),
+    ("lavender", NamedColor::Lavender),
+    ("lavenderblush", NamedColor::LavenderBlush),
+    ("lawngreen", NamedColor::LawnGreen),
+    ("lemonchiffon", NamedColor::LemonChiffon),
+    ("lightblue", NamedColor::LightBlue),
+    ("lightcoral", NamedColor::LightCoral),
+    ("lightcyan", NamedColor::LightCyan),
+    ("lightgoldenrodyellow", NamedColor::LightGoldenrodYellow),
+    ("lightgray", NamedColor::LightGray),
+    ("lightgreen", NamedColor::LightGreen),
+    ("lightgrey", NamedColor::LightGray),
+    ("lightpink", NamedColor::LightPink),
+    ("lightsalmon", NamedColor::LightSalmon),
+    ("lightseagreen", NamedColor::LightSeaGreen),
+    ("lightskyblue", NamedColor::LightSkyBlue),
+    ("lightslategray", NamedColor::LightSlateGray),
+    ("lightslategrey", NamedColor::LightSlateGray),
+    ("lightsteelblue", NamedColor::LightSteelBlue),
+    ("lime", NamedColor::Lime),
+    ("limegreen", NamedColor::LimeGreen),
+    ("linen", NamedColor::Linen),
+    ("magenta", NamedColor::Magenta),
+    ("maroon", NamedColor::Maroon),
+    ("mediumaquamarine", NamedColor::MediumAquamarine),
+    ("mediumblue", NamedColor::MediumBlue),
+    ("mediumorchid", NamedColor::MediumOrchid),
+    ("mediumpurple", NamedColor::MediumPurple),
+    ("mediumseagreen", NamedColor::MediumSeaGreen),
+    ("mediumslateblue", NamedColor::MediumSlateBlue),
+    ("mediumspringgreen", NamedColor::MediumSpringGreen),
+    ("mediumturquoise", NamedColor::MediumTurquoise),
+    ("mediumvioletred", NamedColor::MediumVioletRed),
+    ("midnightblue", NamedColor::MidnightBlue),
+    ("mintcream", NamedColor::MintCream),
+    ("mistyrose", NamedColor::MistyRose),
+    ("moccasin", NamedColor::Moccasin),
+    ("navajowhite", NamedColor::NavajoWhite),
+    ("navy", NamedColor::Navy),
+    ("oldlace", NamedColor::OldLace),
+    ("olive", NamedColor::Olive),
+    ("olivedrab", NamedColor::OliveDrab),
+    ("orange", NamedColor::Orange),
+    ("orangered", NamedColor::OrangeRed),
+    ("orchid", NamedColor::Orchid),
+    ("palegoldenrod", NamedColor::PaleGoldenrod),
+    ("palegreen", NamedColor::PaleGreen),
+    ("paleturquoise", NamedColor::PaleTurquoise),
+    ("palevioletred", NamedColor::PaleVioletRed),
+    ("papayawhip", NamedColor::PapayaWhip),
+    ("peachpuff", NamedColor::PeachPuff),
+    ("peru", NamedColor::Peru),
+    ("pink", NamedColor::Pink),
+    ("plum", NamedColor::Plum),
+    ("powderblue", NamedColor::PowderBlue),
+    ("purple", NamedColor::Purple),
+    ("red", NamedColor::Red),
+    ("rosybrown", NamedColor::RosyBrown),
+    ("royalblue", NamedColor::RoyalBlue),
+    ("saddlebrown", NamedColor::SaddleBrown),
+    ("salmon", NamedColor::Salmon),
+    ("sandybrown", NamedColor::SandyBrown),
+    ("seagreen", NamedColor::SeaGreen),
+    ("seashell", NamedColor::SeaShell),
+    ("sienna", NamedColor::Sienna),
+    ("silver", NamedColor::Silver),
+    ("skyblue", NamedColor::SkyBlue),
+    ("slateblue", NamedColor::SlateBlue),
+    ("slategray", NamedColor::SlateGray),
+    ("slategrey", NamedColor::SlateGray),
+    ("snow", NamedColor::Snow),
+    ("springgreen", NamedColor::SpringGreen),
+    ("steelblue", NamedColor::SteelBlue),
+    ("tan", NamedColor::Tan),
+    ("teal", NamedColor::Teal),
+    ("thistle", NamedColor::Thistle),
+    ("tomato", NamedColor::Tomato),
+    ("turquoise", NamedColor::Turquoise),
+    ("violet", NamedColor::Violet),
+    ("wheat", NamedColor::Wheat),
+    ("white", NamedColor::White),
+    ("whitesmoke", NamedColor::WhiteSmoke),
+    ("yellow", NamedColor::Yellow),
+    ("yellowgreen", NamedColor::YellowGreen),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dark_green_matches_css() {
+        // CSS darkgreen is darker than green, not the same color
+        assert_eq!(NamedColor::DarkGreen.to_rgba(), Rgba::rgb(0, 100, 0));
+        assert_eq!(NamedColor::Green.to_rgba(), Rgba::rgb(0, 128, 0));
+        assert_eq!("darkgreen".parse::<NamedColor>().unwrap().to_rgba(), Rgba::rgb(0, 100, 0));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::color::conversions::conversions::{ab_to_chroma_hue, chroma_hue_to_ab, linear_srgb_to_oklab, oklab_to_linear_srgb};
+use crate::color::{check_component, from_linear_srgb, to_linear_srgb, Rgba};
+use crate::error::Result;
+
+/// OKLab color, a perceptually uniform space
+///
+/// Colors outside the sRGB gamut are clipped when converted to RGBA.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f32, // Lightness: 0-1
+    pub a: f32, // Green to red, about -0.4 to 0.4
+    pub b: f32, // Blue to yellow, about -0.4 to 0.4
+    pub alpha: f32, // Alpha: 0-1
+}
+
+impl Oklab {
+    /// Create new OKLab color
+    pub fn new(l: f32, a: f32, b: f32, alpha: f32) -> Result<Self> {
+        check_component("lightness", l, 0.0..=1.0)?;
+        check_component("alpha", alpha, 0.0..=1.0)?;
+        Ok(Self { l, a, b, alpha })
+    }
+    
+    /// Convert from RGBA
+    pub fn from_rgba(rgba: Rgba) -> Self {
+        let (r, g, b, alpha) = to_linear_srgb(rgba);
+        let (l, a, b) = linear_srgb_to_oklab(r, g, b);
+        Self { l, a, b, alpha }
+    }
+    
+    /// Convert to RGBA
+    pub fn to_rgba(&self) -> Rgba {
+        let (r, g, b) = oklab_to_linear_srgb(self.l, self.a, self.b);
+        from_linear_srgb(r, g, b, self.alpha)
+    }
+    
+    /// Convert to the polar form
+    pub fn to_oklch(&self) -> Oklch {
+        let (c, h) = ab_to_chroma_hue(self.a, self.b);
+        Oklch { l: self.l, c, h, alpha: self.alpha }
+    }
+}
+
+/// OKLCH color, the polar form of [`Oklab`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f32, // Lightness: 0-1
+    pub c: f32, // Chroma: 0 up, about 0.4 at most in practice
+    pub h: f32, // Hue: 0-360
+    pub alpha: f32, // Alpha: 0-1
+}
+
+impl Oklch {
+    /// Create new OKLCH color
+    pub fn new(l: f32, c: f32, h: f32, alpha: f32) -> Result<Self> {
+        check_component("lightness", l, 0.0..=1.0)?;
+        check_component("chroma", c, 0.0..=f32::MAX)?;
+        check_component("hue", h, 0.0..=360.0)?;
+        check_component("alpha", alpha, 0.0..=1.0)?;
+        Ok(Self { l, c, h, alpha })
+    }
+    
+    /// Convert from RGBA
+    pub fn from_rgba(rgba: Rgba) -> Self {
+        Oklab::from_rgba(rgba).to_oklch()
+    }
+    
+    /// Convert to RGBA
+    pub fn to_rgba(&self) -> Rgba {
+        self.to_oklab().to_rgba()
+    }
+    
+    /// Convert to the rectangular form
+    pub fn to_oklab(&self) -> Oklab {
+        let (a, b) = chroma_hue_to_ab(self.c, self.h);
+        Oklab { l: self.l, a, b, alpha: self.alpha }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_oklab_and_oklch_round_trip() {
+        let oklch = Oklch::from_rgba(Rgba::rgb(255, 0, 0));
+        assert!((oklch.l - 0.628).abs() < 0.001 && (oklch.c - 0.2577).abs() < 0.001 && (oklch.h - 29.23).abs() < 0.05, "{oklch:?}");
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgba = Rgba::new(r, g, b, 64);
+                    assert_eq!(Oklab::from_rgba(rgba).to_rgba(), rgba);
+                    assert_eq!(Oklch::from_rgba(rgba).to_rgba(), rgba);
+                }
+            }
+        }
+    }
+}
@@ -1,15 +1,333 @@
-use crate::color::Rgba;
+use crate::color::{Color, Rgba};
+use crate::error::{CloveError, Result};
+use crate::geometry::Point;
+use crate::image::Image;
+use crate::layer::{image_to_pixmap, Layer, LayerId};
+use std::sync::Arc;
+use tiny_skia::{FilterQuality, Paint, PathBuilder, Pixmap, Rect, Shader, SpreadMode, Transform};
+
+/// How a pattern covers the area beyond its tile
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PatternMode {
+    /// Tile repeats in every direction
+    #[default]
+    Repeat,
+    /// Tile repeats, mirrored on every other copy so edges meet seamlessly
+    Reflect,
+    /// Edge pixels of the tile stretch outward
+    Clamp,
+}
+
+impl PatternMode {
+    fn to_skia(self) -> SpreadMode {
+        match self {
+            PatternMode::Repeat => SpreadMode::Repeat,
+            PatternMode::Reflect => SpreadMode::Reflect,
+            PatternMode::Clamp => SpreadMode::Pad,
+        }
+    }
+}
 
 /// Pattern fill
+///
+/// A tile image painted across the shape, placed with its top-left corner at the
+/// origin before scale, rotation and offset are applied. Patterns without a tile
+/// paint their solid color.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Pattern {
+    /// Solid color, also used where a tile can't be painted
     pub color: Rgba,
-    // Future: image patterns, repeating patterns, etc.
+    tile: Option<Arc<Pixmap>>,
+    pub mode: PatternMode,
+    pub scale: (f32, f32),
+    /// Degrees clockwise
+    pub rotation: f32,
+    pub offset: Point,
 }
 
 impl Pattern {
     pub fn new(color: Rgba) -> Self {
-        Self { color }
+        Self {
+            color,
+            tile: None,
+            mode: PatternMode::Repeat,
+            scale: (1.0, 1.0),
+            rotation: 0.0,
+            offset: Point::ZERO,
+        }
+    }
+
+    /// Pattern repeating an image
+    pub fn image(image: &Image) -> Result<Self> {
+        Ok(Self::tile(image_to_pixmap(&image.data)?, average(&image.data)))
+    }
+
+    /// Pattern repeating a layer's content
+    pub fn from_layer(layer: &Layer) -> Result<Self> {
+        Ok(Self::tile(image_to_pixmap(&layer.content)?, average(&layer.content)))
+    }
+
+    /// Pattern repeating a tile drawn by `draw` on a blank `width` x `height` layer
+    pub fn draw(width: u32, height: u32, draw: impl FnOnce(&mut Layer) -> Result<()>) -> Result<Self> {
+        let mut layer = Layer::new(LayerId::new(), "pattern", width, height);
+        draw(&mut layer)?;
+        Self::from_layer(&layer)
+    }
+
+    /// Parallel lines of `line_width`, `spacing` apart and `angle` degrees from horizontal
+    ///
+    /// Like the other generators, fails when the tile would be over 4096 pixels across.
+    pub fn hatch(color: Color, spacing: f32, line_width: f32, angle: f32) -> Result<Self> {
+        let size = tile_size(spacing)?;
+        let rgba = color.to_rgba();
+        let tile = paint_tile(size, size, |tile| {
+            let top = (size as f32 - line_width) / 2.0;
+            fill_rects(tile, rgba, &[(0.0, top, size as f32, line_width)]);
+        })?;
+        Ok(Self::tile(tile, rgba).rotate(angle))
+    }
+
+    /// Two sets of hatch lines crossing at right angles
+    pub fn cross_hatch(color: Color, spacing: f32, line_width: f32, angle: f32) -> Result<Self> {
+        let size = tile_size(spacing)?;
+        let rgba = color.to_rgba();
+        let tile = paint_tile(size, size, |tile| {
+            let (extent, middle) = (size as f32, (size as f32 - line_width) / 2.0);
+            fill_rects(tile, rgba, &[(0.0, middle, extent, line_width), (middle, 0.0, line_width, extent)]);
+        })?;
+        Ok(Self::tile(tile, rgba).rotate(angle))
+    }
+
+    /// Grid of dots of `radius`, `spacing` apart
+    pub fn dots(color: Color, spacing: f32, radius: f32) -> Result<Self> {
+        let size = tile_size(spacing)?;
+        let rgba = color.to_rgba();
+        let tile = paint_tile(size, size, |tile| {
+            let center = size as f32 / 2.0;
+            if let Some(circle) = PathBuilder::from_circle(center, center, radius) {
+                tile.fill_path(&circle, &solid(rgba), tiny_skia::FillRule::Winding, Transform::identity(), None);
+            }
+        })?;
+        Ok(Self::tile(tile, rgba))
+    }
+
+    /// Alternating stripes of `color` and `background`, each `width` wide, `angle` degrees from horizontal
+    pub fn stripes(color: Color, background: Color, width: f32, angle: f32) -> Result<Self> {
+        let size = tile_size(width * 2.0)?;
+        let (rgba, background) = (color.to_rgba(), background.to_rgba());
+        let tile = paint_tile(size, size, |tile| {
+            let half = size as f32 / 2.0;
+            fill_rects(tile, rgba, &[(0.0, 0.0, size as f32, half)]);
+            fill_rects(tile, background, &[(0.0, half, size as f32, half)]);
+        })?;
+        Ok(Self::tile(tile, rgba).rotate(angle))
+    }
+
+    /// Checkerboard of `cell` sized squares, starting with `color` at the top left
+    pub fn checkerboard(color: Color, other: Color, cell: f32) -> Result<Self> {
+        let size = tile_size(cell * 2.0)?;
+        let (rgba, other) = (color.to_rgba(), other.to_rgba());
+        let tile = paint_tile(size, size, |tile| {
+            let half = size as f32 / 2.0;
+            fill_rects(tile, rgba, &[(0.0, 0.0, half, half), (half, half, half, half)]);
+            fill_rects(tile, other, &[(half, 0.0, half, half), (0.0, half, half, half)]);
+        })?;
+        Ok(Self::tile(tile, rgba))
+    }
+
+    /// Set how the area beyond the tile is covered
+    pub fn mode(mut self, mode: PatternMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn scale(mut self, sx: f32, sy: f32) -> Self {
+        self.scale = (sx, sy);
+        self
+    }
+
+    /// Rotate by a further `degrees` around the origin
+    pub fn rotate(mut self, degrees: f32) -> Self {
+        self.rotation += degrees;
+        self
+    }
+
+    /// Shift the tile grid by `(x, y)`
+    pub fn offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Point::new(x, y);
+        self
+    }
+
+    /// Tile size in pixels, if the pattern has a tile
+    pub fn tile_size(&self) -> Option<(u32, u32)> {
+        self.tile.as_ref().map(|tile| (tile.width(), tile.height()))
+    }
+
+    fn tile(tile: Pixmap, color: Rgba) -> Self {
+        Self {
+            tile: Some(Arc::new(tile)),
+            ..Self::new(color)
+        }
+    }
+
+    fn transform(&self) -> Transform {
+        Transform::from_scale(self.scale.0, self.scale.1)
+            .post_rotate(self.rotation)
+            .post_translate(self.offset.x, self.offset.y)
+    }
+
+    /// Convert to a tiny-skia pattern shader, `None` for solid patterns
+    pub(crate) fn to_skia_pattern(&self, opacity: f32) -> Option<Shader<'_>> {
+        let tile = self.tile.as_ref()?;
+        let transform = self.transform();
+        // Untransformed tiles land on whole pixels, so smoothing would only blur them
+        let quality = if transform.is_translate() && self.offset.x.fract() == 0.0 && self.offset.y.fract() == 0.0 {
+            FilterQuality::Nearest
+        } else {
+            FilterQuality::Bilinear
+        };
+        Some(tiny_skia::Pattern::new(tile.as_ref().as_ref(), self.mode.to_skia(), quality, opacity, transform))
     }
 }
 
+/// Largest tile edge the generators will allocate, in pixels
+const MAX_TILE_SIZE: u32 = 4096;
+
+/// Whole-pixel tile edge for a requested spacing
+fn tile_size(spacing: f32) -> Result<u32> {
+    let size = spacing.round().max(1.0);
+    // `as` saturates, so infinite spacings report u32::MAX
+    let edge = size as u32;
+    if edge > MAX_TILE_SIZE {
+        return Err(CloveError::InvalidDimensions { width: edge, height: edge });
+    }
+    Ok(edge)
+}
+
+fn solid(rgba: Rgba) -> Paint<'static> {
+    let mut paint = Paint {
+        anti_alias: true,
+        ..Default::default()
+    };
+    paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
+    paint
+}
+
+fn paint_tile(width: u32, height: u32, draw: impl FnOnce(&mut Pixmap)) -> Result<Pixmap> {
+    let mut tile = Pixmap::new(width, height).ok_or(CloveError::InvalidDimensions { width, height })?;
+    draw(&mut tile);
+    Ok(tile)
+}
+
+/// Fill rectangles as one path so overlaps aren't painted twice
+fn fill_rects(tile: &mut Pixmap, rgba: Rgba, rects: &[(f32, f32, f32, f32)]) {
+    let mut builder = PathBuilder::new();
+    for &(x, y, width, height) in rects {
+        if let Some(rect) = Rect::from_xywh(x, y, width, height) {
+            builder.push_rect(rect);
+        }
+    }
+    if let Some(path) = builder.finish() {
+        tile.fill_path(&path, &solid(rgba), tiny_skia::FillRule::Winding, Transform::identity(), None);
+    }
+}
+
+/// Mean color weighted by alpha, standing in for the pattern where only a solid color fits
+fn average(image: &image::RgbaImage) -> Rgba {
+    let (mut sum, mut alpha) = ([0.0f64; 3], 0.0f64);
+    for pixel in image.pixels() {
+        let a = pixel[3] as f64;
+        for (total, &channel) in sum.iter_mut().zip(&pixel.0[..3]) {
+            *total += channel as f64 * a;
+        }
+        alpha += a;
+    }
+    if alpha == 0.0 {
+        return Rgba::TRANSPARENT;
+    }
+    let count = (image.width() as f64 * image.height() as f64).max(1.0);
+    let channel = |i: usize| (sum[i] / alpha).round() as u8;
+    Rgba::new(channel(0), channel(1), channel(2), (alpha / count).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkerboard_fill_repeats() {
+        let board = Pattern::checkerboard(Color::rgba(255, 0, 0, 255), Color::rgba(0, 0, 255, 255), 4.0).unwrap();
+        assert_eq!(board.tile_size(), Some((8, 8)));
+
+        let mut layer = Layer::new(LayerId::new(), "pattern", 32, 32);
+        layer.draw_rect()
+            .position(0.0, 0.0)
+            .size(32.0, 32.0)
+            .fill(Color::pattern(board))
+            .draw()
+            .unwrap();
+        let pixel = |x: u32, y: u32| *layer.content.get_pixel(x, y);
+        assert_eq!(pixel(1, 1).0, [255, 0, 0, 255]);
+        assert_eq!(pixel(5, 1).0, [0, 0, 255, 255]);
+        assert_eq!(pixel(9, 1).0, [255, 0, 0, 255]);
+        assert_eq!(pixel(13, 5).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_drawn_tile_and_modes() {
+        let tile = Pattern::draw(4, 4, |layer| {
+            layer.draw_rect().position(0.0, 0.0).size(2.0, 4.0).fill(Color::rgba(0, 255, 0, 255)).draw()?;
+            Ok(())
+        }).unwrap();
+        assert_eq!(tile.color, Rgba::new(0, 255, 0, 128));
+
+        let mut layer = Layer::new(LayerId::new(), "pattern", 16, 4);
+        layer.draw_rect()
+            .position(0.0, 0.0)
+            .size(16.0, 4.0)
+            .fill(Color::pattern(tile.mode(PatternMode::Clamp)))
+            .draw()
+            .unwrap();
+        // Clamped tiles repeat their right edge, which is empty
+        assert_eq!(layer.alpha_at(1.0, 1.0), 255);
+        assert_eq!(layer.alpha_at(9.0, 1.0), 0);
+    }
+
+    #[test]
+    fn test_hatch_strokes() {
+        let hatch = Pattern::hatch(Color::rgba(0, 0, 0, 255), 4.0, 2.0, 0.0).unwrap();
+        let mut layer = Layer::new(LayerId::new(), "pattern", 20, 20);
+        layer.draw_line()
+            .from(0.0, 10.0)
+            .to(20.0, 10.0)
+            .stroke(Color::pattern(hatch), 20.0)
+            .draw()
+            .unwrap();
+        // Lines cover the middle two rows of every four
+        assert_eq!(layer.alpha_at(5.0, 1.0), 255);
+        assert_eq!(layer.alpha_at(5.0, 3.0), 0);
+    }
+
+    #[test]
+    fn test_rotation_accumulates() {
+        let hatch = Pattern::hatch(Color::rgba(0, 0, 0, 255), 8.0, 1.0, 45.0).unwrap().rotate(10.0);
+        assert_eq!(hatch.rotation, 55.0);
+    }
+
+    #[test]
+    fn test_oversized_tiles_are_errors() {
+        let black = Color::rgba(0, 0, 0, 255);
+        assert!(matches!(
+            Pattern::hatch(black.clone(), 1e6, 1.0, 0.0),
+            Err(CloveError::InvalidDimensions { width: 1_000_000, height: 1_000_000 })
+        ));
+        assert!(matches!(
+            Pattern::dots(black.clone(), f32::INFINITY, 1.0),
+            Err(CloveError::InvalidDimensions { width: u32::MAX, .. })
+        ));
+        assert!(matches!(
+            Pattern::checkerboard(black.clone(), black, f32::MAX),
+            Err(CloveError::InvalidDimensions { .. })
+        ));
+    }
+}
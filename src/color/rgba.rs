@@ -55,29 +55,13 @@ impl Rgba {
     
     /// Convert to HSLA
     pub fn to_hsla(&self) -> Result<crate::color::Hsla> {
-        let r = self.r as f32 / 255.0;
-        let g = self.g as f32 / 255.0;
-        let b = self.b as f32 / 255.0;
-        let a = self.a as f32 / 255.0;
-        
-        let max = r.max(g.max(b));
-        let min = r.min(g.min(b));
-        let delta = max - min;
-        
-        let h = if delta == 0.0 {
-            0.0
-        } else if max == r {
-            60.0 * (((g - b) / delta) % 6.0)
-        } else if max == g {
-            60.0 * (((b - r) / delta) + 2.0)
-        } else {
-            60.0 * (((r - g) / delta) + 4.0)
-        };
-        
-        let s = if max == 0.0 { 0.0 } else { delta / max };
-        let l = (max + min) / 2.0;
-        
-        crate::color::Hsla::new(h, s, l, a)
+        Ok(crate::color::Hsla::from_rgba(*self))
+    }
+    
+    /// Create from f32 channels (0.0-1.0 range), rounding and clamping each
+    pub fn from_f32(r: f32, g: f32, b: f32, a: f32) -> Self {
+        let channel = |value: f32| (value * 255.0).round().clamp(0.0, 255.0) as u8;
+        Self::new(channel(r), channel(g), channel(b), channel(a))
     }
     
     /// Get as f32 tuple (0.0-1.0 range)
@@ -111,6 +95,11 @@ mod tests {
         assert_eq!(hsla.h, 0.0);
         assert_eq!(hsla.s, 1.0);
         assert_eq!(hsla.l, 0.5);
+        
+        // Hues past magenta wrap instead of going negative
+        let hsla = Rgba::rgb(255, 0, 128).to_hsla().unwrap();
+        assert!((hsla.h - 329.9).abs() < 0.1);
+        assert_eq!(hsla.to_rgba(), Rgba::rgb(255, 0, 128));
     }
 }
 
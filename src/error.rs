@@ -26,6 +26,9 @@ pub enum CloveError {
     #[error("Invalid HSL value: h={h}, s={s}, l={l}")]
     InvalidHsl { h: f32, s: f32, l: f32 },
     
+    #[error("Invalid CSS color: {0}")]
+    InvalidCssColor(String),
+    
     // Font errors
     #[error("Font not found: {0}")]
     FontNotFound(String),
@@ -56,6 +59,20 @@ pub enum CloveError {
     #[error("Layer limit exceeded (max: {max})")]
     LayerLimitExceeded { max: usize },
     
+    // Path errors
+    #[error("Invalid path data: {0}")]
+    InvalidPathData(String),
+    
+    // Project file errors
+    #[error("Invalid project file: {0}")]
+    InvalidProject(String),
+    
+    #[error("Unsupported project version: {found} (max supported: {supported})")]
+    UnsupportedProjectVersion { found: u16, supported: u16 },
+    
+    #[error("OpenRaster error: {0}")]
+    OpenRasterError(String),
+    
     // IO errors
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
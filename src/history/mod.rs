@@ -0,0 +1,480 @@
+use crate::layer::{Adjustment, BlendMode, Layer, LayerId};
+use image::{GrayImage, RgbaImage};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Edge length of the square tiles used for pixel deltas
+pub const TILE_SIZE: u32 = 64;
+
+/// Undo/redo history for layer edits
+///
+/// Pixel edits are stored as the tiles that changed (before and after), not as
+/// full copies of the layer. The oldest steps are dropped once either `max_depth`
+/// steps or `max_memory` bytes are exceeded.
+pub struct History {
+    undo: VecDeque<Entry>,
+    redo: Vec<Entry>,
+    group: Option<Entry>,
+    group_depth: usize,
+    max_depth: usize,
+    max_memory: usize,
+    memory: usize,
+}
+
+/// One undoable step, made of one or more changes
+pub(crate) struct Entry {
+    label: String,
+    changes: Vec<Change>,
+    bytes: usize,
+}
+
+/// A single recorded change to the layer stack
+pub(crate) enum Change {
+    Pixels {
+        layer: LayerId,
+        delta: PixelDelta,
+    },
+    Properties {
+        layer: LayerId,
+        before: Box<LayerProperties>,
+        after: Box<LayerProperties>,
+    },
+    Created {
+        layer: LayerId,
+        index: usize,
+        // Holds the layer while its creation is undone, detached from history
+        // so the entry doesn't keep its own history alive
+        removed: Option<Box<Layer>>,
+    },
+}
+
+/// Pixel difference between two versions of a layer's content
+pub(crate) enum PixelDelta {
+    Tiles(Vec<TileDelta>),
+    // Content changed size, so tiles don't line up
+    Resized {
+        before: RgbaImage,
+        after: RgbaImage,
+    },
+}
+
+/// Before/after bytes of one changed tile
+pub(crate) struct TileDelta {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    before: Vec<u8>,
+    after: Vec<u8>,
+}
+
+/// Layer state that property setters can change
+#[derive(Clone)]
+pub(crate) struct LayerProperties {
+    name: String,
+    x: f32,
+    y: f32,
+    width: Option<u32>,
+    height: Option<u32>,
+    opacity: f32,
+    blend_mode: BlendMode,
+    visible: bool,
+    adjustment: Option<Adjustment>,
+    mask: Option<GrayImage>,
+}
+
+impl History {
+    /// Create history keeping at most `max_depth` steps and `max_memory` bytes
+    pub fn new(max_depth: usize, max_memory: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            group: None,
+            group_depth: 0,
+            max_depth,
+            max_memory,
+            memory: 0,
+        }
+    }
+
+    /// Check if there is a step to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty() || self.group.as_ref().is_some_and(|g| !g.changes.is_empty())
+    }
+
+    /// Check if there is a step to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Label of the step `undo` would revert
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.back().map(|e| e.label.as_str())
+    }
+
+    /// Label of the step `redo` would reapply
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|e| e.label.as_str())
+    }
+
+    /// Number of steps that can be undone
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Approximate memory held by undo and redo steps, in bytes
+    pub fn memory_usage(&self) -> usize {
+        self.memory
+    }
+
+    /// Start grouping changes into a single step until the matching `end_group`
+    pub fn begin_group(&mut self, label: &str) {
+        if self.group_depth == 0 {
+            self.group = Some(Entry::new(label));
+        }
+        self.group_depth += 1;
+    }
+
+    /// Finish a group started with `begin_group`
+    pub fn end_group(&mut self) {
+        if self.group_depth == 0 {
+            return;
+        }
+        self.group_depth -= 1;
+        if self.group_depth == 0 {
+            self.close_group();
+        }
+    }
+
+    /// Clear all undo and redo steps
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.group = None;
+        self.group_depth = 0;
+        self.memory = 0;
+    }
+
+    /// Record a change as its own step, or into the open group
+    pub(crate) fn record(&mut self, label: &str, change: Change) {
+        self.clear_redo();
+        match self.group.as_mut() {
+            Some(group) => group.push(change),
+            None => {
+                let mut entry = Entry::new(label);
+                entry.push(change);
+                self.push_undo(entry);
+            }
+        }
+    }
+
+    pub(crate) fn take_undo(&mut self) -> Option<Entry> {
+        self.group_depth = 0;
+        self.close_group();
+        let entry = self.undo.pop_back()?;
+        self.memory -= entry.bytes;
+        Some(entry)
+    }
+
+    pub(crate) fn take_redo(&mut self) -> Option<Entry> {
+        let entry = self.redo.pop()?;
+        self.memory -= entry.bytes;
+        Some(entry)
+    }
+
+    pub(crate) fn push_undo(&mut self, entry: Entry) {
+        self.memory += entry.bytes;
+        self.undo.push_back(entry);
+        self.trim();
+    }
+
+    pub(crate) fn push_redo(&mut self, entry: Entry) {
+        self.memory += entry.bytes;
+        self.redo.push(entry);
+        self.trim();
+    }
+
+    /// Drop the oldest undo steps until both caps are met
+    fn trim(&mut self) {
+        while self.undo.len() > self.max_depth || (self.memory > self.max_memory && !self.undo.is_empty()) {
+            if let Some(oldest) = self.undo.pop_front() {
+                self.memory -= oldest.bytes;
+            }
+        }
+    }
+
+    fn clear_redo(&mut self) {
+        for entry in self.redo.drain(..) {
+            self.memory -= entry.bytes;
+        }
+    }
+
+    fn close_group(&mut self) {
+        if let Some(group) = self.group.take() {
+            if !group.changes.is_empty() {
+                self.push_undo(group);
+            }
+        }
+    }
+}
+
+impl Default for History {
+    /// 100 steps and 256 MiB
+    fn default() -> Self {
+        Self::new(100, 256 * 1024 * 1024)
+    }
+}
+
+impl Entry {
+    fn new(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            changes: Vec::new(),
+            bytes: 0,
+        }
+    }
+
+    fn push(&mut self, change: Change) {
+        self.bytes += change.bytes();
+        self.changes.push(change);
+    }
+
+    /// Revert every change, last first
+    pub(crate) fn undo(&mut self, layers: &mut Vec<Layer>) {
+        for change in self.changes.iter_mut().rev() {
+            change.apply(layers, true, None);
+        }
+        self.recount();
+    }
+
+    /// Reapply every change, first first, attaching `history` to restored layers
+    pub(crate) fn redo(&mut self, layers: &mut Vec<Layer>, history: Option<&Arc<Mutex<History>>>) {
+        for change in self.changes.iter_mut() {
+            change.apply(layers, false, history);
+        }
+        self.recount();
+    }
+
+    /// Undoing or redoing a creation moves a whole layer in or out of the entry
+    fn recount(&mut self) {
+        self.bytes = self.changes.iter().map(Change::bytes).sum();
+    }
+}
+
+impl Change {
+    fn bytes(&self) -> usize {
+        match self {
+            Change::Pixels { delta: PixelDelta::Tiles(tiles), .. } => {
+                tiles.iter().map(|t| t.before.len() + t.after.len()).sum()
+            }
+            Change::Pixels { delta: PixelDelta::Resized { before, after }, .. } => {
+                before.as_raw().len() + after.as_raw().len()
+            }
+            Change::Properties { before, after, .. } => before.bytes() + after.bytes(),
+            Change::Created { removed, .. } => {
+                std::mem::size_of::<Change>()
+                    + removed.as_ref().map_or(0, |layer| {
+                        layer.content.as_raw().len() + layer.mask.as_ref().map_or(0, |m| m.as_raw().len())
+                    })
+            }
+        }
+    }
+
+    fn apply(&mut self, layers: &mut Vec<Layer>, undo: bool, history: Option<&Arc<Mutex<History>>>) {
+        match self {
+            Change::Pixels { layer, delta } => {
+                if let Some(layer) = layers.iter_mut().find(|l| l.id == *layer) {
+                    delta.apply(&mut layer.content, undo);
+                }
+            }
+            Change::Properties { layer, before, after } => {
+                if let Some(layer) = layers.iter_mut().find(|l| l.id == *layer) {
+                    if undo { before.apply(layer) } else { after.apply(layer) }
+                }
+            }
+            Change::Created { layer, index, removed } => {
+                if undo {
+                    if let Some(position) = layers.iter().position(|l| l.id == *layer) {
+                        *index = position;
+                        let mut layer = layers.remove(position);
+                        layer.set_history(None);
+                        *removed = Some(Box::new(layer));
+                    }
+                } else if let Some(mut restored) = removed.take() {
+                    restored.set_history(history.cloned());
+                    layers.insert((*index).min(layers.len()), *restored);
+                }
+            }
+        }
+    }
+}
+
+impl PixelDelta {
+    /// Diff two versions of layer content, `None` if nothing changed
+    pub(crate) fn between(before: &RgbaImage, after: &RgbaImage) -> Option<Self> {
+        if before.dimensions() != after.dimensions() {
+            return Some(PixelDelta::Resized {
+                before: before.clone(),
+                after: after.clone(),
+            });
+        }
+
+        let (width, height) = before.dimensions();
+        let mut tiles = Vec::new();
+        for y in (0..height).step_by(TILE_SIZE as usize) {
+            for x in (0..width).step_by(TILE_SIZE as usize) {
+                let tile_width = TILE_SIZE.min(width - x);
+                let tile_height = TILE_SIZE.min(height - y);
+                let before_tile = read_tile(before, x, y, tile_width, tile_height);
+                let after_tile = read_tile(after, x, y, tile_width, tile_height);
+                if before_tile != after_tile {
+                    tiles.push(TileDelta {
+                        x,
+                        y,
+                        width: tile_width,
+                        height: tile_height,
+                        before: before_tile,
+                        after: after_tile,
+                    });
+                }
+            }
+        }
+
+        (!tiles.is_empty()).then_some(PixelDelta::Tiles(tiles))
+    }
+
+    fn apply(&self, content: &mut RgbaImage, undo: bool) {
+        match self {
+            PixelDelta::Tiles(tiles) => {
+                for tile in tiles {
+                    let data = if undo { &tile.before } else { &tile.after };
+                    write_tile(content, tile.x, tile.y, tile.width, tile.height, data);
+                }
+            }
+            PixelDelta::Resized { before, after } => {
+                *content = if undo { before.clone() } else { after.clone() };
+            }
+        }
+    }
+}
+
+impl LayerProperties {
+    pub(crate) fn of(layer: &Layer) -> Self {
+        Self {
+            name: layer.name.clone(),
+            x: layer.x,
+            y: layer.y,
+            width: layer.width,
+            height: layer.height,
+            opacity: layer.opacity,
+            blend_mode: layer.blend_mode,
+            visible: layer.visible,
+            adjustment: layer.adjustment.clone(),
+            mask: layer.mask.clone(),
+        }
+    }
+
+    fn apply(&self, layer: &mut Layer) {
+        layer.name = self.name.clone();
+        layer.x = self.x;
+        layer.y = self.y;
+        layer.width = self.width;
+        layer.height = self.height;
+        layer.opacity = self.opacity;
+        layer.blend_mode = self.blend_mode;
+        layer.visible = self.visible;
+        layer.adjustment = self.adjustment.clone();
+        layer.mask = self.mask.clone();
+    }
+
+    fn bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.name.len()
+            + self.mask.as_ref().map_or(0, |m| m.as_raw().len())
+    }
+}
+
+impl PartialEq for LayerProperties {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.x == other.x
+            && self.y == other.y
+            && self.width == other.width
+            && self.height == other.height
+            && self.opacity == other.opacity
+            && self.blend_mode == other.blend_mode
+            && self.visible == other.visible
+            && self.adjustment == other.adjustment
+            && self.mask.as_ref().map(|m| m.as_raw()) == other.mask.as_ref().map(|m| m.as_raw())
+    }
+}
+
+fn read_tile(image: &RgbaImage, x: u32, y: u32, width: u32, height: u32) -> Vec<u8> {
+    let stride = image.width() as usize * 4;
+    let raw = image.as_raw();
+    let mut tile = Vec::with_capacity((width * height * 4) as usize);
+    for row in y..y + height {
+        let start = row as usize * stride + x as usize * 4;
+        tile.extend_from_slice(&raw[start..start + width as usize * 4]);
+    }
+    tile
+}
+
+fn write_tile(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, data: &[u8]) {
+    let stride = image.width() as usize * 4;
+    let row_len = width as usize * 4;
+    let raw: &mut [u8] = image.as_mut();
+    for (i, row) in (y..y + height).enumerate() {
+        let start = row as usize * stride + x as usize * 4;
+        raw[start..start + row_len].copy_from_slice(&data[i * row_len..(i + 1) * row_len]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_changed_tiles_are_stored() {
+        let before = RgbaImage::new(200, 100);
+        let mut after = before.clone();
+        after.put_pixel(130, 70, image::Rgba([1, 2, 3, 4]));
+
+        let delta = PixelDelta::between(&before, &after).unwrap();
+        match &delta {
+            PixelDelta::Tiles(tiles) => {
+                assert_eq!(tiles.len(), 1);
+                assert_eq!((tiles[0].x, tiles[0].y), (128, 64));
+                assert_eq!((tiles[0].width, tiles[0].height), (64, 36));
+            }
+            PixelDelta::Resized { .. } => panic!("expected tile delta"),
+        }
+
+        let mut content = after.clone();
+        delta.apply(&mut content, true);
+        assert_eq!(content, before);
+        delta.apply(&mut content, false);
+        assert_eq!(content, after);
+    }
+
+    #[test]
+    fn test_unchanged_content_has_no_delta() {
+        let image = RgbaImage::new(10, 10);
+        assert!(PixelDelta::between(&image, &image).is_none());
+    }
+
+    #[test]
+    fn test_depth_and_memory_caps() {
+        let mut history = History::new(2, usize::MAX);
+        for i in 0..3 {
+            history.record(&format!("step {}", i), Change::Created { layer: LayerId::new(), index: 0, removed: None });
+        }
+        assert_eq!(history.undo_len(), 2);
+        assert_eq!(history.undo_label(), Some("step 2"));
+
+        let mut history = History::new(10, 0);
+        history.record("too big", Change::Created { layer: LayerId::new(), index: 0, removed: None });
+        assert!(!history.can_undo());
+        assert_eq!(history.memory_usage(), 0);
+    }
+}
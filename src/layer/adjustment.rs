@@ -0,0 +1,79 @@
+use crate::error::Result;
+use crate::filter::Filter;
+use image::{GrayImage, RgbaImage};
+
+/// Filter chain applied non-destructively to everything below an adjustment layer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Adjustment {
+    pub filters: Vec<Filter>,
+}
+
+impl Adjustment {
+    /// Create adjustment with a single filter
+    pub fn new(filter: Filter) -> Self {
+        Self {
+            filters: vec![filter],
+        }
+    }
+
+    /// Append another filter to the chain
+    pub fn then(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Run the filter chain over an image
+    pub fn apply(&self, image: &RgbaImage) -> Result<RgbaImage> {
+        let mut result = image.clone();
+        for filter in &self.filters {
+            result = filter.apply(&result)?;
+        }
+        Ok(result)
+    }
+
+    /// Build a layer mask from the alpha channel of an image
+    ///
+    /// With `invert` set, opaque pixels are excluded from the adjustment instead,
+    /// e.g. to keep a logo in color under a grayscale adjustment.
+    pub fn mask_from_alpha(image: &RgbaImage, invert: bool) -> GrayImage {
+        GrayImage::from_fn(image.width(), image.height(), |x, y| {
+            let a = image.get_pixel(x, y)[3];
+            image::Luma([if invert { 255 - a } else { a }])
+        })
+    }
+}
+
+impl From<Filter> for Adjustment {
+    fn from(filter: Filter) -> Self {
+        Self::new(filter)
+    }
+}
+
+impl From<Vec<Filter>> for Adjustment {
+    fn from(filters: Vec<Filter>) -> Self {
+        Self { filters }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_applies_in_order() {
+        let image = RgbaImage::from_pixel(1, 1, image::Rgba([200, 100, 50, 255]));
+        let adjustment = Adjustment::new(Filter::Grayscale).then(Filter::Invert);
+        let result = adjustment.apply(&image).unwrap();
+        let gray = Filter::Grayscale.apply(&image).unwrap().get_pixel(0, 0)[0];
+        assert_eq!(result.get_pixel(0, 0)[0], 255 - gray);
+    }
+
+    #[test]
+    fn test_mask_from_alpha_invert() {
+        let mut image = RgbaImage::new(2, 1);
+        image.put_pixel(0, 0, image::Rgba([0, 0, 0, 255]));
+        let mask = Adjustment::mask_from_alpha(&image, true);
+        assert_eq!(mask.get_pixel(0, 0)[0], 0);
+        assert_eq!(mask.get_pixel(1, 0)[0], 255);
+    }
+}
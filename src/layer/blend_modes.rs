@@ -1,6 +1,8 @@
-/// Blend mode for layer compositing
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Blend mode for layer compositing and individual draw calls
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum BlendMode {
+    // Separable blend modes
+    #[default]
     Normal,
     Multiply,
     Screen,
@@ -11,5 +13,140 @@ pub enum BlendMode {
     ColorBurn,
     HardLight,
     SoftLight,
+    Difference,
+    Exclusion,
+
+    // Non-separable blend modes
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+
+    // Arithmetic
+    Plus,
+    Modulate,
+
+    // Porter-Duff operators (Normal is source-over)
+    Clear,
+    Source,
+    Destination,
+    DestinationOver,
+    SourceIn,
+    DestinationIn,
+    SourceOut,
+    DestinationOut,
+    SourceAtop,
+    DestinationAtop,
+    Xor,
+}
+
+impl BlendMode {
+    /// Every blend mode, in declaration order
+    pub const ALL: [BlendMode; 29] = [
+        BlendMode::Normal,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Overlay,
+        BlendMode::Darken,
+        BlendMode::Lighten,
+        BlendMode::ColorDodge,
+        BlendMode::ColorBurn,
+        BlendMode::HardLight,
+        BlendMode::SoftLight,
+        BlendMode::Difference,
+        BlendMode::Exclusion,
+        BlendMode::Hue,
+        BlendMode::Saturation,
+        BlendMode::Color,
+        BlendMode::Luminosity,
+        BlendMode::Plus,
+        BlendMode::Modulate,
+        BlendMode::Clear,
+        BlendMode::Source,
+        BlendMode::Destination,
+        BlendMode::DestinationOver,
+        BlendMode::SourceIn,
+        BlendMode::DestinationIn,
+        BlendMode::SourceOut,
+        BlendMode::DestinationOut,
+        BlendMode::SourceAtop,
+        BlendMode::DestinationAtop,
+        BlendMode::Xor,
+    ];
+
+    /// Stable kebab-case name, e.g. `"color-dodge"` or `"destination-out"`
+    pub fn name(self) -> &'static str {
+        match self {
+            BlendMode::Normal => "normal",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Overlay => "overlay",
+            BlendMode::Darken => "darken",
+            BlendMode::Lighten => "lighten",
+            BlendMode::ColorDodge => "color-dodge",
+            BlendMode::ColorBurn => "color-burn",
+            BlendMode::HardLight => "hard-light",
+            BlendMode::SoftLight => "soft-light",
+            BlendMode::Difference => "difference",
+            BlendMode::Exclusion => "exclusion",
+            BlendMode::Hue => "hue",
+            BlendMode::Saturation => "saturation",
+            BlendMode::Color => "color",
+            BlendMode::Luminosity => "luminosity",
+            BlendMode::Plus => "plus",
+            BlendMode::Modulate => "modulate",
+            BlendMode::Clear => "clear",
+            BlendMode::Source => "source",
+            BlendMode::Destination => "destination",
+            BlendMode::DestinationOver => "destination-over",
+            BlendMode::SourceIn => "source-in",
+            BlendMode::DestinationIn => "destination-in",
+            BlendMode::SourceOut => "source-out",
+            BlendMode::DestinationOut => "destination-out",
+            BlendMode::SourceAtop => "source-atop",
+            BlendMode::DestinationAtop => "destination-atop",
+            BlendMode::Xor => "xor",
+        }
+    }
+
+    /// Look up a blend mode by its kebab-case name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|mode| mode.name() == name)
+    }
+
+    /// Convert to tiny-skia blend mode
+    pub(crate) fn to_skia(self) -> tiny_skia::BlendMode {
+        match self {
+            BlendMode::Normal => tiny_skia::BlendMode::SourceOver,
+            BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+            BlendMode::Screen => tiny_skia::BlendMode::Screen,
+            BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
+            BlendMode::Darken => tiny_skia::BlendMode::Darken,
+            BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+            BlendMode::ColorDodge => tiny_skia::BlendMode::ColorDodge,
+            BlendMode::ColorBurn => tiny_skia::BlendMode::ColorBurn,
+            BlendMode::HardLight => tiny_skia::BlendMode::HardLight,
+            BlendMode::SoftLight => tiny_skia::BlendMode::SoftLight,
+            BlendMode::Difference => tiny_skia::BlendMode::Difference,
+            BlendMode::Exclusion => tiny_skia::BlendMode::Exclusion,
+            BlendMode::Hue => tiny_skia::BlendMode::Hue,
+            BlendMode::Saturation => tiny_skia::BlendMode::Saturation,
+            BlendMode::Color => tiny_skia::BlendMode::Color,
+            BlendMode::Luminosity => tiny_skia::BlendMode::Luminosity,
+            BlendMode::Plus => tiny_skia::BlendMode::Plus,
+            BlendMode::Modulate => tiny_skia::BlendMode::Modulate,
+            BlendMode::Clear => tiny_skia::BlendMode::Clear,
+            BlendMode::Source => tiny_skia::BlendMode::Source,
+            BlendMode::Destination => tiny_skia::BlendMode::Destination,
+            BlendMode::DestinationOver => tiny_skia::BlendMode::DestinationOver,
+            BlendMode::SourceIn => tiny_skia::BlendMode::SourceIn,
+            BlendMode::DestinationIn => tiny_skia::BlendMode::DestinationIn,
+            BlendMode::SourceOut => tiny_skia::BlendMode::SourceOut,
+            BlendMode::DestinationOut => tiny_skia::BlendMode::DestinationOut,
+            BlendMode::SourceAtop => tiny_skia::BlendMode::SourceAtop,
+            BlendMode::DestinationAtop => tiny_skia::BlendMode::DestinationAtop,
+            BlendMode::Xor => tiny_skia::BlendMode::Xor,
+        }
+    }
 }
 
@@ -1,9 +1,13 @@
 pub mod blend_modes;
+pub mod adjustment;
 
 pub use blend_modes::BlendMode;
+pub use adjustment::Adjustment;
 
 use crate::error::Result;
-use image::RgbaImage;
+use crate::history::{Change, History, LayerProperties, PixelDelta};
+use crate::transform::TransformOps;
+use image::{GrayImage, RgbaImage};
 use tiny_skia::Pixmap;
 
 /// Layer ID
@@ -17,6 +21,12 @@ impl LayerId {
     }
 }
 
+impl Default for LayerId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Layer struct
 pub struct Layer {
     pub id: LayerId,
@@ -29,17 +39,27 @@ pub struct Layer {
     pub opacity: f32,
     pub blend_mode: BlendMode,
     pub visible: bool,
+    /// Filter chain applied to the composite below instead of drawing `content`
+    pub adjustment: Option<Adjustment>,
+    /// Grayscale mask in canvas coordinates (white reveals, black hides)
+    pub mask: Option<GrayImage>,
     base_width: u32,
     base_height: u32,
     font_manager: Option<std::sync::Arc<std::sync::Mutex<crate::text::font_manager::FontManager>>>,
+    history: Option<std::sync::Arc<std::sync::Mutex<History>>>,
 }
 
 impl Layer {
     pub fn new(id: LayerId, name: &str, base_width: u32, base_height: u32) -> Self {
+        Self::with_content(id, name, base_width, base_height, RgbaImage::new(base_width, base_height))
+    }
+    
+    /// Create a layer around existing pixels, which may already be scaled from the base size
+    pub(crate) fn with_content(id: LayerId, name: &str, base_width: u32, base_height: u32, content: RgbaImage) -> Self {
         Self {
             id,
             name: name.to_string(),
-            content: RgbaImage::new(base_width, base_height),
+            content,
             x: 0.0,
             y: 0.0,
             width: None,
@@ -47,9 +67,12 @@ impl Layer {
             opacity: 1.0,
             blend_mode: BlendMode::Normal,
             visible: true,
+            adjustment: None,
+            mask: None,
             base_width,
             base_height,
             font_manager: None,
+            history: None,
         }
     }
     
@@ -63,26 +86,76 @@ impl Layer {
         self.font_manager.as_ref().and_then(|fm| fm.lock().ok())
     }
     
+    /// Attach undo history that edits on this layer are recorded into
+    pub(crate) fn set_history(&mut self, history: Option<std::sync::Arc<std::sync::Mutex<History>>>) {
+        self.history = history;
+    }
+    
+    fn record(&self, label: &str, change: Change) {
+        if let Some(mut history) = self.history.as_ref().and_then(|h| h.lock().ok()) {
+            history.record(label, change);
+        }
+    }
+    
+    /// Replace content, recording the changed tiles
+    fn replace_content(&mut self, label: &str, content: RgbaImage) {
+        let before = std::mem::replace(&mut self.content, content);
+        if self.history.is_some() {
+            if let Some(delta) = PixelDelta::between(&before, &self.content) {
+                self.record(label, Change::Pixels { layer: self.id, delta });
+            }
+        }
+    }
+    
+    /// Run a property edit, recording it as one undoable step
+    fn edit_properties(&mut self, label: &str, edit: impl FnOnce(&mut Self)) -> &mut Self {
+        if self.history.is_none() {
+            edit(self);
+            return self;
+        }
+        
+        let before = LayerProperties::of(self);
+        if let Some(mut history) = self.history.as_ref().and_then(|h| h.lock().ok()) {
+            history.begin_group(label);
+        }
+        edit(self);
+        let after = LayerProperties::of(self);
+        if before != after {
+            self.record(label, Change::Properties {
+                layer: self.id,
+                before: Box::new(before),
+                after: Box::new(after),
+            });
+        }
+        if let Some(mut history) = self.history.as_ref().and_then(|h| h.lock().ok()) {
+            history.end_group();
+        }
+        self
+    }
+    
     /// Set layer width
     pub fn width(&mut self, width: u32) -> &mut Self {
-        self.width = Some(width);
-        self.scale_content();
-        self
+        self.edit_properties("Resize layer", |layer| {
+            layer.width = Some(width);
+            layer.scale_content();
+        })
     }
     
     /// Set layer height
     pub fn height(&mut self, height: u32) -> &mut Self {
-        self.height = Some(height);
-        self.scale_content();
-        self
+        self.edit_properties("Resize layer", |layer| {
+            layer.height = Some(height);
+            layer.scale_content();
+        })
     }
     
     /// Set layer dimensions
     pub fn set_dimensions(&mut self, width: u32, height: u32) -> &mut Self {
-        self.width = Some(width);
-        self.height = Some(height);
-        self.scale_content();
-        self
+        self.edit_properties("Resize layer", |layer| {
+            layer.width = Some(width);
+            layer.height = Some(height);
+            layer.scale_content();
+        })
     }
     
     /// Get effective dimensions
@@ -93,32 +166,139 @@ impl Layer {
         )
     }
     
+    /// Effective alpha at a canvas point, after offset, opacity and mask
+    pub fn alpha_at(&self, x: f32, y: f32) -> u8 {
+        let (local_x, local_y) = ((x - self.x).floor(), (y - self.y).floor());
+        if local_x < 0.0 || local_y < 0.0
+            || local_x >= self.content.width() as f32
+            || local_y >= self.content.height() as f32
+        {
+            return 0;
+        }
+        
+        let mut alpha = self.content.get_pixel(local_x as u32, local_y as u32)[3] as f32 * self.opacity;
+        if let Some(mask) = &self.mask {
+            let (mask_x, mask_y) = (x.floor(), y.floor());
+            let coverage = if mask_x >= 0.0 && mask_y >= 0.0
+                && mask_x < mask.width() as f32
+                && mask_y < mask.height() as f32
+            {
+                mask.get_pixel(mask_x as u32, mask_y as u32)[0]
+            } else {
+                0
+            };
+            alpha *= coverage as f32 / 255.0;
+        }
+        alpha.round() as u8
+    }
+    
+    /// Get dimensions the layer was created with
+    pub fn base_dimensions(&self) -> (u32, u32) {
+        (self.base_width, self.base_height)
+    }
+    
     fn scale_content(&mut self) {
         if let (Some(w), Some(h)) = (self.width, self.height) {
             if w != self.content.width() || h != self.content.height() {
                 use image::imageops::resize;
-                self.content = resize(
+                let scaled = resize(
                     &self.content,
                     w,
                     h,
                     image::imageops::FilterType::Lanczos3,
                 );
+                self.replace_content("Resize layer", scaled);
             }
         }
     }
     
     /// Draw rectangle
-    pub fn draw_rect(&mut self) -> crate::shapes::RectBuilder {
+    pub fn draw_rect(&mut self) -> crate::shapes::RectBuilder<'_> {
         crate::shapes::RectBuilder::new(self, 0.0, 0.0, 100.0, 100.0)
     }
     
     /// Draw circle
-    pub fn draw_circle(&mut self) -> crate::shapes::CircleBuilder {
+    pub fn draw_circle(&mut self) -> crate::shapes::CircleBuilder<'_> {
         crate::shapes::CircleBuilder::new(self, 0.0, 0.0, 50.0)
     }
     
+    /// Draw rounded rectangle
+    pub fn draw_rounded_rect(&mut self) -> crate::shapes::RoundedRectBuilder<'_> {
+        crate::shapes::RoundedRectBuilder::new(self, 0.0, 0.0, 100.0, 100.0)
+    }
+    
+    /// Draw ellipse
+    pub fn draw_ellipse(&mut self) -> crate::shapes::EllipseBuilder<'_> {
+        crate::shapes::EllipseBuilder::new(self, 0.0, 0.0, 50.0, 50.0)
+    }
+    
+    /// Draw line
+    pub fn draw_line(&mut self) -> crate::shapes::LineBuilder<'_> {
+        crate::shapes::LineBuilder::new(self, 0.0, 0.0, 100.0, 0.0)
+    }
+    
+    /// Draw polyline, points are added with `add_point` or `points`
+    pub fn draw_polyline(&mut self) -> crate::shapes::PolylineBuilder<'_> {
+        crate::shapes::PolylineBuilder::new(self)
+    }
+    
+    /// Draw polygon, points are added with `add_point` or `points`
+    pub fn draw_polygon(&mut self) -> crate::shapes::PolygonBuilder<'_> {
+        crate::shapes::PolygonBuilder::new(self)
+    }
+    
+    /// Draw triangle
+    pub fn draw_triangle(&mut self) -> crate::shapes::TriangleBuilder<'_> {
+        use crate::geometry::Point;
+        crate::shapes::TriangleBuilder::new(self, Point::new(50.0, 0.0), Point::new(100.0, 100.0), Point::new(0.0, 100.0))
+    }
+    
+    /// Draw arc
+    pub fn draw_arc(&mut self) -> crate::shapes::ArcBuilder<'_> {
+        crate::shapes::ArcBuilder::new(self, crate::geometry::Point::ZERO, 50.0, 0.0, 90.0)
+    }
+    
+    /// Draw quadratic bezier curve, `controls` makes it cubic
+    pub fn draw_bezier(&mut self) -> crate::shapes::BezierBuilder<'_> {
+        use crate::geometry::Point;
+        crate::shapes::BezierBuilder::quadratic(self, Point::ZERO, Point::new(50.0, 100.0), Point::new(100.0, 0.0))
+    }
+    
+    /// Draw custom path
+    pub fn draw_path(&mut self) -> crate::shapes::PathBuilder<'_> {
+        crate::shapes::PathBuilder::new(self)
+    }
+    
+    /// Draw star
+    pub fn draw_star(&mut self) -> crate::shapes::StarBuilder<'_> {
+        crate::shapes::StarBuilder::new(self, crate::geometry::Point::ZERO, 50.0, 25.0, 5)
+    }
+    
+    /// Draw many owned shapes in one pass
+    pub fn draw_batch(&mut self) -> crate::shapes::BatchBuilder<'_> {
+        crate::shapes::BatchBuilder::new(self)
+    }
+    
+    /// Draw an owned shape or text with a reusable style
+    pub fn draw_shape(&mut self, shape: &impl crate::shapes::Drawable, style: &crate::shapes::Style) -> Result<&mut Self> {
+        shape.draw_on(self, style)?;
+        Ok(self)
+    }
+    
+    /// Paint the whole layer with `color`, over its current content
+    ///
+    /// Takes any fill, so gradients, mesh gradients and patterns work as backgrounds.
+    pub fn fill(&mut self, color: crate::color::Color) -> Result<&mut Self> {
+        let (width, height) = self.dimensions();
+        self.draw_rect()
+            .position(0.0, 0.0)
+            .size(width as f32, height as f32)
+            .fill(color)
+            .draw()
+    }
+    
     /// Draw text
-    pub fn draw_text(&mut self, text: &str) -> crate::text::TextBuilder {
+    pub fn draw_text(&mut self, text: &str) -> crate::text::TextBuilder<'_> {
         use crate::text::TextStyle;
         use crate::geometry::Point;
         let style = TextStyle::default();
@@ -126,7 +306,7 @@ impl Layer {
     }
     
     /// Draw image
-    pub fn draw_image(&mut self, image: crate::image::Image) -> ImageBuilder {
+    pub fn draw_image(&mut self, image: crate::image::Image) -> ImageBuilder<'_> {
         ImageBuilder::new(self, image)
     }
     
@@ -134,92 +314,117 @@ impl Layer {
     pub fn apply_filter(&mut self, filter: crate::filter::Filter) -> Result<&mut Self> {
         // Apply filter to layer content
         let filtered_content = filter.apply(&self.content)?;
-        self.content = filtered_content;
+        self.replace_content("Apply filter", filtered_content);
         Ok(self)
     }
     
     /// Set blend mode
     pub fn blend_mode(&mut self, mode: BlendMode) -> &mut Self {
-        self.blend_mode = mode;
-        self
+        self.edit_properties("Set blend mode", |layer| layer.blend_mode = mode)
     }
     
     /// Set layer opacity
     pub fn opacity(&mut self, opacity: f32) -> &mut Self {
-        self.opacity = opacity.clamp(0.0, 1.0);
-        self
+        self.edit_properties("Set opacity", |layer| layer.opacity = opacity.clamp(0.0, 1.0))
+    }
+    
+    /// Rename layer
+    pub fn rename(&mut self, name: &str) -> &mut Self {
+        self.edit_properties("Rename layer", |layer| layer.name = name.to_string())
+    }
+    
+    /// Move layer to a position on the canvas
+    pub fn position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.edit_properties("Move layer", |layer| {
+            layer.x = x;
+            layer.y = y;
+        })
+    }
+    
+    /// Show or hide layer
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.edit_properties("Set visibility", |layer| layer.visible = visible)
+    }
+    
+    /// Check if this is an adjustment layer
+    pub fn is_adjustment(&self) -> bool {
+        self.adjustment.is_some()
+    }
+    
+    /// Append a filter to the adjustment chain, turning this into an adjustment layer
+    pub fn adjust(&mut self, filter: crate::filter::Filter) -> &mut Self {
+        self.edit_properties("Adjust", |layer| {
+            layer.adjustment.get_or_insert_with(Adjustment::default).filters.push(filter);
+        })
+    }
+    
+    /// Set layer mask
+    pub fn mask(&mut self, mask: GrayImage) -> &mut Self {
+        self.edit_properties("Set mask", |layer| layer.mask = Some(mask))
+    }
+    
+    /// Remove layer mask
+    pub fn clear_mask(&mut self) -> &mut Self {
+        self.edit_properties("Clear mask", |layer| layer.mask = None)
     }
     
     /// Get pixmap for drawing (converts RgbaImage to Pixmap)
     pub(crate) fn get_pixmap_mut(&mut self) -> Result<Pixmap> {
-        let (width, height) = self.dimensions();
-        let mut pixmap = Pixmap::new(width, height)
-            .ok_or_else(|| crate::error::CloveError::InvalidDimensions { width, height })?;
-        
-        // Copy RgbaImage data to Pixmap
-        let img_data = self.content.as_raw();
-        let pixmap_data = pixmap.data_mut();
-        
-        // tiny-skia uses premultiplied alpha, image crate uses straight alpha
-        // We need to convert
-        for (i, chunk) in img_data.chunks_exact(4).enumerate() {
-            let r = chunk[0];
-            let g = chunk[1];
-            let b = chunk[2];
-            let a = chunk[3];
-            
-            let idx = i * 4;
-            if idx + 3 < pixmap_data.len() {
-                // Premultiply alpha
-                let alpha_f = a as f32 / 255.0;
-                pixmap_data[idx] = (r as f32 * alpha_f) as u8;
-                pixmap_data[idx + 1] = (g as f32 * alpha_f) as u8;
-                pixmap_data[idx + 2] = (b as f32 * alpha_f) as u8;
-                pixmap_data[idx + 3] = a;
-            }
-        }
-        
-        Ok(pixmap)
+        layer_to_pixmap(self)
     }
     
     /// Update layer content from pixmap (converts Pixmap back to RgbaImage)
     pub(crate) fn update_from_pixmap(&mut self, pixmap: &Pixmap) -> Result<()> {
-        let width = pixmap.width();
-        let height = pixmap.height();
-        
-        // Create new RgbaImage
-        let mut img = RgbaImage::new(width, height);
-        let img_data = img.as_mut();
-        let pixmap_data = pixmap.data();
-        
-        // Convert from premultiplied alpha to straight alpha
-        for (i, chunk) in pixmap_data.chunks_exact(4).enumerate() {
-            let r_pre = chunk[0];
-            let g_pre = chunk[1];
-            let b_pre = chunk[2];
-            let a = chunk[3];
-            
-            let idx = i * 4;
-            if idx + 3 < img_data.len() && a > 0 {
-                // Unpremultiply alpha
-                let alpha_f = a as f32 / 255.0;
-                img_data[idx] = (r_pre as f32 / alpha_f) as u8;
-                img_data[idx + 1] = (g_pre as f32 / alpha_f) as u8;
-                img_data[idx + 2] = (b_pre as f32 / alpha_f) as u8;
-                img_data[idx + 3] = a;
-            } else if idx + 3 < img_data.len() {
-                img_data[idx] = r_pre;
-                img_data[idx + 1] = g_pre;
-                img_data[idx + 2] = b_pre;
-                img_data[idx + 3] = a;
-            }
-        }
-        
-        self.content = img;
+        self.replace_content("Draw", pixmap_to_image(pixmap));
         Ok(())
     }
 }
 
+/// Copy straight-alpha RGBA bytes into premultiplied pixmap bytes
+pub(crate) fn premultiply_into(src: &[u8], dst: &mut [u8]) {
+    for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+        let alpha_f = s[3] as f32 / 255.0;
+        d[0] = (s[0] as f32 * alpha_f) as u8;
+        d[1] = (s[1] as f32 * alpha_f) as u8;
+        d[2] = (s[2] as f32 * alpha_f) as u8;
+        d[3] = s[3];
+    }
+}
+
+/// Copy premultiplied pixmap bytes into straight-alpha RGBA bytes
+pub(crate) fn unpremultiply_into(src: &[u8], dst: &mut [u8]) {
+    for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+        let a = s[3];
+        if a > 0 {
+            let alpha_f = a as f32 / 255.0;
+            d[0] = (s[0] as f32 / alpha_f) as u8;
+            d[1] = (s[1] as f32 / alpha_f) as u8;
+            d[2] = (s[2] as f32 / alpha_f) as u8;
+        } else {
+            d[0] = s[0];
+            d[1] = s[1];
+            d[2] = s[2];
+        }
+        d[3] = a;
+    }
+}
+
+/// Convert a straight-alpha image into a premultiplied pixmap of the same size
+pub(crate) fn image_to_pixmap(image: &RgbaImage) -> Result<Pixmap> {
+    let (width, height) = image.dimensions();
+    let mut pixmap = Pixmap::new(width, height)
+        .ok_or(crate::error::CloveError::InvalidDimensions { width, height })?;
+    premultiply_into(image.as_raw(), pixmap.data_mut());
+    Ok(pixmap)
+}
+
+/// Convert a premultiplied pixmap into a straight-alpha image of the same size
+pub(crate) fn pixmap_to_image(pixmap: &Pixmap) -> RgbaImage {
+    let mut image = RgbaImage::new(pixmap.width(), pixmap.height());
+    unpremultiply_into(pixmap.data(), image.as_mut());
+    image
+}
+
 /// Image builder for drawing images on layers
 pub struct ImageBuilder<'a> {
     layer: &'a mut Layer,
@@ -227,6 +432,8 @@ pub struct ImageBuilder<'a> {
     x: f32,
     y: f32,
     opacity: f32,
+    blend_mode: BlendMode,
+    transform: TransformOps,
 }
 
 impl<'a> ImageBuilder<'a> {
@@ -237,6 +444,8 @@ impl<'a> ImageBuilder<'a> {
             x: 0.0,
             y: 0.0,
             opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            transform: TransformOps::default(),
         }
     }
     
@@ -251,6 +460,13 @@ impl<'a> ImageBuilder<'a> {
         self
     }
     
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
+    crate::transform::transform_methods!("image");
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
         use crate::error::CloveError;
         
@@ -266,17 +482,32 @@ impl<'a> ImageBuilder<'a> {
             ).ok_or_else(|| CloveError::InvalidState("Invalid image size".to_string()))?,
         ).ok_or_else(|| CloveError::InvalidState("Failed to create pixmap from image".to_string()))?;
         
-        // Create paint with opacity
-        let mut paint = tiny_skia::PixmapPaint::default();
-        paint.opacity = self.opacity;
+        // Create paint with opacity and blend mode, smoothing only when transformed
+        let paint = tiny_skia::PixmapPaint {
+            opacity: self.opacity,
+            blend_mode: self.blend_mode.to_skia(),
+            quality: if self.transform.is_identity() {
+                tiny_skia::FilterQuality::Nearest
+            } else {
+                tiny_skia::FilterQuality::Bilinear
+            },
+        };
+        
+        let (x, y) = (self.x.trunc(), self.y.trunc());
+        let center = crate::geometry::Point::new(
+            x + image_pixmap.width() as f32 / 2.0,
+            y + image_pixmap.height() as f32 / 2.0,
+        );
+        let transform = tiny_skia::Transform::from_translate(x, y)
+            .post_concat(self.transform.resolve(center));
         
         // Draw image onto layer pixmap
         pixmap.draw_pixmap(
-            self.x as i32,
-            self.y as i32,
+            0,
+            0,
             image_pixmap.as_ref(),
             &paint,
-            tiny_skia::Transform::identity(),
+            transform,
             None,
         );
         
@@ -293,6 +524,7 @@ pub struct LayerManager {
     active: Option<LayerId>,
     base_width: u32,
     base_height: u32,
+    history: Option<std::sync::Arc<std::sync::Mutex<History>>>,
 }
 
 impl LayerManager {
@@ -302,7 +534,54 @@ impl LayerManager {
             active: None,
             base_width,
             base_height,
+            history: None,
+        }
+    }
+    
+    /// Start recording layer edits into undo history
+    pub fn enable_history(&mut self, history: History) {
+        let history = std::sync::Arc::new(std::sync::Mutex::new(history));
+        for layer in &mut self.layers {
+            layer.set_history(Some(history.clone()));
+        }
+        self.history = Some(history);
+    }
+    
+    /// Stop recording and drop all history
+    pub fn disable_history(&mut self) {
+        for layer in &mut self.layers {
+            layer.set_history(None);
+        }
+        self.history = None;
+    }
+    
+    /// Lock the undo history, if enabled
+    pub fn history(&self) -> Option<std::sync::MutexGuard<'_, History>> {
+        self.history.as_ref().and_then(|h| h.lock().ok())
+    }
+    
+    /// Revert the last recorded step, returns false if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(mut entry) = self.history().and_then(|mut h| h.take_undo()) else {
+            return false;
+        };
+        entry.undo(&mut self.layers);
+        if let Some(mut history) = self.history() {
+            history.push_redo(entry);
         }
+        true
+    }
+    
+    /// Reapply the last undone step, returns false if there was nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(mut entry) = self.history().and_then(|mut h| h.take_redo()) else {
+            return false;
+        };
+        entry.redo(&mut self.layers, self.history.as_ref());
+        if let Some(mut history) = self.history() {
+            history.push_undo(entry);
+        }
+        true
     }
     
     /// Create layer with canvas dimensions
@@ -312,8 +591,7 @@ impl LayerManager {
         if let Some(fm) = font_manager {
             layer.font_manager = Some(fm);
         }
-        self.layers.push(layer);
-        id
+        self.push(layer)
     }
     
     /// Create layer with custom dimensions
@@ -325,6 +603,25 @@ impl LayerManager {
         if let Some(fm) = font_manager {
             layer.font_manager = Some(fm);
         }
+        self.push(layer)
+    }
+    
+    /// Create an adjustment layer that filters the composite of all layers below it
+    pub fn create_adjustment(&mut self, name: &str, adjustment: Adjustment) -> LayerId {
+        let id = LayerId::new();
+        let mut layer = Layer::new(id, name, self.base_width, self.base_height);
+        layer.adjustment = Some(adjustment);
+        self.push(layer)
+    }
+    
+    /// Add an existing layer on top of the stack
+    pub fn push(&mut self, mut layer: Layer) -> LayerId {
+        let id = layer.id;
+        layer.set_history(self.history.clone());
+        if let Some(mut history) = self.history() {
+            let label = format!("Create layer '{}'", layer.name);
+            history.record(&label, Change::Created { layer: id, index: self.layers.len(), removed: None });
+        }
         self.layers.push(layer);
         id
     }
@@ -334,95 +631,60 @@ impl LayerManager {
         self.layers.iter_mut().find(|l| l.id == id)
     }
     
+    /// Get all layers, bottom to top
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+    
+    /// Get canvas dimensions the layers composite onto
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.base_width, self.base_height)
+    }
+    
+    /// Find the topmost visible layer with a pixel at a canvas point
+    ///
+    /// A pixel counts when its alpha, scaled by layer opacity and mask, is at least
+    /// `alpha_threshold` (and never zero). Adjustment layers have no pixels and are skipped.
+    pub fn hit_test(&self, x: f32, y: f32, alpha_threshold: u8) -> Option<LayerId> {
+        self.layers.iter().rev()
+            .filter(|l| l.visible && !l.is_adjustment())
+            .find(|l| l.alpha_at(x, y) >= alpha_threshold.max(1))
+            .map(|l| l.id)
+    }
+    
     /// Merge all visible layers
     pub fn merge_all(&self) -> Result<RgbaImage> {
-        use tiny_skia::{Pixmap, Paint, Transform};
-        
-        let visible_layers: Vec<_> = self.layers.iter()
-            .filter(|l| l.visible)
-            .collect();
+        use tiny_skia::Transform;
         
-        if visible_layers.is_empty() {
+        if !self.layers.iter().any(|l| l.visible) {
             return Ok(RgbaImage::new(self.base_width, self.base_height));
         }
         
         // Create result pixmap
         let mut result_pixmap = Pixmap::new(self.base_width, self.base_height)
-            .ok_or_else(|| crate::error::CloveError::InvalidDimensions { 
+            .ok_or(crate::error::CloveError::InvalidDimensions { 
                 width: self.base_width, 
                 height: self.base_height 
             })?;
         
-        // Convert each layer to pixmap and composite
-        for layer in visible_layers.iter() {
-            // Convert layer content to pixmap
-            let layer_pixmap = {
-                let (width, height) = layer.dimensions();
-                let mut pixmap = Pixmap::new(width, height)
-                    .ok_or_else(|| crate::error::CloveError::InvalidDimensions { width, height })?;
-                
-                // Copy layer content to pixmap
-                let img_data = layer.content.as_raw();
-                let pixmap_data = pixmap.data_mut();
-                
-                for (i, chunk) in img_data.chunks_exact(4).enumerate() {
-                    let r = chunk[0];
-                    let g = chunk[1];
-                    let b = chunk[2];
-                    let a = chunk[3];
-                    
-                    let idx = i * 4;
-                    if idx + 3 < pixmap_data.len() {
-                        // Premultiply alpha
-                        let alpha_f = a as f32 / 255.0;
-                        pixmap_data[idx] = (r as f32 * alpha_f) as u8;
-                        pixmap_data[idx + 1] = (g as f32 * alpha_f) as u8;
-                        pixmap_data[idx + 2] = (b as f32 * alpha_f) as u8;
-                        pixmap_data[idx + 3] = a;
-                    }
-                }
-                
-                pixmap
-            };
-            
-            // Create paint with opacity and blend mode
-            let mut paint = Paint::default();
-            paint.anti_alias = false;
-            
-            // Set blend mode
-            match layer.blend_mode {
-                BlendMode::Normal => paint.blend_mode = tiny_skia::BlendMode::SourceOver,
-                BlendMode::Multiply => paint.blend_mode = tiny_skia::BlendMode::Multiply,
-                BlendMode::Screen => paint.blend_mode = tiny_skia::BlendMode::Screen,
-                BlendMode::Overlay => paint.blend_mode = tiny_skia::BlendMode::Overlay,
-                BlendMode::Darken => paint.blend_mode = tiny_skia::BlendMode::Darken,
-                BlendMode::Lighten => paint.blend_mode = tiny_skia::BlendMode::Lighten,
-                BlendMode::ColorDodge => paint.blend_mode = tiny_skia::BlendMode::ColorDodge,
-                BlendMode::ColorBurn => paint.blend_mode = tiny_skia::BlendMode::ColorBurn,
-                BlendMode::HardLight => paint.blend_mode = tiny_skia::BlendMode::HardLight,
-                BlendMode::SoftLight => paint.blend_mode = tiny_skia::BlendMode::SoftLight,
+        for layer in self.layers.iter().filter(|l| l.visible) {
+            if let Some(adjustment) = &layer.adjustment {
+                Self::composite_adjustment(&mut result_pixmap, layer, adjustment)?;
+                continue;
             }
             
-            // Apply opacity by creating a pixmap paint
-            let blend_mode = match layer.blend_mode {
-                BlendMode::Normal => tiny_skia::BlendMode::SourceOver,
-                BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
-                BlendMode::Screen => tiny_skia::BlendMode::Screen,
-                BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
-                BlendMode::Darken => tiny_skia::BlendMode::Darken,
-                BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
-                BlendMode::ColorDodge => tiny_skia::BlendMode::ColorDodge,
-                BlendMode::ColorBurn => tiny_skia::BlendMode::ColorBurn,
-                BlendMode::HardLight => tiny_skia::BlendMode::HardLight,
-                BlendMode::SoftLight => tiny_skia::BlendMode::SoftLight,
-            };
+            // Convert layer content to pixmap
+            let layer_pixmap = layer_to_pixmap(layer)?;
             
             let pixmap_paint = tiny_skia::PixmapPaint {
                 quality: tiny_skia::FilterQuality::Nearest,
                 opacity: layer.opacity,
-                blend_mode,
+                blend_mode: layer.blend_mode.to_skia(),
             };
             
+            let mask = layer.mask.as_ref()
+                .and_then(|m| skia_mask(m, self.base_width, self.base_height));
+            
             // Draw layer onto result with position and transform
             let transform = Transform::from_translate(layer.x, layer.y);
             result_pixmap.draw_pixmap(
@@ -430,38 +692,206 @@ impl LayerManager {
                 layer_pixmap.as_ref(),
                 &pixmap_paint,
                 transform,
-                None,
+                mask.as_ref(),
             );
         }
         
-        // Convert result pixmap back to RgbaImage
-        let mut result = RgbaImage::new(self.base_width, self.base_height);
-        let result_data = result.as_mut();
-        let pixmap_data = result_pixmap.data();
+        Ok(pixmap_to_image(&result_pixmap))
+    }
+    
+    /// Filter everything composited so far and blend it back in through opacity and mask
+    fn composite_adjustment(target: &mut Pixmap, layer: &Layer, adjustment: &Adjustment) -> Result<()> {
+        let adjusted = image_to_pixmap(&adjustment.apply(&pixmap_to_image(target))?)?;
         
-        for (i, chunk) in pixmap_data.chunks_exact(4).enumerate() {
-            let r_pre = chunk[0];
-            let g_pre = chunk[1];
-            let b_pre = chunk[2];
-            let a = chunk[3];
-            
-            let idx = i * 4;
-            if idx + 3 < result_data.len() && a > 0 {
-                // Unpremultiply alpha
-                let alpha_f = a as f32 / 255.0;
-                result_data[idx] = (r_pre as f32 / alpha_f) as u8;
-                result_data[idx + 1] = (g_pre as f32 / alpha_f) as u8;
-                result_data[idx + 2] = (b_pre as f32 / alpha_f) as u8;
-                result_data[idx + 3] = a;
-            } else if idx + 3 < result_data.len() {
-                result_data[idx] = r_pre;
-                result_data[idx + 1] = g_pre;
-                result_data[idx + 2] = b_pre;
-                result_data[idx + 3] = a;
+        // Normal replaces the pixels outright; other modes blend the adjusted
+        // copy over the original before the coverage lerp below
+        let blended = if layer.blend_mode == BlendMode::Normal {
+            adjusted
+        } else {
+            let mut blended = target.clone();
+            blended.draw_pixmap(
+                0, 0,
+                adjusted.as_ref(),
+                &tiny_skia::PixmapPaint {
+                    quality: tiny_skia::FilterQuality::Nearest,
+                    opacity: 1.0,
+                    blend_mode: layer.blend_mode.to_skia(),
+                },
+                tiny_skia::Transform::identity(),
+                None,
+            );
+            blended
+        };
+        
+        let width = target.width();
+        for (i, (dst, src)) in target.data_mut().chunks_exact_mut(4)
+            .zip(blended.data().chunks_exact(4))
+            .enumerate()
+        {
+            let x = i as u32 % width;
+            let y = i as u32 / width;
+            let mask_value = match &layer.mask {
+                Some(mask) if x < mask.width() && y < mask.height() => mask.get_pixel(x, y)[0] as f32 / 255.0,
+                Some(_) => 0.0,
+                None => 1.0,
+            };
+            let coverage = layer.opacity * mask_value;
+            if coverage <= 0.0 {
+                continue;
+            }
+            for c in 0..4 {
+                dst[c] = (dst[c] as f32 + (src[c] as f32 - dst[c] as f32) * coverage).round() as u8;
             }
         }
         
-        Ok(result)
+        Ok(())
+    }
+}
+
+/// Convert layer content to a pixmap of the layer's effective dimensions
+fn layer_to_pixmap(layer: &Layer) -> Result<Pixmap> {
+    let (width, height) = layer.dimensions();
+    let mut pixmap = Pixmap::new(width, height)
+        .ok_or(crate::error::CloveError::InvalidDimensions { width, height })?;
+    
+    // tiny-skia uses premultiplied alpha, image crate uses straight alpha
+    premultiply_into(layer.content.as_raw(), pixmap.data_mut());
+    Ok(pixmap)
+}
+
+/// Build a canvas-sized tiny-skia mask from a grayscale layer mask
+fn skia_mask(mask: &GrayImage, width: u32, height: u32) -> Option<tiny_skia::Mask> {
+    let mut skia_mask = tiny_skia::Mask::new(width, height)?;
+    let data = skia_mask.data_mut();
+    for y in 0..height.min(mask.height()) {
+        for x in 0..width.min(mask.width()) {
+            data[(y * width + x) as usize] = mask.get_pixel(x, y)[0];
+        }
     }
+    Some(skia_mask)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::Filter;
+    
+    #[test]
+    fn test_adjustment_filters_layers_below_only() {
+        let mut manager = LayerManager::new(3, 1);
+        let below = manager.create("below", None);
+        manager.get(below).unwrap().content = RgbaImage::from_pixel(3, 1, image::Rgba([255, 0, 0, 255]));
+        
+        let adjustment = manager.create_adjustment("invert", Adjustment::new(Filter::Invert));
+        let mut mask = GrayImage::new(3, 1);
+        mask.put_pixel(0, 0, image::Luma([255]));
+        mask.put_pixel(2, 0, image::Luma([255]));
+        manager.get(adjustment).unwrap().mask(mask);
+        
+        let above = manager.create_with_size("above", 1, 1, None);
+        manager.get(above).unwrap().content = RgbaImage::from_pixel(1, 1, image::Rgba([0, 0, 255, 255]));
+        
+        let merged = manager.merge_all().unwrap();
+        // Covered by the layer above, masked out, and filtered
+        assert_eq!(merged.get_pixel(0, 0).0, [0, 0, 255, 255]);
+        assert_eq!(merged.get_pixel(1, 0).0, [255, 0, 0, 255]);
+        assert_eq!(merged.get_pixel(2, 0).0, [0, 255, 255, 255]);
+    }
+    
+    #[test]
+    fn test_adjustment_opacity_mixes_with_original() {
+        let mut manager = LayerManager::new(1, 1);
+        let below = manager.create("below", None);
+        manager.get(below).unwrap().content = RgbaImage::from_pixel(1, 1, image::Rgba([0, 0, 0, 255]));
+        let adjustment = manager.create_adjustment("invert", Filter::Invert.into());
+        manager.get(adjustment).unwrap().opacity(0.5);
+        
+        let merged = manager.merge_all().unwrap();
+        assert_eq!(merged.get_pixel(0, 0).0, [128, 128, 128, 255]);
+    }
+    
+    #[test]
+    fn test_undo_redo_draw_property_and_creation() {
+        let mut manager = LayerManager::new(100, 100);
+        manager.enable_history(History::default());
+        let id = manager.create("shapes", None);
+        
+        let layer = manager.get(id).unwrap();
+        let mut pixmap = layer.get_pixmap_mut().unwrap();
+        pixmap.fill(tiny_skia::Color::WHITE);
+        layer.update_from_pixmap(&pixmap).unwrap();
+        layer.opacity(0.5);
+        
+        assert!(manager.undo());
+        assert_eq!(manager.get(id).unwrap().opacity, 1.0);
+        assert!(manager.undo());
+        assert_eq!(manager.get(id).unwrap().content.get_pixel(99, 99)[3], 0);
+        assert!(manager.undo());
+        assert!(manager.layers().is_empty());
+        assert!(!manager.undo());
+        
+        assert!(manager.redo());
+        assert!(manager.redo());
+        assert_eq!(manager.get(id).unwrap().content.get_pixel(99, 99).0, [255, 255, 255, 255]);
+        assert!(manager.redo());
+        assert_eq!(manager.get(id).unwrap().opacity, 0.5);
+        assert!(!manager.redo());
+    }
+    
+    #[test]
+    fn test_undone_creation_is_detached_and_counted() {
+        let mut manager = LayerManager::new(100, 100);
+        manager.enable_history(History::default());
+        let id = manager.create("big", None);
+        let created = manager.history().unwrap().memory_usage();
+        
+        assert!(manager.undo());
+        assert!(manager.history().unwrap().memory_usage() >= created + 100 * 100 * 4);
+        // Only the manager holds the history while the layer sits in the redo step
+        assert_eq!(std::sync::Arc::strong_count(manager.history.as_ref().unwrap()), 1);
+        
+        assert!(manager.redo());
+        assert_eq!(manager.history().unwrap().memory_usage(), created);
+        manager.get(id).unwrap().opacity(0.5);
+        assert_eq!(manager.history().unwrap().undo_label(), Some("Set opacity"));
+    }
+    
+    #[test]
+    fn test_hit_test_topmost_with_offset_and_opacity() {
+        let mut manager = LayerManager::new(20, 20);
+        let bottom = manager.create("bottom", None);
+        manager.get(bottom).unwrap().content = RgbaImage::from_pixel(20, 20, image::Rgba([0, 0, 0, 255]));
+        
+        let top = manager.create_with_size("top", 5, 5, None);
+        let layer = manager.get(top).unwrap();
+        layer.content = RgbaImage::from_pixel(5, 5, image::Rgba([255, 0, 0, 255]));
+        layer.position(10.0, 10.0);
+        
+        assert_eq!(manager.hit_test(12.0, 12.0, 1), Some(top));
+        assert_eq!(manager.hit_test(2.0, 2.0, 1), Some(bottom));
+        
+        manager.get(top).unwrap().opacity(0.25);
+        assert_eq!(manager.hit_test(12.0, 12.0, 128), Some(bottom));
+        
+        manager.get(bottom).unwrap().set_visible(false);
+        assert_eq!(manager.hit_test(2.0, 2.0, 1), None);
+    }
+
+    #[test]
+    fn test_layer_entry_points_share_options() {
+        use crate::color::Color;
+        use crate::shapes::{Circle, Style};
+        let mut layer = Layer::new(LayerId::new(), "shapes", 40, 40);
+        let red = Color::rgba(255, 0, 0, 255);
+        
+        layer.draw_ellipse().center(10.0, 10.0).radius(8.0, 4.0).fill(red.clone()).opacity(0.5).draw().unwrap();
+        layer.draw_star().center(30.0, 10.0).radius(8.0, 4.0).fill(red.clone()).draw().unwrap();
+        layer.draw_line().from(0.0, 30.0).to(40.0, 30.0).stroke(red.clone(), 2.0).opacity(0.5).draw().unwrap();
+        layer.draw_shape(&Circle::new(30.0, 20.0, 2.0), &Style::new().fill(red)).unwrap();
+        
+        assert!((126..=129).contains(&layer.alpha_at(10.0, 10.0)));
+        assert_eq!(layer.alpha_at(30.0, 10.0), 255);
+        assert!((126..=129).contains(&layer.alpha_at(20.0, 30.0)));
+        assert_eq!(layer.alpha_at(30.0, 20.0), 255);
+    }
+}
@@ -9,6 +9,7 @@ pub mod text;
 pub mod image;
 pub mod layer;
 pub mod filter;
+pub mod history;
 pub mod transform;
 
 pub mod prelude;
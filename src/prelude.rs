@@ -2,12 +2,14 @@
 
 pub use crate::error::{Result, CloveError};
 pub use crate::geometry::{Point, Size, Rect, Bounds};
-pub use crate::color::{Color, Rgba, Hsla, NamedColor, LinearGradient, RadialGradient, LinearGradientBuilder, RadialGradientBuilder};
+pub use crate::color::{Color, Rgba, Hsla, Hsva, Hwba, Lab, Lch, Oklab, Oklch, Cmyk, NamedColor, LinearGradient, RadialGradient, ConicGradient, LinearGradientBuilder, RadialGradientBuilder, ConicGradientBuilder, GradientUnits, SpreadMode, GradientStop, InterpolationSpace, HueDirection, Easing, Dither, Pattern, PatternMode, MeshGradient, MeshGradientBuilder, FreeformGradient, FreeformGradientBuilder};
 pub use crate::canvas::{Canvas, CanvasBuilder};
-pub use crate::text::{FontManager, TextStyle, TextAlign, TextWidth, FontWeight, FontStyle, TextBuilder};
-pub use crate::layer::{Layer, LayerManager, BlendMode};
-pub use crate::shapes::{RectBuilder, CircleBuilder, RoundedRectBuilder, EllipseBuilder, LineBuilder};
+pub use crate::text::{FontManager, TextStyle, TextAlign, TextWidth, FontWeight, FontStyle, TextBuilder, Text};
+pub use crate::layer::{Layer, LayerManager, BlendMode, Adjustment};
+pub use crate::shapes::{RectBuilder, CircleBuilder, RoundedRectBuilder, EllipseBuilder, LineBuilder, StrokeStyle, LineCap, LineJoin, FillRule, Path2D, BooleanOp,
+    Shape, Drawable, Style, Circle, Ellipse, Polygon, Star, Bezier, Instance};
 pub use crate::image::{ImageFormat, ImageLoader, Image};
 pub use crate::filter::Filter;
 pub use crate::transform::Transform;
+pub use crate::history::History;
 
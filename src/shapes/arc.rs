@@ -1,8 +1,10 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::geometry::Point;
 use crate::error::Result;
-use tiny_skia::{Paint, PathBuilder, Transform};
+use crate::transform::TransformOps;
+use crate::shapes::{FillRule, Shadow, StrokeStyle};
+use tiny_skia::{Path, PathBuilder};
 
 /// Arc builder
 pub struct ArcBuilder<'a> {
@@ -12,7 +14,12 @@ pub struct ArcBuilder<'a> {
     start_angle: f32,
     end_angle: f32,
     stroke: Option<(Color, f32)>,
+    stroke_style: StrokeStyle,
+    shadow: Option<Shadow>,
+    transform: TransformOps,
     fill: Option<Color>,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> ArcBuilder<'a> {
@@ -24,126 +31,153 @@ impl<'a> ArcBuilder<'a> {
             start_angle,
             end_angle,
             stroke: None,
+            stroke_style: StrokeStyle::default(),
+            shadow: None,
+            transform: TransformOps::default(),
             fill: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center = Point::new(x, y);
+        self
+    }
+    
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+    
+    /// Set start and end angles in degrees
+    pub fn angles(mut self, start_angle: f32, end_angle: f32) -> Self {
+        self.start_angle = start_angle;
+        self.end_angle = end_angle;
+        self
+    }
+    
     pub fn stroke(mut self, color: Color, width: f32) -> Self {
         self.stroke = Some((color, width));
         self
     }
     
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+    
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
     pub fn fill(mut self, color: Color) -> Self {
         self.fill = Some(color);
         self
     }
     
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
         // Get pixmap from layer
         let mut pixmap = self.layer.get_pixmap_mut()?;
         
-        // Convert angles from degrees to radians
-        let start_rad = self.start_angle.to_radians();
-        let end_rad = self.end_angle.to_radians();
+        let path = arc_path(self.center, self.radius, self.start_angle, self.end_angle, self.fill.is_some()).ok_or_else(|| {
+            crate::error::CloveError::InvalidState("Failed to create arc path".to_string())
+        })?;
         
-        // Create arc path using smooth bezier curves
-        let mut path_builder = PathBuilder::new();
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: self.fill.as_ref(),
+            fill_rule: FillRule::NonZero,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
-        // Start point
-        let start_x = self.center.x + self.radius * start_rad.cos();
-        let start_y = self.center.y + self.radius * start_rad.sin();
-        path_builder.move_to(start_x, start_y);
+        // Update layer content from pixmap
+        self.layer.update_from_pixmap(&pixmap)?;
         
-        // Create arc using cubic bezier curves for smooth arc
-        let angle_diff = end_rad - start_rad;
+        Ok(self.layer)
+    }
+}
+
+/// Circular arc from `start_angle` to `end_angle` in degrees, closed through the center when `pie`
+///
+/// Shared by `ArcBuilder` and the `Arc` shape.
+pub(crate) fn arc_path(center: Point, radius: f32, start_angle: f32, end_angle: f32, pie: bool) -> Option<Path> {
+    // Convert angles from degrees to radians
+    let start_rad = start_angle.to_radians();
+    let end_rad = end_angle.to_radians();
+    
+    // Create arc path using smooth bezier curves
+    let mut path_builder = PathBuilder::new();
+    
+    // Start point
+    let start_x = center.x + radius * start_rad.cos();
+    let start_y = center.y + radius * start_rad.sin();
+    path_builder.move_to(start_x, start_y);
+    
+    // Create arc using cubic bezier curves for smooth arc
+    let angle_diff = end_rad - start_rad;
+    
+    // Use bezier curves to approximate circular arc smoothly
+    // Split arc into segments of max 90 degrees each
+    let max_segment_angle = std::f32::consts::PI / 2.0;
+    let num_segments = (angle_diff.abs() / max_segment_angle).ceil() as u32;
+    let segment_angle = angle_diff / num_segments as f32;
+    
+    for i in 0..num_segments {
+        let seg_start_angle = start_rad + segment_angle * i as f32;
+        let seg_end_angle = start_rad + segment_angle * (i as f32 + 1.0);
         
-        // Use bezier curves to approximate circular arc smoothly
-        // Split arc into segments of max 90 degrees each
-        let max_segment_angle = std::f32::consts::PI / 2.0;
-        let num_segments = (angle_diff.abs() / max_segment_angle).ceil() as u32;
-        let segment_angle = angle_diff / num_segments as f32;
+        // Calculate points for bezier curve
+        let p0_x = center.x + radius * seg_start_angle.cos();
+        let p0_y = center.y + radius * seg_start_angle.sin();
         
-        for i in 0..num_segments {
-            let seg_start_angle = start_rad + segment_angle * i as f32;
-            let seg_end_angle = start_rad + segment_angle * (i as f32 + 1.0);
-            
-            // Calculate points for bezier curve
-            let p0_x = self.center.x + self.radius * seg_start_angle.cos();
-            let p0_y = self.center.y + self.radius * seg_start_angle.sin();
-            
-            let p3_x = self.center.x + self.radius * seg_end_angle.cos();
-            let p3_y = self.center.y + self.radius * seg_end_angle.sin();
-            
-            // Control points for smooth circular arc approximation
-            // Using the formula: control_point_distance = radius * (4/3) * tan(angle/4)
-            let control_distance = self.radius * (4.0 / 3.0) * (segment_angle.abs() / 4.0).tan();
-            
-            let p1_x = p0_x - control_distance * seg_start_angle.sin();
-            let p1_y = p0_y + control_distance * seg_start_angle.cos();
-            
-            let p2_x = p3_x + control_distance * seg_end_angle.sin();
-            let p2_y = p3_y - control_distance * seg_end_angle.cos();
-            
-            if i == 0 {
-                path_builder.move_to(p0_x, p0_y);
-            }
-            
-            path_builder.cubic_to(p1_x, p1_y, p2_x, p2_y, p3_x, p3_y);
-        }
+        let p3_x = center.x + radius * seg_end_angle.cos();
+        let p3_y = center.y + radius * seg_end_angle.sin();
         
-        // If fill is requested, close the path to center
-        if self.fill.is_some() {
-            path_builder.line_to(self.center.x, self.center.y);
-            path_builder.close();
-        }
+        // Control points for smooth circular arc approximation
+        // Using the formula: control_point_distance = radius * (4/3) * tan(angle/4)
+        let control_distance = radius * (4.0 / 3.0) * (segment_angle.abs() / 4.0).tan();
         
-        let path = path_builder.finish().ok_or_else(|| {
-            crate::error::CloveError::InvalidState("Failed to create arc path".to_string())
-        })?;
+        let p1_x = p0_x - control_distance * seg_start_angle.sin();
+        let p1_y = p0_y + control_distance * seg_start_angle.cos();
         
-        // Draw fill if specified
-        if let Some(fill_color) = self.fill.clone() {
-            let mut paint = Paint::default();
-            paint.anti_alias = true;
-            
-            match fill_color {
-                Color::LinearGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_linear_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                Color::RadialGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_radial_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                _ => {
-                    let rgba = fill_color.to_rgba();
-                    paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-                    pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                }
-            }
-        }
+        let p2_x = p3_x + control_distance * seg_end_angle.sin();
+        let p2_y = p3_y - control_distance * seg_end_angle.cos();
         
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+        if i == 0 {
+            path_builder.move_to(p0_x, p0_y);
         }
         
-        // Update layer content from pixmap
-        self.layer.update_from_pixmap(&pixmap)?;
-        
-        Ok(self.layer)
+        path_builder.cubic_to(p1_x, p1_y, p2_x, p2_y, p3_x, p3_y);
+    }
+    
+    // If fill is requested, close the path to center
+    if pie {
+        path_builder.line_to(center.x, center.y);
+        path_builder.close();
     }
+    
+    path_builder.finish()
 }
-
@@ -0,0 +1,426 @@
+use crate::color::Color;
+use crate::error::{CloveError, Result};
+use crate::geometry::Point;
+use crate::layer::Layer;
+use crate::transform::Transform;
+use super::{FillRule, Path2D, Shape, Style};
+use rayon::prelude::*;
+use std::sync::Arc;
+use tiny_skia::{IntRect, Mask, Paint, Path, Pixmap, PixmapMut};
+
+/// Fewest rows per band when splitting a batch across threads
+const MIN_BAND_HEIGHT: u32 = 16;
+
+/// Shapes rasterized together before their rows are composited, bounding scratch memory
+const CHUNK_SIZE: usize = 256;
+
+/// Curve flattening tolerance in layer pixels
+const FLATTEN_TOLERANCE: f32 = 0.02;
+
+/// Placement and color of one copy of a shape in a batch
+#[derive(Debug, Clone, Default)]
+pub struct Instance {
+    pub transform: Transform,
+    /// Replaces the style's fill color
+    pub fill: Option<Color>,
+}
+
+impl Instance {
+    /// Copy shifted by `(x, y)`, so a shape centered on the origin lands centered on the point
+    pub fn at(x: f32, y: f32) -> Self {
+        Self {
+            transform: Transform::new().translate(x, y),
+            fill: None,
+        }
+    }
+
+    /// Rotate by `degrees` around the shape's center
+    pub fn rotate(mut self, degrees: f32) -> Self {
+        self.transform = self.transform.rotate(degrees);
+        self
+    }
+
+    /// Scale around the shape's center
+    pub fn scale(mut self, sx: f32, sy: f32) -> Self {
+        self.transform = self.transform.scale(sx, sy);
+        self
+    }
+
+    pub fn fill(mut self, color: Color) -> Self {
+        self.fill = Some(color);
+        self
+    }
+}
+
+/// One shape ready to rasterize
+struct BatchItem {
+    path: Arc<Path>,
+    fill_rule: FillRule,
+    style: Arc<Style>,
+    fill: Option<Color>,
+    transform: tiny_skia::Transform,
+    /// Layer pixels the shape can touch, including stroke and anti-aliasing
+    area: IntRect,
+}
+
+/// Draws many shapes with a single layer conversion, rasterizing row bands in parallel
+///
+/// Shapes are painted in the order they were added, as if drawn one by one. Each shape is
+/// rasterized over its whole area and then split between bands, so where the bands meet
+/// never changes the output. Batches containing shadows are drawn on one thread since
+/// blurs cross band edges.
+pub struct BatchBuilder<'a> {
+    layer: &'a mut Layer,
+    items: Vec<BatchItem>,
+    error: Option<CloveError>,
+}
+
+impl<'a> BatchBuilder<'a> {
+    pub fn new(layer: &'a mut Layer) -> Self {
+        Self {
+            layer,
+            items: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Add a shape drawn with `style`
+    pub fn add(self, shape: &impl Shape, style: &Style) -> Self {
+        self.instances(shape, style, [Instance::default()])
+    }
+
+    /// Add copies of one shape, each placed and optionally colored by an `Instance`
+    pub fn instances(mut self, shape: &impl Shape, style: &Style, instances: impl IntoIterator<Item = Instance>) -> Self {
+        let path = shape.to_path();
+        if path.is_empty() || self.error.is_some() {
+            return self;
+        }
+        let style = Arc::new(style.clone());
+        let outline = match path.to_skia() {
+            Ok(outline) => outline,
+            Err(err) => {
+                self.error = Some(err);
+                return self;
+            }
+        };
+        let base = style.transform.resolve_for_path(&outline);
+        let bounds = outline.bounds();
+        let center = Point::new(bounds.x() + bounds.width() / 2.0, bounds.y() + bounds.height() / 2.0);
+        let transforms: Vec<_> = instances.into_iter()
+            .map(|instance| (base.post_concat(instance.transform.to_skia_about(center)), instance.fill))
+            .collect();
+
+        // Curves are flattened up front: clipping them to a band changes how they subdivide,
+        // which would leave seams between bands
+        let stretch = transforms.iter().map(|(transform, _)| stretch(transform)).fold(1.0, f32::max);
+        let skia_path = match flatten(&path, FLATTEN_TOLERANCE / stretch) {
+            Ok(skia_path) => Arc::new(skia_path),
+            Err(err) => {
+                self.error = Some(err);
+                return self;
+            }
+        };
+        let bounds = skia_path.bounds();
+        for (transform, fill) in transforms {
+            let Some(area) = device_area(bounds, transform, &style) else {
+                continue;
+            };
+            self.items.push(BatchItem {
+                path: skia_path.clone(),
+                fill_rule: path.fill_rule,
+                style: style.clone(),
+                fill,
+                transform,
+                area,
+            });
+        }
+        self
+    }
+
+    /// Number of shapes queued
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if no shapes are queued
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn draw(self) -> Result<&'a mut Layer> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        if self.items.is_empty() {
+            return Ok(self.layer);
+        }
+
+        let mut pixmap = self.layer.get_pixmap_mut()?;
+        let bands = rayon::current_num_threads() as u32 * 4;
+        let band_height = pixmap.height().div_ceil(bands.max(1)).max(MIN_BAND_HEIGHT);
+        rasterize(&self.items, &mut pixmap, band_height);
+        self.layer.update_from_pixmap(&pixmap)?;
+        Ok(self.layer)
+    }
+}
+
+/// Paint items in order, splitting the pixmap into bands of `band_height` rows
+fn rasterize(items: &[BatchItem], pixmap: &mut Pixmap, band_height: u32) {
+    if items.iter().any(|item| item.style.shadow.is_some()) {
+        for item in items {
+            super::fill_and_stroke(pixmap, &item.path, &item.shape_style(item.transform));
+        }
+        return;
+    }
+
+    let (width, height) = (pixmap.width(), pixmap.height());
+    let stride = width as usize * 4;
+    for chunk in items.chunks(CHUNK_SIZE) {
+        let rasters: Vec<_> = chunk.par_iter()
+            .filter_map(|item| item.render(width, height))
+            .collect();
+        pixmap.data_mut()
+            .par_chunks_mut(stride * band_height as usize)
+            .enumerate()
+            .for_each(|(index, rows)| {
+                let top = index as u32 * band_height;
+                let rows_in_band = (rows.len() / stride) as u32;
+                let (Some(mut band), Some(mut mask)) = (PixmapMut::from_bytes(rows, width, rows_in_band), Mask::new(width, rows_in_band)) else {
+                    return;
+                };
+                for raster in &rasters {
+                    raster.composite(&mut band, &mut mask, top);
+                }
+            });
+    }
+}
+
+/// Paint for one coverage mask of a rendered shape
+enum Shading<'a> {
+    /// Solid colors, the same for every pixel
+    Paint(Paint<'a>),
+    /// Premultiplied pixels over the raster's area, for paints rendered per pixel
+    Pixels(Pixmap),
+}
+
+/// One shape's coverage and paint over its layer area, independent of the bands
+struct Raster<'a> {
+    area: IntRect,
+    blend_mode: crate::layer::BlendMode,
+    /// Fill then stroke
+    passes: Vec<(Mask, Shading<'a>)>,
+}
+
+impl Raster<'_> {
+    /// Paint the rows of this raster that fall in a band starting at layer row `top`
+    ///
+    /// `mask` is band-sized and left cleared.
+    fn composite(&self, band: &mut PixmapMut, mask: &mut Mask, top: u32) {
+        let width = band.width() as usize;
+        let first = (self.area.y() as u32).max(top);
+        let last = (self.area.bottom() as u32).min(top + band.height());
+        if first >= last {
+            return;
+        }
+        let Some(rect) = tiny_skia::Rect::from_xywh(self.area.x() as f32, (first - top) as f32, self.area.width() as f32, (last - first) as f32) else {
+            return;
+        };
+        let (left, columns) = (self.area.x() as usize, self.area.width() as usize);
+
+        for (coverage, shading) in &self.passes {
+            for row in first..last {
+                let source = (row - self.area.y() as u32) as usize * columns;
+                let target = (row - top) as usize * width + left;
+                mask.data_mut()[target..target + columns].copy_from_slice(&coverage.data()[source..source + columns]);
+            }
+
+            let shaded;
+            let paint = match shading {
+                Shading::Paint(paint) => paint,
+                Shading::Pixels(pixels) => {
+                    shaded = Paint {
+                        shader: tiny_skia::Pattern::new(
+                            pixels.as_ref(),
+                            tiny_skia::SpreadMode::Pad,
+                            tiny_skia::FilterQuality::Nearest,
+                            1.0,
+                            tiny_skia::Transform::from_translate(self.area.x() as f32, self.area.y() as f32 - top as f32),
+                        ),
+                        blend_mode: self.blend_mode.to_skia(),
+                        ..Default::default()
+                    };
+                    &shaded
+                }
+            };
+            band.fill_rect(rect, paint, tiny_skia::Transform::identity(), Some(mask));
+
+            for row in first..last {
+                let target = (row - top) as usize * width + left;
+                mask.data_mut()[target..target + columns].fill(0);
+            }
+        }
+    }
+}
+
+impl BatchItem {
+    /// Style for drawing with `transform`
+    fn shape_style(&self, transform: tiny_skia::Transform) -> super::ShapeStyle<'_> {
+        super::ShapeStyle {
+            fill: self.fill.as_ref().or(self.style.fill.as_ref()),
+            fill_rule: self.fill_rule,
+            stroke: self.style.stroke.as_ref(),
+            stroke_style: &self.style.stroke_style,
+            shadow: self.style.shadow.as_ref(),
+            opacity: self.style.opacity,
+            blend_mode: self.style.blend_mode,
+            transform,
+        }
+    }
+
+    /// Rasterize fill and stroke over the part of the item's area inside a `width` by `height` layer
+    fn render(&self, width: u32, height: u32) -> Option<Raster<'_>> {
+        let area = self.area.intersect(&IntRect::from_xywh(0, 0, width, height)?)?;
+        let origin = (area.x() as u32, area.y() as u32);
+        let style = self.shape_style(self.transform.post_translate(-(area.x() as f32), -(area.y() as f32)));
+
+        let mut passes = Vec::new();
+        if let Some(color) = style.fill {
+            let coverage = coverage(area, |pixmap, paint| {
+                pixmap.fill_path(&self.path, paint, style.fill_rule.to_skia(), style.transform, None);
+            })?;
+            passes.extend(self.shading(color, &style, area, origin, 0.0).map(|shading| (coverage, shading)));
+        }
+        if let Some((color, stroke_width)) = style.stroke {
+            let stroke = style.stroke_style.to_skia(*stroke_width);
+            let coverage = coverage(area, |pixmap, paint| {
+                pixmap.stroke_path(&self.path, paint, &stroke, style.transform, None);
+            })?;
+            let reach = stroke.width / 2.0 * stroke.miter_limit.max(1.0);
+            passes.extend(self.shading(color, &style, area, origin, reach).map(|shading| (coverage, shading)));
+        }
+        Some(Raster { area, blend_mode: style.blend_mode, passes })
+    }
+
+    /// Paint for `color`, rendered over the area when it can't be used as a shader directly
+    fn shading<'a>(&'a self, color: &'a Color, style: &super::ShapeStyle, area: IntRect, origin: (u32, u32), reach: f32) -> Option<Shading<'a>> {
+        if color.is_solid() {
+            return super::paint_for(color, self.path.bounds(), style.opacity, style.blend_mode).map(Shading::Paint);
+        }
+        let mut pixels = Pixmap::new(area.width(), area.height())?;
+        let whole = tiny_skia::Rect::from_xywh(0.0, 0.0, area.width() as f32, area.height() as f32)?;
+        let style = super::ShapeStyle { blend_mode: crate::layer::BlendMode::Normal, ..*style };
+        super::with_paint(color, &style, &mut pixels.as_mut(), origin, self.path.bounds(), reach, |pixmap, paint| {
+            let mut paint = paint.clone();
+            paint.shader.transform(style.transform);
+            pixmap.fill_rect(whole, &paint, tiny_skia::Transform::identity(), None);
+        });
+        Some(Shading::Pixels(pixels))
+    }
+}
+
+/// Coverage of whatever `draw` paints with an opaque paint, over `area`
+fn coverage(area: IntRect, draw: impl FnOnce(&mut Pixmap, &Paint)) -> Option<Mask> {
+    let mut pixmap = Pixmap::new(area.width(), area.height())?;
+    let mut paint = Paint {
+        anti_alias: true,
+        ..Default::default()
+    };
+    paint.set_color_rgba8(0, 0, 0, 255);
+    draw(&mut pixmap, &paint);
+    Some(Mask::from_pixmap(pixmap.as_ref(), tiny_skia::MaskType::Alpha))
+}
+
+/// Replace curves with lines within `tolerance` pixels, keeping contours closed
+fn flatten(path: &Path2D, tolerance: f32) -> Result<Path> {
+    let mut lines = Path2D::new().fill_rule(path.fill_rule);
+    kurbo::flatten(path.to_bez_path()?, tolerance.max(0.001) as f64, |element| {
+        let taken = std::mem::take(&mut lines);
+        lines = match element {
+            kurbo::PathEl::MoveTo(p) => taken.move_to(p.x as f32, p.y as f32),
+            kurbo::PathEl::LineTo(p) => taken.line_to(p.x as f32, p.y as f32),
+            kurbo::PathEl::ClosePath => taken.close_path(),
+            _ => taken,
+        };
+    });
+    lines.to_skia()
+}
+
+/// Largest factor a transform scales lengths by
+fn stretch(transform: &tiny_skia::Transform) -> f32 {
+    transform.sx.hypot(transform.ky).max(transform.kx.hypot(transform.sy))
+}
+
+/// Layer pixels a transformed path can touch, padded for stroke joins and anti-aliasing
+fn device_area(bounds: tiny_skia::Rect, transform: tiny_skia::Transform, style: &Style) -> Option<IntRect> {
+    let stroke = style.stroke.as_ref().map_or(0.0, |(_, width)| {
+        width / 2.0 * style.stroke_style.miter_limit.max(1.0) * stretch(&transform)
+    });
+    let pad = stroke + 1.0;
+    bounds.transform(transform)?.outset(pad, pad)?.round_out()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::LayerId;
+    use crate::shapes::Circle;
+
+    #[test]
+    fn test_bands_match_single_pass() {
+        let circle = Circle::new(0.0, 0.0, 6.0);
+        let style = Style::new().fill(Color::rgba(0, 0, 255, 160)).stroke(Color::rgba(0, 0, 0, 255), 1.5);
+        let mut layer = Layer::new(LayerId::new(), "batch", 100, 100);
+        let batch = layer.draw_batch().instances(&circle, &style, (0..300).map(|i| {
+            Instance::at((i * 37 % 97) as f32 + 1.3, (i * 53 % 89) as f32 + 2.6).rotate(i as f32)
+        }));
+
+        let mut single = Pixmap::new(100, 100).unwrap();
+        rasterize(&batch.items, &mut single, 100);
+        let mut banded = Pixmap::new(100, 100).unwrap();
+        rasterize(&batch.items, &mut banded, 7);
+        assert!(single.data() == banded.data());
+        assert!(single.data().iter().any(|&v| v > 0));
+    }
+
+    #[test]
+    fn test_instance_colors_and_order() {
+        let square = crate::geometry::Rect::new(-5.0, -5.0, 10.0, 10.0);
+        let style = Style::new().fill(Color::rgba(255, 0, 0, 255));
+        let mut layer = Layer::new(LayerId::new(), "batch", 40, 40);
+        layer.draw_batch()
+            .instances(&square, &style, [Instance::at(10.0, 10.0), Instance::at(14.0, 10.0).fill(Color::rgba(0, 255, 0, 255))])
+            .draw()
+            .unwrap();
+
+        let image = layer.get_pixmap_mut().unwrap();
+        let pixel = |x: u32, y: u32| image.pixel(x, y).unwrap();
+        assert_eq!((pixel(7, 10).red(), pixel(7, 10).green()), (255, 0));
+        assert_eq!((pixel(12, 10).red(), pixel(12, 10).green()), (0, 255));
+    }
+
+    #[test]
+    fn test_dither_lines_up_with_the_layer() {
+        use crate::shapes::Drawable;
+        let gradient = Color::linear_gradient()
+            .start(0.0, 0.0)
+            .end(0.0, 64.0)
+            .add_stop(0.0, Color::rgba(20, 20, 20, 255))
+            .add_stop(1.0, Color::rgba(30, 30, 30, 255))
+            .dither(crate::color::Dither::Ordered)
+            .build();
+        let style = Style::new().fill(gradient);
+        // Offset so the shape is rendered apart from the layer origin
+        let square = crate::geometry::Rect::new(3.0, 5.0, 50.0, 50.0);
+
+        let mut direct = Layer::new(LayerId::new(), "direct", 64, 64);
+        square.draw_on(&mut direct, &style).unwrap();
+        let mut batched = Layer::new(LayerId::new(), "batch", 64, 64);
+        let batch = batched.draw_batch().add(&square, &style);
+        let mut banded = Pixmap::new(64, 64).unwrap();
+        rasterize(&batch.items, &mut banded, 5);
+        batch.draw().unwrap();
+
+        assert_eq!(batched.content, direct.content);
+        assert_eq!(crate::layer::pixmap_to_image(&banded), direct.content);
+    }
+}
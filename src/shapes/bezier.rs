@@ -1,8 +1,10 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::geometry::Point;
 use crate::error::Result;
-use tiny_skia::{Paint, PathBuilder, Transform};
+use crate::transform::TransformOps;
+use crate::shapes::{FillRule, Shadow, StrokeStyle};
+use tiny_skia::PathBuilder;
 
 /// Bezier curve builder (Quadratic & Cubic)
 pub struct BezierBuilder<'a> {
@@ -11,7 +13,13 @@ pub struct BezierBuilder<'a> {
     control1: Point,
     control2: Option<Point>, // None for quadratic, Some for cubic
     end: Point,
+    fill: Option<Color>,
     stroke: Option<(Color, f32)>,
+    stroke_style: StrokeStyle,
+    shadow: Option<Shadow>,
+    transform: TransformOps,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> BezierBuilder<'a> {
@@ -23,7 +31,13 @@ impl<'a> BezierBuilder<'a> {
             control1: control,
             control2: None,
             end,
+            fill: None,
             stroke: None,
+            stroke_style: StrokeStyle::default(),
+            shadow: None,
+            transform: TransformOps::default(),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
@@ -35,15 +49,77 @@ impl<'a> BezierBuilder<'a> {
             control1,
             control2: Some(control2),
             end,
+            fill: None,
             stroke: None,
+            stroke_style: StrokeStyle::default(),
+            shadow: None,
+            transform: TransformOps::default(),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
+    pub fn from(mut self, x: f32, y: f32) -> Self {
+        self.start = Point::new(x, y);
+        self
+    }
+    
+    pub fn to(mut self, x: f32, y: f32) -> Self {
+        self.end = Point::new(x, y);
+        self
+    }
+    
+    /// Make this a quadratic curve with one control point
+    pub fn control(mut self, x: f32, y: f32) -> Self {
+        self.control1 = Point::new(x, y);
+        self.control2 = None;
+        self
+    }
+    
+    /// Make this a cubic curve with two control points
+    pub fn controls(mut self, x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        self.control1 = Point::new(x1, y1);
+        self.control2 = Some(Point::new(x2, y2));
+        self
+    }
+    
+    pub fn fill(mut self, color: Color) -> Self {
+        self.fill = Some(color);
+        self
+    }
+    
     pub fn stroke(mut self, color: Color, width: f32) -> Self {
         self.stroke = Some((color, width));
         self
     }
     
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+    
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
         // Get pixmap from layer
         let mut pixmap = self.layer.get_pixmap_mut()?;
@@ -74,18 +150,16 @@ impl<'a> BezierBuilder<'a> {
             crate::error::CloveError::InvalidState("Failed to create bezier path".to_string())
         })?;
         
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
-        }
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: self.fill.as_ref(),
+            fill_rule: FillRule::NonZero,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
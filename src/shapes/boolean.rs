@@ -0,0 +1,310 @@
+use super::path::Path2D;
+use super::FillRule;
+use crate::error::Result;
+use std::collections::{HashMap, HashSet};
+
+/// Boolean operation combining two paths
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BooleanOp {
+    /// Area covered by either path
+    Union,
+    /// Area covered by both paths
+    Intersection,
+    /// Area of the first path not covered by the second
+    Difference,
+    /// Area covered by exactly one path
+    Xor,
+}
+
+impl BooleanOp {
+    fn keeps(self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+            BooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+/// Curve flattening tolerance in pixels
+const TOLERANCE: f64 = 0.05;
+
+/// Parameter distance treated as an edge endpoint
+const EPSILON: f64 = 1e-9;
+
+type Vec2 = (f64, f64);
+type Edge = (Vec2, Vec2);
+
+/// Flattened path with its fill rule
+struct Region {
+    contours: Vec<Vec<Vec2>>,
+    fill_rule: FillRule,
+}
+
+impl Region {
+    fn of(path: &Path2D) -> Result<Self> {
+        let mut contours = Vec::new();
+        let mut current: Vec<Vec2> = Vec::new();
+        let mut finish = |current: &mut Vec<Vec2>, next: Option<Vec2>| {
+            let contour = std::mem::take(current);
+            let restart = next.or_else(|| contour.first().copied());
+            if contour.len() >= 3 {
+                contours.push(contour);
+            }
+            current.extend(restart);
+        };
+
+        kurbo::flatten(path.to_bez_path()?, TOLERANCE, |element| match element {
+            kurbo::PathEl::MoveTo(p) => finish(&mut current, Some((p.x, p.y))),
+            kurbo::PathEl::LineTo(p) => current.push((p.x, p.y)),
+            kurbo::PathEl::ClosePath => finish(&mut current, None),
+            _ => {}
+        });
+        finish(&mut current, None);
+
+        Ok(Self { contours, fill_rule: path.fill_rule })
+    }
+
+    /// Edges of all contours, each implicitly closed
+    fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.contours.iter().flat_map(|contour| {
+            (0..contour.len()).map(move |i| (contour[i], contour[(i + 1) % contour.len()]))
+        }).filter(|(p, q)| p != q)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        let mut winding = 0;
+        for (a, b) in self.edges() {
+            let side = cross((b.0 - a.0, b.1 - a.1), (point.0 - a.0, point.1 - a.1));
+            if a.1 <= point.1 {
+                if b.1 > point.1 && side > 0.0 {
+                    winding += 1;
+                }
+            } else if b.1 <= point.1 && side < 0.0 {
+                winding -= 1;
+            }
+        }
+        match self.fill_rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// Combine two paths into a new path of straight lines
+///
+/// All edges are split where they cross, then an edge piece is kept when the
+/// result is inside on exactly one side of it, oriented with that side on the left.
+pub(crate) fn combine(a: &Path2D, b: &Path2D, op: BooleanOp) -> Result<Path2D> {
+    let (region_a, region_b) = (Region::of(a)?, Region::of(b)?);
+    let edges: Vec<Edge> = region_a.edges().chain(region_b.edges()).collect();
+    let inside = |p: Vec2| op.keeps(region_a.contains(p), region_b.contains(p));
+
+    let mut kept = Vec::new();
+    let mut seen = HashSet::new();
+    for (p, q) in split_at_intersections(&edges) {
+        let (dx, dy) = (q.0 - p.0, q.1 - p.1);
+        let length = dx.hypot(dy);
+        let offset = (length * 0.1).min(1e-3) / length;
+        let middle = ((p.0 + q.0) / 2.0, (p.1 + q.1) / 2.0);
+        let left = inside((middle.0 - dy * offset, middle.1 + dx * offset));
+        let right = inside((middle.0 + dy * offset, middle.1 - dx * offset));
+        if left == right {
+            continue;
+        }
+        let edge = if left { (p, q) } else { (q, p) };
+        // Coincident edges of both paths only count once
+        if seen.insert((key(edge.0), key(edge.1))) {
+            kept.push(edge);
+        }
+    }
+
+    Ok(chain(&kept))
+}
+
+fn cross(a: Vec2, b: Vec2) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn key(p: Vec2) -> (i64, i64) {
+    ((p.0 * 1e6).round() as i64, (p.1 * 1e6).round() as i64)
+}
+
+/// Split every edge at the points where other edges cross or touch it
+fn split_at_intersections(edges: &[Edge]) -> Vec<Edge> {
+    let mut cuts: Vec<Vec<(f64, Vec2)>> = vec![Vec::new(); edges.len()];
+    for i in 0..edges.len() {
+        for j in (i + 1)..edges.len() {
+            for (t, u, point) in intersections(edges[i], edges[j]) {
+                if t > EPSILON && t < 1.0 - EPSILON {
+                    cuts[i].push((t, point));
+                }
+                if u > EPSILON && u < 1.0 - EPSILON {
+                    cuts[j].push((u, point));
+                }
+            }
+        }
+    }
+
+    let mut pieces = Vec::new();
+    for (&(p, q), mut edge_cuts) in edges.iter().zip(cuts) {
+        edge_cuts.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut start = p;
+        for end in edge_cuts.into_iter().map(|(_, point)| point).chain(std::iter::once(q)) {
+            if key(start) != key(end) {
+                pieces.push((start, end));
+                start = end;
+            }
+        }
+    }
+    pieces
+}
+
+/// Intersections of two edges as (parameter on a, parameter on b, point)
+///
+/// Points at an edge endpoint reuse that endpoint exactly so pieces chain up.
+fn intersections(a: Edge, b: Edge) -> Vec<(f64, f64, Vec2)> {
+    let (min_a, max_a) = ((a.0 .0.min(a.1 .0), a.0 .1.min(a.1 .1)), (a.0 .0.max(a.1 .0), a.0 .1.max(a.1 .1)));
+    let (min_b, max_b) = ((b.0 .0.min(b.1 .0), b.0 .1.min(b.1 .1)), (b.0 .0.max(b.1 .0), b.0 .1.max(b.1 .1)));
+    if max_a.0 < min_b.0 || max_b.0 < min_a.0 || max_a.1 < min_b.1 || max_b.1 < min_a.1 {
+        return Vec::new();
+    }
+
+    let r = (a.1 .0 - a.0 .0, a.1 .1 - a.0 .1);
+    let s = (b.1 .0 - b.0 .0, b.1 .1 - b.0 .1);
+    let offset = (b.0 .0 - a.0 .0, b.0 .1 - a.0 .1);
+    let denominator = cross(r, s);
+    let (length_r, length_s) = (r.0.hypot(r.1), s.0.hypot(s.1));
+
+    if denominator.abs() <= 1e-12 * length_r * length_s {
+        // Parallel: only collinear overlaps matter, split at the other edge's endpoints
+        if cross(offset, r).abs() > 1e-9 * length_r {
+            return Vec::new();
+        }
+        let project = |point: Vec2, origin: Vec2, direction: Vec2, length: f64| {
+            ((point.0 - origin.0) * direction.0 + (point.1 - origin.1) * direction.1) / (length * length)
+        };
+        let mut found = Vec::new();
+        for (u, point) in [(0.0, b.0), (1.0, b.1)] {
+            found.push((project(point, a.0, r, length_r), u, point));
+        }
+        for (t, point) in [(0.0, a.0), (1.0, a.1)] {
+            found.push((t, project(point, b.0, s, length_s), point));
+        }
+        return found;
+    }
+
+    let t = cross(offset, s) / denominator;
+    let u = cross(offset, r) / denominator;
+    let range = -EPSILON..=1.0 + EPSILON;
+    if !range.contains(&t) || !range.contains(&u) {
+        return Vec::new();
+    }
+    let point = if t <= EPSILON {
+        a.0
+    } else if t >= 1.0 - EPSILON {
+        a.1
+    } else if u <= EPSILON {
+        b.0
+    } else if u >= 1.0 - EPSILON {
+        b.1
+    } else {
+        (a.0 .0 + t * r.0, a.0 .1 + t * r.1)
+    };
+    vec![(t, u, point)]
+}
+
+/// Link directed edges into closed contours
+fn chain(edges: &[Edge]) -> Path2D {
+    let mut outgoing: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (i, edge) in edges.iter().enumerate() {
+        outgoing.entry(key(edge.0)).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut path = Path2D::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let start = key(edges[first].0);
+        let mut points = vec![edges[first].0];
+        let mut end = edges[first].1;
+        while key(end) != start {
+            points.push(end);
+            let next = outgoing.get(&key(end))
+                .and_then(|candidates| candidates.iter().copied().find(|&i| !used[i]));
+            let Some(next) = next else {
+                break;
+            };
+            used[next] = true;
+            end = edges[next].1;
+        }
+
+        if points.len() >= 3 {
+            path = path.move_to(points[0].0 as f32, points[0].1 as f32);
+            for point in &points[1..] {
+                path = path.line_to(point.0 as f32, point.1 as f32);
+            }
+            path = path.close_path();
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn squares() -> (Path2D, Path2D) {
+        (Path2D::rect(0.0, 0.0, 10.0, 10.0), Path2D::rect(5.0, 5.0, 10.0, 10.0))
+    }
+
+    fn contains(path: &Path2D, x: f64, y: f64) -> bool {
+        Region::of(path).unwrap().contains((x, y))
+    }
+
+    #[test]
+    fn test_square_operations() {
+        let (a, b) = squares();
+
+        let union = a.union(&b).unwrap();
+        // One outline of eight corners
+        assert_eq!(union.to_svg(), "M0 0 L10 0 L10 5 L15 5 L15 15 L5 15 L5 10 L0 10 Z");
+        assert!(contains(&union, 2.0, 2.0) && contains(&union, 12.0, 12.0));
+        assert!(!contains(&union, 12.0, 2.0));
+
+        let intersection = a.intersection(&b).unwrap();
+        assert!(contains(&intersection, 7.0, 7.0));
+        assert!(!contains(&intersection, 2.0, 2.0));
+
+        let difference = a.difference(&b).unwrap();
+        assert!(contains(&difference, 2.0, 2.0));
+        assert!(!contains(&difference, 7.0, 7.0) && !contains(&difference, 12.0, 12.0));
+
+        let xor = a.xor(&b).unwrap();
+        assert!(contains(&xor, 2.0, 2.0) && contains(&xor, 12.0, 12.0));
+        assert!(!contains(&xor, 7.0, 7.0));
+    }
+
+    #[test]
+    fn test_shared_edge_union_merges() {
+        let union = Path2D::rect(0.0, 0.0, 10.0, 10.0)
+            .union(&Path2D::rect(10.0, 0.0, 10.0, 10.0))
+            .unwrap();
+        assert!(contains(&union, 10.0, 5.0));
+        assert!(contains(&union, 5.0, 5.0) && contains(&union, 15.0, 5.0));
+    }
+
+    #[test]
+    fn test_circle_cut_out_makes_ring() {
+        let ring = Path2D::circle(20.0, 20.0, 15.0)
+            .difference(&Path2D::circle(20.0, 20.0, 8.0))
+            .unwrap();
+        assert!(contains(&ring, 20.0, 8.0));
+        assert!(!contains(&ring, 20.0, 20.0));
+        assert!(!contains(&ring, 20.0, 2.0));
+    }
+}
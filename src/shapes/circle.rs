@@ -1,8 +1,9 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::error::Result;
-use crate::shapes::Shadow;
-use tiny_skia::{Paint, PathBuilder, Transform};
+use crate::transform::TransformOps;
+use crate::shapes::{FillRule, Path2D, Shadow, StrokeStyle};
+use tiny_skia::{Path, PathBuilder};
 
 /// Circle builder
 pub struct CircleBuilder<'a> {
@@ -12,8 +13,11 @@ pub struct CircleBuilder<'a> {
     radius: f32,
     fill: Option<Color>,
     stroke: Option<(Color, f32)>,
+    stroke_style: StrokeStyle,
     shadow: Option<Shadow>,
+    transform: TransformOps,
     opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> CircleBuilder<'a> {
@@ -25,8 +29,11 @@ impl<'a> CircleBuilder<'a> {
             radius,
             fill: None,
             stroke: None,
+            stroke_style: StrokeStyle::default(),
             shadow: None,
+            transform: TransformOps::default(),
             opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
@@ -51,78 +58,65 @@ impl<'a> CircleBuilder<'a> {
         self
     }
     
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
     pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
         self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
         self
     }
     
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
     pub fn opacity(mut self, opacity: f32) -> Self {
         self.opacity = opacity.clamp(0.0, 1.0);
         self
     }
     
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
+    super::hit_test_methods!();
+    
+    /// Outline as a reusable path, with transforms applied
+    pub fn to_path(&self) -> Option<Path2D> {
+        let path = self.build_path()?;
+        let path = path.clone().transform(self.transform.resolve_for_path(&path))?;
+        Some(Path2D::from_skia(&path))
+    }
+    
+    /// Build the outline path
+    fn build_path(&self) -> Option<Path> {
+        PathBuilder::from_circle(self.center_x, self.center_y, self.radius)
+    }
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
         // Get pixmap from layer
         let mut pixmap = self.layer.get_pixmap_mut()?;
         
-        // Create path for circle
-        let path = PathBuilder::from_circle(self.center_x, self.center_y, self.radius)
-            .ok_or_else(|| {
-                crate::error::CloveError::InvalidState("Failed to create circle path".to_string())
-            })?;
+        let path = self.build_path().ok_or_else(|| {
+            crate::error::CloveError::InvalidState("Failed to create circle path".to_string())
+        })?;
         
-        // Draw fill if specified
-        let fill = self.fill.clone();
-        
-        if let Some(fill_color) = fill {
-            let mut paint = Paint::default();
-            paint.anti_alias = true;
-            
-            match fill_color {
-                Color::RadialGradient(grad) => {
-                    // Use tiny-skia shader for radial gradient
-                    if let Some(shader) = grad.to_skia_radial_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        // Apply opacity by modifying the shader colors or using a separate paint
-                        // For now, we'll apply opacity to the final result
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                Color::LinearGradient(grad) => {
-                    // Use tiny-skia shader for linear gradient
-                    if let Some(shader) = grad.to_skia_linear_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        // Apply opacity by modifying the shader colors or using a separate paint
-                        // For now, we'll apply opacity to the final result
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                _ => {
-                    // Solid color
-                    let rgba = fill_color.to_rgba();
-                    // Apply opacity to alpha channel
-                    let alpha = (self.opacity * (rgba.a as f32 / 255.0) * 255.0) as u8;
-                    paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, alpha);
-                    
-                    pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                }
-            }
-        }
-        
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            // Apply opacity to alpha channel
-            let alpha = (self.opacity * (rgba.a as f32 / 255.0) * 255.0) as u8;
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, alpha);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
-        }
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: self.fill.as_ref(),
+            fill_rule: FillRule::NonZero,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
@@ -1,8 +1,9 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::error::Result;
-use crate::shapes::Shadow;
-use tiny_skia::{Paint, PathBuilder, Transform};
+use crate::transform::TransformOps;
+use crate::shapes::{FillRule, Path2D, Shadow, StrokeStyle};
+use tiny_skia::{Path, PathBuilder};
 
 /// Ellipse builder
 pub struct EllipseBuilder<'a> {
@@ -13,8 +14,11 @@ pub struct EllipseBuilder<'a> {
     radius_y: f32,
     fill: Option<Color>,
     stroke: Option<(Color, f32)>,
+    stroke_style: StrokeStyle,
     shadow: Option<Shadow>,
+    transform: TransformOps,
     opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> EllipseBuilder<'a> {
@@ -27,11 +31,26 @@ impl<'a> EllipseBuilder<'a> {
             radius_y,
             fill: None,
             stroke: None,
+            stroke_style: StrokeStyle::default(),
             shadow: None,
+            transform: TransformOps::default(),
             opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center_x = x;
+        self.center_y = y;
+        self
+    }
+    
+    pub fn radius(mut self, radius_x: f32, radius_y: f32) -> Self {
+        self.radius_x = radius_x;
+        self.radius_y = radius_y;
+        self
+    }
+    
     pub fn fill(mut self, color: Color) -> Self {
         self.fill = Some(color);
         self
@@ -42,16 +61,50 @@ impl<'a> EllipseBuilder<'a> {
         self
     }
     
-    pub fn draw(self) -> Result<&'a mut Layer> {
-        // Get pixmap from layer
-        let mut pixmap = self.layer.get_pixmap_mut()?;
-        
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+    
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
+    super::hit_test_methods!();
+    
+    /// Outline as a reusable path, with transforms applied
+    pub fn to_path(&self) -> Option<Path2D> {
+        let path = self.build_path()?;
+        let path = path.clone().transform(self.transform.resolve_for_path(&path))?;
+        Some(Path2D::from_skia(&path))
+    }
+    
+    /// Build the outline path
+    fn build_path(&self) -> Option<Path> {
         // Create ellipse path manually using bezier curves
         let mut path_builder = PathBuilder::new();
         
         // Approximate ellipse using cubic bezier curves
         // Using the method from: https://spencermortensen.com/articles/bezier-circle/
-        let magic = 0.551915024494; // 4/3 * (sqrt(2) - 1)
+        let magic = 0.551_915_f32; // 4/3 * (sqrt(2) - 1)
         let cpx = self.radius_x * magic;
         let cpy = self.radius_y * magic;
         
@@ -88,50 +141,27 @@ impl<'a> EllipseBuilder<'a> {
         
         path_builder.close();
         
-        let path = path_builder.finish().ok_or_else(|| {
+        path_builder.finish()
+    }
+    
+    pub fn draw(self) -> Result<&'a mut Layer> {
+        // Get pixmap from layer
+        let mut pixmap = self.layer.get_pixmap_mut()?;
+        
+        let path = self.build_path().ok_or_else(|| {
             crate::error::CloveError::InvalidState("Failed to create ellipse path".to_string())
         })?;
         
-        // Draw fill if specified
-        if let Some(fill_color) = self.fill.clone() {
-            let mut paint = Paint::default();
-            paint.anti_alias = true;
-            
-            match fill_color {
-                Color::LinearGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_linear_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                Color::RadialGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_radial_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                _ => {
-                    let rgba = fill_color.to_rgba();
-                    let alpha = (self.opacity * (rgba.a as f32 / 255.0) * 255.0) as u8;
-                    paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, alpha);
-                    pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                }
-            }
-        }
-        
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            let alpha = (self.opacity * (rgba.a as f32 / 255.0) * 255.0) as u8;
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, alpha);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
-        }
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: self.fill.as_ref(),
+            fill_rule: FillRule::NonZero,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
@@ -1,7 +1,9 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::error::Result;
-use tiny_skia::{Paint, PathBuilder, Transform};
+use crate::transform::TransformOps;
+use crate::shapes::{FillRule, Shadow, StrokeStyle};
+use tiny_skia::PathBuilder;
 
 /// Line builder
 pub struct LineBuilder<'a> {
@@ -11,6 +13,11 @@ pub struct LineBuilder<'a> {
     x2: f32,
     y2: f32,
     stroke: Option<(Color, f32)>,
+    stroke_style: StrokeStyle,
+    shadow: Option<Shadow>,
+    transform: TransformOps,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> LineBuilder<'a> {
@@ -22,6 +29,11 @@ impl<'a> LineBuilder<'a> {
             x2,
             y2,
             stroke: None,
+            stroke_style: StrokeStyle::default(),
+            shadow: None,
+            transform: TransformOps::default(),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
@@ -42,6 +54,33 @@ impl<'a> LineBuilder<'a> {
         self
     }
     
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+    
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
         // Get pixmap from layer
         let mut pixmap = self.layer.get_pixmap_mut()?;
@@ -55,18 +94,16 @@ impl<'a> LineBuilder<'a> {
             crate::error::CloveError::InvalidState("Failed to create line path".to_string())
         })?;
         
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
-        }
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: None,
+            fill_rule: FillRule::NonZero,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
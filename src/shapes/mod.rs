@@ -9,7 +9,13 @@ pub mod triangle;
 pub mod arc;
 pub mod bezier;
 pub mod path;
+pub mod boolean;
+mod path_geometry;
+pub mod shape;
+pub mod batch;
 pub mod star;
+pub mod stroke;
+pub mod shadow;
 
 pub use rectangle::RectBuilder;
 pub use rounded_rect::RoundedRectBuilder;
@@ -21,28 +27,438 @@ pub use polygon::PolygonBuilder;
 pub use triangle::TriangleBuilder;
 pub use arc::ArcBuilder;
 pub use bezier::BezierBuilder;
-pub use path::PathBuilder;
+pub use path::{Path2D, PathBuilder};
+pub use boolean::BooleanOp;
+pub use batch::{BatchBuilder, Instance};
+pub use shape::{Arc, Bezier, Circle, Drawable, Ellipse, Polygon, Shape, Star, Style};
 pub use star::StarBuilder;
+pub use stroke::{LineCap, LineJoin, StrokeStyle};
+pub use shadow::Shadow;
 
-use crate::color::Color;
+use crate::color::{Color, Dither};
+use crate::layer::BlendMode;
+use tiny_skia::{Paint, Path, Pixmap, PixmapMut, Transform};
 
-/// Shadow for shapes
-#[derive(Debug, Clone)]
-pub struct Shadow {
-    pub color: Color,
-    pub offset_x: f32,
-    pub offset_y: f32,
-    pub blur: f32,
+/// Rule deciding which areas of a self-intersecting or compound path are inside
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FillRule {
+    /// Inside unless paths wind around a point equally in both directions
+    #[default]
+    NonZero,
+    /// Inside when a ray crosses an odd number of edges, so nested contours cut holes
+    EvenOdd,
 }
 
-impl Shadow {
-    pub fn new(color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
-        Self {
-            color,
-            offset_x,
-            offset_y,
-            blur,
+impl FillRule {
+    pub(crate) fn to_skia(self) -> tiny_skia::FillRule {
+        match self {
+            FillRule::NonZero => tiny_skia::FillRule::Winding,
+            FillRule::EvenOdd => tiny_skia::FillRule::EvenOdd,
         }
     }
 }
 
+/// Build a paint for a fill color, applying opacity and blend mode
+///
+/// `bounds` is the shape's box in its own space, which bounding-box gradient units refer to.
+pub(crate) fn paint_for(color: &Color, bounds: tiny_skia::Rect, opacity: f32, blend_mode: BlendMode) -> Option<Paint<'_>> {
+    let mut paint = Paint {
+        anti_alias: true,
+        blend_mode: blend_mode.to_skia(),
+        ..Default::default()
+    };
+    
+    match color {
+        Color::LinearGradient(grad) => {
+            let mut shader = grad.to_skia_linear_gradient(grad.units.to_skia(bounds))?;
+            shader.apply_opacity(opacity);
+            paint.shader = shader;
+        }
+        Color::RadialGradient(grad) => {
+            let mut shader = grad.to_skia_radial_gradient(grad.units.to_skia(bounds))?;
+            shader.apply_opacity(opacity);
+            paint.shader = shader;
+        }
+        Color::Pattern(pattern) if pattern.tile_size().is_some() => {
+            paint.shader = pattern.to_skia_pattern(opacity)?;
+        }
+        _ => {
+            // Solid color, opacity applied to alpha channel
+            let rgba = color.to_rgba();
+            let alpha = (opacity * (rgba.a as f32 / 255.0) * 255.0) as u8;
+            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, alpha);
+        }
+    }
+    
+    Some(paint)
+}
+
+/// Paint settings a shape builder passes to `fill_and_stroke`
+pub(crate) struct ShapeStyle<'a> {
+    pub fill: Option<&'a Color>,
+    pub fill_rule: FillRule,
+    pub stroke: Option<&'a (Color, f32)>,
+    pub stroke_style: &'a StrokeStyle,
+    pub shadow: Option<&'a Shadow>,
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
+    /// Applied to geometry, stroke and paint alike
+    pub transform: Transform,
+}
+
+/// Draw a shape's shadow, fill and stroke onto a pixmap
+pub(crate) fn fill_and_stroke(pixmap: &mut Pixmap, path: &Path, style: &ShapeStyle) {
+    let stroke = style.stroke.map(|(_, width)| style.stroke_style.to_skia(*width));
+    
+    // Drop shadow goes beneath everything
+    if let Some(shadow) = style.shadow.filter(|s| !s.inset) {
+        if let Some(coverage) = shadow::path_coverage(path, style.transform, style.fill.map(|_| style.fill_rule), stroke.as_ref(), pixmap.width(), pixmap.height()) {
+            shadow.draw(pixmap, &coverage, style.opacity, style.blend_mode);
+        }
+    }
+    
+    fill_shape(&mut pixmap.as_mut(), path, style);
+    
+    // Inset shadow sits over the fill, inside the filled area (or the stroke if unfilled)
+    if let Some(shadow) = style.shadow.filter(|s| s.inset) {
+        let fill = style.fill.map(|_| style.fill_rule);
+        let coverage_stroke = if fill.is_some() { None } else { stroke.as_ref() };
+        if let Some(coverage) = shadow::path_coverage(path, style.transform, fill, coverage_stroke, pixmap.width(), pixmap.height()) {
+            shadow.draw(pixmap, &coverage, style.opacity, style.blend_mode);
+        }
+    }
+    
+    if let Some(stroke) = stroke {
+        stroke_shape(&mut pixmap.as_mut(), path, style, &stroke);
+    }
+}
+
+/// Fill a path if the style has a fill
+pub(crate) fn fill_shape(pixmap: &mut PixmapMut, path: &Path, style: &ShapeStyle) {
+    if let Some(color) = style.fill {
+        with_paint(color, style, pixmap, (0, 0), path.bounds(), 0.0, |pixmap, paint| {
+            pixmap.fill_path(path, paint, style.fill_rule.to_skia(), style.transform, None);
+        });
+    }
+}
+
+/// Stroke a path with the style's stroke color, with the same paint handling as fills
+pub(crate) fn stroke_shape(pixmap: &mut PixmapMut, path: &Path, style: &ShapeStyle, stroke: &tiny_skia::Stroke) {
+    if let Some((stroke_color, _)) = style.stroke {
+        let reach = stroke.width / 2.0 * stroke.miter_limit.max(1.0);
+        with_paint(stroke_color, style, pixmap, (0, 0), path.bounds(), reach, |pixmap, paint| {
+            pixmap.stroke_path(path, paint, stroke, style.transform, None);
+        });
+    }
+}
+
+/// Build the paint for a color and hand it to `draw`
+///
+/// Conic, mesh, freeform and dithered gradients have no tiny-skia shader, so they're rendered for the device
+/// pixels the shape can cover (its `bounds` grown by `reach`) and painted as an untransformed pattern.
+/// `origin` is the device position of the pixmap's top left, which dither patterns line up with.
+/// `bounds` also anchors gradients given in bounding-box units.
+pub(crate) fn with_paint(color: &Color, style: &ShapeStyle, pixmap: &mut PixmapMut, origin: (u32, u32), bounds: tiny_skia::Rect, reach: f32, draw: impl FnOnce(&mut PixmapMut, &Paint)) {
+    let needs_rendering = match color {
+        Color::ConicGradient(_) | Color::MeshGradient(_) | Color::FreeformGradient(_) => true,
+        Color::LinearGradient(gradient) => gradient.dither != Dither::None,
+        Color::RadialGradient(gradient) => gradient.dither != Dither::None,
+        _ => false,
+    };
+    if !needs_rendering {
+        if let Some(paint) = paint_for(color, bounds, style.opacity, style.blend_mode) {
+            draw(pixmap, &paint);
+        }
+        return;
+    }
+
+    let area = bounds.outset(reach, reach)
+        .and_then(|bounds| bounds.transform(style.transform))
+        .and_then(|bounds| bounds.round_out())
+        .and_then(|bounds| bounds.intersect(&tiny_skia::IntRect::from_xywh(0, 0, pixmap.width(), pixmap.height())?));
+    let (Some(area), Some(inverse)) = (area, style.transform.invert()) else {
+        return;
+    };
+    let rendered = match color {
+        Color::ConicGradient(gradient) => gradient.render(area, origin, bounds, style.transform),
+        Color::LinearGradient(gradient) => gradient.render(area, origin, bounds, style.transform),
+        Color::RadialGradient(gradient) => gradient.render(area, origin, bounds, style.transform),
+        Color::MeshGradient(gradient) => gradient.render(area, style.transform),
+        Color::FreeformGradient(gradient) => gradient.render(area, style.transform),
+        _ => None,
+    };
+    let Some(rendered) = rendered else {
+        return;
+    };
+    let paint = Paint {
+        anti_alias: true,
+        blend_mode: style.blend_mode.to_skia(),
+        shader: tiny_skia::Pattern::new(
+            rendered.as_ref(),
+            tiny_skia::SpreadMode::Pad,
+            tiny_skia::FilterQuality::Nearest,
+            style.opacity,
+            // Undo the shape transform so rendered pixels land where they were computed
+            inverse.pre_translate(area.x() as f32, area.y() as f32),
+        ),
+        ..Default::default()
+    };
+    draw(pixmap, &paint);
+}
+
+/// Builder hit testing against the outline from `build_path`, using the `transform`,
+/// `fill`, `stroke` and `stroke_style` fields
+///
+/// Pass the path to the builder's fill rule field, or nothing for non-zero.
+macro_rules! hit_test_methods {
+    () => {
+        /// Check if a point lies inside the fill, or within `tolerance` of its edge
+        pub fn hit_fill(&self, x: f32, y: f32, tolerance: f32) -> bool {
+            self.build_path().into_iter().any(|path| {
+                $crate::shapes::hit_fill(&path, self.transform.resolve_for_path(&path), $crate::shapes::FillRule::NonZero, x, y, tolerance)
+            })
+        }
+
+        $crate::shapes::hit_test_methods!(@stroke);
+    };
+    ($($fill_rule:ident).+) => {
+        /// Check if a point lies inside the fill, or within `tolerance` of its edge
+        pub fn hit_fill(&self, x: f32, y: f32, tolerance: f32) -> bool {
+            self.build_path().into_iter().any(|path| {
+                $crate::shapes::hit_fill(&path, self.transform.resolve_for_path(&path), self.$($fill_rule).+, x, y, tolerance)
+            })
+        }
+
+        $crate::shapes::hit_test_methods!(@stroke);
+    };
+    (@stroke) => {
+        /// Check if a point lies on the stroke, or within `tolerance` of it
+        pub fn hit_stroke(&self, x: f32, y: f32, tolerance: f32) -> bool {
+            let width = self.stroke.as_ref().map_or(0.0, |(_, width)| *width);
+            self.build_path().into_iter().any(|path| {
+                $crate::shapes::hit_stroke(&path, self.transform.resolve_for_path(&path), width, &self.stroke_style, x, y, tolerance)
+            })
+        }
+
+        /// Check if a point hits the painted fill or stroke
+        pub fn hit_test(&self, x: f32, y: f32, tolerance: f32) -> bool {
+            (self.fill.is_some() && self.hit_fill(x, y, tolerance))
+                || (self.stroke.is_some() && self.hit_stroke(x, y, tolerance))
+        }
+    };
+}
+
+pub(crate) use hit_test_methods;
+
+/// Check if a point is inside a path's fill, or within `tolerance` of its outline
+pub(crate) fn hit_fill(path: &Path, transform: Transform, fill_rule: FillRule, x: f32, y: f32, tolerance: f32) -> bool {
+    let Some(path) = path.clone().transform(transform) else {
+        return false;
+    };
+    point_in_path(&path, fill_rule, x, y)
+        || (tolerance > 0.0 && hit_outline(&path, x, y, tolerance))
+}
+
+/// Check if a point is on a stroke of `width` around a path, or within `tolerance` of it
+pub(crate) fn hit_stroke(path: &Path, transform: Transform, width: f32, style: &StrokeStyle, x: f32, y: f32, tolerance: f32) -> bool {
+    // Stroke in local space so the width scales with the transform
+    let outline = (width > 0.0)
+        .then(|| path.stroke(&style.to_skia(width), 1.0))
+        .flatten()
+        .and_then(|outline| outline.transform(transform));
+    
+    match outline {
+        Some(outline) => {
+            point_in_path(&outline, FillRule::NonZero, x, y)
+                || (tolerance > 0.0 && hit_outline(&outline, x, y, tolerance))
+        }
+        None => tolerance > 0.0 && path.clone().transform(transform)
+            .is_some_and(|path| hit_outline(&path, x, y, tolerance)),
+    }
+}
+
+/// Check if a point is within `tolerance` of a path's outline
+fn hit_outline(path: &Path, x: f32, y: f32, tolerance: f32) -> bool {
+    let stroke = StrokeStyle::default().to_skia(2.0 * tolerance);
+    path.stroke(&stroke, 1.0)
+        .is_some_and(|outline| point_in_path(&outline, FillRule::NonZero, x, y))
+}
+
+/// Sample a path at a single point by rasterizing it into a 1x1 mask
+fn point_in_path(path: &Path, fill_rule: FillRule, x: f32, y: f32) -> bool {
+    let Some(mut mask) = tiny_skia::Mask::new(1, 1) else {
+        return false;
+    };
+    // Non anti-aliased fills sample pixel centers, so move the point onto (0.5, 0.5)
+    mask.fill_path(path, fill_rule.to_skia(), false, Transform::from_translate(0.5 - x, 0.5 - y));
+    mask.data()[0] > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::layer::{BlendMode, Layer, LayerId};
+    
+    #[test]
+    fn test_destination_out_erases() {
+        let mut layer = Layer::new(LayerId::new(), "test", 20, 20);
+        layer.draw_rect()
+            .position(0.0, 0.0)
+            .size(20.0, 20.0)
+            .fill(Color::rgba(255, 0, 0, 255))
+            .draw().unwrap()
+            .draw_circle()
+            .center(10.0, 10.0)
+            .radius(5.0)
+            .fill(Color::rgba(0, 0, 0, 255))
+            .blend_mode(BlendMode::DestinationOut)
+            .draw().unwrap();
+        
+        assert_eq!(layer.content.get_pixel(10, 10)[3], 0);
+        assert_eq!(layer.content.get_pixel(1, 1).0, [255, 0, 0, 255]);
+    }
+    
+    #[test]
+    fn test_gradient_fill_applies_opacity() {
+        let linear = Color::linear_gradient()
+            .start(0.0, 0.0)
+            .end(20.0, 0.0)
+            .add_stop(0.0, Color::rgba(255, 0, 0, 255))
+            .add_stop(1.0, Color::rgba(255, 0, 0, 255))
+            .build();
+        let radial = Color::radial_gradient()
+            .center(10.0, 10.0)
+            .radius(10.0)
+            .add_stop(0.0, Color::rgba(0, 0, 255, 255))
+            .add_stop(1.0, Color::rgba(0, 0, 255, 255))
+            .build();
+
+        for gradient in [linear, radial] {
+            let mut layer = Layer::new(LayerId::new(), "test", 20, 20);
+            layer.draw_rect()
+                .position(0.0, 0.0)
+                .size(20.0, 20.0)
+                .fill(gradient)
+                .opacity(0.5)
+                .draw()
+                .unwrap();
+            // Gradients fade like solid fills instead of painting at full strength
+            assert!(layer.alpha_at(10.0, 10.0).abs_diff(128) <= 1);
+        }
+    }
+
+    #[test]
+    fn test_hit_fill_and_stroke() {
+        let mut layer = Layer::new(LayerId::new(), "test", 10, 10);
+        let circle = layer.draw_circle()
+            .center(50.0, 50.0)
+            .radius(20.0)
+            .stroke(Color::rgba(0, 0, 0, 255), 4.0);
+        
+        assert!(circle.hit_fill(50.0, 50.0, 0.0));
+        assert!(!circle.hit_fill(50.0, 25.0, 0.0));
+        assert!(circle.hit_fill(50.0, 25.0, 6.0));
+        assert!(circle.hit_stroke(50.0, 31.0, 0.0));
+        assert!(!circle.hit_stroke(50.0, 50.0, 2.0));
+        
+        // Unfilled shapes only hit on their stroke
+        assert!(!circle.hit_test(50.0, 50.0, 0.0));
+        assert!(circle.hit_test(71.0, 50.0, 0.0));
+    }
+    
+    #[test]
+    fn test_dashed_stroke_leaves_gaps() {
+        use super::{LineBuilder, LineCap, StrokeStyle};
+        
+        let mut layer = Layer::new(LayerId::new(), "test", 40, 10);
+        LineBuilder::new(&mut layer, 0.0, 5.0, 40.0, 5.0)
+            .stroke(Color::rgba(0, 0, 0, 255), 2.0)
+            .stroke_style(StrokeStyle::new().dash(vec![10.0, 10.0], 0.0).cap(LineCap::Butt))
+            .draw().unwrap();
+        
+        assert_eq!(layer.content.get_pixel(5, 5)[3], 255);
+        assert_eq!(layer.content.get_pixel(15, 5)[3], 0);
+        assert_eq!(layer.content.get_pixel(25, 5)[3], 255);
+    }
+    
+    #[test]
+    fn test_shadow_drawn_beneath_shape() {
+        let mut layer = Layer::new(LayerId::new(), "test", 30, 30);
+        layer.draw_rect()
+            .position(5.0, 5.0)
+            .size(10.0, 10.0)
+            .fill(Color::rgba(255, 0, 0, 255))
+            .shadow(Color::rgba(0, 0, 255, 255), 5.0, 5.0, 0.0)
+            .draw().unwrap();
+        
+        assert_eq!(layer.content.get_pixel(12, 12).0, [255, 0, 0, 255]);
+        assert_eq!(layer.content.get_pixel(17, 17).0, [0, 0, 255, 255]);
+    }
+    
+    #[test]
+    fn test_rotation_moves_geometry_and_hit_area() {
+        let mut layer = Layer::new(LayerId::new(), "test", 40, 40);
+        let rect = layer.draw_rect()
+            .position(10.0, 18.0)
+            .size(20.0, 4.0)
+            .fill(Color::rgba(0, 0, 0, 255))
+            .rotate(90.0);
+        
+        assert!(rect.hit_fill(20.0, 12.0, 0.0));
+        assert!(!rect.hit_fill(12.0, 20.0, 0.0));
+        
+        rect.draw().unwrap();
+        assert_eq!(layer.content.get_pixel(20, 12)[3], 255);
+        assert_eq!(layer.content.get_pixel(12, 20)[3], 0);
+    }
+    
+    #[test]
+    fn test_gradient_stroke_keeps_all_stops() {
+        use super::LineBuilder;
+        use crate::color::LinearGradient;
+        
+        let gradient = LinearGradient::new()
+            .start(0.0, 0.0)
+            .end(40.0, 0.0)
+            .add_stop(0.0, Color::rgba(255, 0, 0, 255))
+            .add_stop(1.0, Color::rgba(0, 0, 255, 255))
+            .build();
+        
+        let mut layer = Layer::new(LayerId::new(), "test", 40, 10);
+        LineBuilder::new(&mut layer, 0.0, 5.0, 40.0, 5.0)
+            .stroke(gradient, 4.0)
+            .draw().unwrap();
+        
+        let left = layer.content.get_pixel(2, 5);
+        let right = layer.content.get_pixel(37, 5);
+        assert!(left[0] > 200 && left[2] < 50);
+        assert!(right[2] > 200 && right[0] < 50);
+    }
+    
+    #[test]
+    fn test_even_odd_compound_polygon_has_hole() {
+        use super::{FillRule, PolygonBuilder};
+        use crate::geometry::Point;
+        
+        fn square(builder: PolygonBuilder<'_>, from: f32, to: f32) -> PolygonBuilder<'_> {
+            builder
+                .add_point(Point::new(from, from))
+                .add_point(Point::new(to, from))
+                .add_point(Point::new(to, to))
+                .add_point(Point::new(from, to))
+        }
+        
+        let mut layer = Layer::new(LayerId::new(), "test", 30, 30);
+        let donut = square(PolygonBuilder::new(&mut layer), 0.0, 30.0).new_contour();
+        let donut = square(donut, 10.0, 20.0)
+            .fill(Color::rgba(0, 0, 0, 255))
+            .fill_rule(FillRule::EvenOdd);
+        
+        assert!(donut.hit_fill(5.0, 5.0, 0.0));
+        assert!(!donut.hit_fill(15.0, 15.0, 0.0));
+        
+        donut.draw().unwrap();
+        assert_eq!(layer.content.get_pixel(5, 5)[3], 255);
+        assert_eq!(layer.content.get_pixel(15, 15)[3], 0);
+    }
+}
@@ -1,52 +1,579 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::error::Result;
-use tiny_skia::{Paint, PathBuilder as SkiaPathBuilder, Transform};
+use crate::geometry::Point;
+use crate::transform::TransformOps;
+use crate::shapes::{BooleanOp, FillRule, Shadow, StrokeStyle};
+use tiny_skia::{Path, PathBuilder as SkiaPathBuilder};
 
-/// Path command
-#[derive(Debug, Clone)]
+/// Path command, always in absolute coordinates
+///
+/// Relative, horizontal/vertical and smooth commands on `PathBuilder` are
+/// resolved into these when added.
+#[derive(Debug, Clone, PartialEq)]
 pub enum PathCommand {
     MoveTo(f32, f32),
     LineTo(f32, f32),
     CurveTo(f32, f32, f32, f32, f32, f32),
+    /// Quadratic curve with one control point
+    QuadTo(f32, f32, f32, f32),
+    /// SVG elliptical arc, rotation in degrees
+    ArcTo {
+        rx: f32,
+        ry: f32,
+        x_axis_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        x: f32,
+        y: f32,
+    },
     ClosePath,
 }
 
+/// Pen state for resolving relative and smooth commands
+#[derive(Debug, Clone, Copy, Default)]
+struct Pen {
+    current: (f32, f32),
+    start: (f32, f32),
+    // Last control point of the previous curve, for smooth continuations
+    last_cubic: Option<(f32, f32)>,
+    last_quad: Option<(f32, f32)>,
+}
+
+impl Pen {
+    fn offset(&self, dx: f32, dy: f32) -> (f32, f32) {
+        (self.current.0 + dx, self.current.1 + dy)
+    }
+    
+    /// Reflect a control point through the current point, or use the current point
+    fn reflect(&self, control: Option<(f32, f32)>) -> (f32, f32) {
+        control.map_or(self.current, |(cx, cy)| {
+            (2.0 * self.current.0 - cx, 2.0 * self.current.1 - cy)
+        })
+    }
+    
+    fn advance(&mut self, command: &PathCommand) {
+        self.last_cubic = None;
+        self.last_quad = None;
+        match *command {
+            PathCommand::MoveTo(x, y) => {
+                self.current = (x, y);
+                self.start = (x, y);
+            }
+            PathCommand::LineTo(x, y) | PathCommand::ArcTo { x, y, .. } => self.current = (x, y),
+            PathCommand::CurveTo(_, _, x2, y2, x, y) => {
+                self.current = (x, y);
+                self.last_cubic = Some((x2, y2));
+            }
+            PathCommand::QuadTo(x1, y1, x, y) => {
+                self.current = (x, y);
+                self.last_quad = Some((x1, y1));
+            }
+            PathCommand::ClosePath => self.current = self.start,
+        }
+    }
+}
+
+/// Reusable path value that outlives a draw call
+///
+/// Takes the same commands as `PathBuilder`, can be combined with boolean
+/// operations and is drawn with `PathBuilder::from_path`.
+#[derive(Debug, Clone, Default)]
+pub struct Path2D {
+    commands: Vec<PathCommand>,
+    pen: Pen,
+    pub fill_rule: FillRule,
+}
+
+impl Path2D {
+    /// Create an empty path
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Rectangle path
+    pub fn rect(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self::new()
+            .move_to(x, y)
+            .line_to(x + width, y)
+            .line_to(x + width, y + height)
+            .line_to(x, y + height)
+            .close_path()
+    }
+    
+    /// Circle path
+    pub fn circle(cx: f32, cy: f32, radius: f32) -> Self {
+        Self::ellipse(cx, cy, radius, radius)
+    }
+    
+    /// Ellipse path
+    pub fn ellipse(cx: f32, cy: f32, rx: f32, ry: f32) -> Self {
+        Self::new()
+            .move_to(cx + rx, cy)
+            .arc_to(rx, ry, 0.0, false, true, cx - rx, cy)
+            .arc_to(rx, ry, 0.0, false, true, cx + rx, cy)
+            .close_path()
+    }
+    
+    /// Closed polygon path through `points`
+    pub fn polygon(points: &[Point]) -> Self {
+        let mut path = Self::new();
+        for (i, point) in points.iter().enumerate() {
+            path = if i == 0 { path.move_to(point.x, point.y) } else { path.line_to(point.x, point.y) };
+        }
+        if points.is_empty() { path } else { path.close_path() }
+    }
+    
+    /// Create a path from SVG path data, e.g. `"M10 10 h50 a20 20 0 0 1 20 20 z"`
+    pub fn from_svg(data: &str) -> Result<Self> {
+        Self::new().svg(data)
+    }
+    
+    /// Append commands from SVG path data
+    pub fn svg(mut self, data: &str) -> Result<Self> {
+        let path = kurbo::BezPath::from_svg(data)
+            .map_err(|e| crate::error::CloveError::InvalidPathData(e.to_string()))?;
+        for element in path.elements() {
+            self = self.push_element(element);
+        }
+        Ok(self)
+    }
+    
+    /// Add an absolute command
+    pub fn push(mut self, command: PathCommand) -> Self {
+        self.pen.advance(&command);
+        self.commands.push(command);
+        self
+    }
+    
+    pub fn move_to(self, x: f32, y: f32) -> Self {
+        self.push(PathCommand::MoveTo(x, y))
+    }
+    
+    pub fn line_to(self, x: f32, y: f32) -> Self {
+        self.push(PathCommand::LineTo(x, y))
+    }
+    
+    pub fn curve_to(self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) -> Self {
+        self.push(PathCommand::CurveTo(x1, y1, x2, y2, x, y))
+    }
+    
+    /// Quadratic curve through control point (x1, y1)
+    pub fn quad_to(self, x1: f32, y1: f32, x: f32, y: f32) -> Self {
+        self.push(PathCommand::QuadTo(x1, y1, x, y))
+    }
+    
+    /// SVG-style elliptical arc to (x, y), rotation in degrees
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_to(self, rx: f32, ry: f32, x_axis_rotation: f32, large_arc: bool, sweep: bool, x: f32, y: f32) -> Self {
+        self.push(PathCommand::ArcTo { rx, ry, x_axis_rotation, large_arc, sweep, x, y })
+    }
+    
+    /// Horizontal line to `x`
+    pub fn horizontal_to(self, x: f32) -> Self {
+        let y = self.pen.current.1;
+        self.line_to(x, y)
+    }
+    
+    /// Vertical line to `y`
+    pub fn vertical_to(self, y: f32) -> Self {
+        let x = self.pen.current.0;
+        self.line_to(x, y)
+    }
+    
+    /// Cubic curve whose first control point mirrors the previous curve's last one
+    pub fn smooth_curve_to(self, x2: f32, y2: f32, x: f32, y: f32) -> Self {
+        let (x1, y1) = self.pen.reflect(self.pen.last_cubic);
+        self.curve_to(x1, y1, x2, y2, x, y)
+    }
+    
+    /// Quadratic curve whose control point mirrors the previous quadratic's
+    pub fn smooth_quad_to(self, x: f32, y: f32) -> Self {
+        let (x1, y1) = self.pen.reflect(self.pen.last_quad);
+        self.quad_to(x1, y1, x, y)
+    }
+    
+    /// Relative `move_to`
+    pub fn move_by(self, dx: f32, dy: f32) -> Self {
+        let (x, y) = self.pen.offset(dx, dy);
+        self.move_to(x, y)
+    }
+    
+    /// Relative `line_to`
+    pub fn line_by(self, dx: f32, dy: f32) -> Self {
+        let (x, y) = self.pen.offset(dx, dy);
+        self.line_to(x, y)
+    }
+    
+    /// Relative `curve_to`, all points offset from the current point
+    pub fn curve_by(self, dx1: f32, dy1: f32, dx2: f32, dy2: f32, dx: f32, dy: f32) -> Self {
+        let ((x1, y1), (x2, y2), (x, y)) = (self.pen.offset(dx1, dy1), self.pen.offset(dx2, dy2), self.pen.offset(dx, dy));
+        self.curve_to(x1, y1, x2, y2, x, y)
+    }
+    
+    /// Relative `quad_to`
+    pub fn quad_by(self, dx1: f32, dy1: f32, dx: f32, dy: f32) -> Self {
+        let ((x1, y1), (x, y)) = (self.pen.offset(dx1, dy1), self.pen.offset(dx, dy));
+        self.quad_to(x1, y1, x, y)
+    }
+    
+    /// Relative `arc_to`
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_by(self, rx: f32, ry: f32, x_axis_rotation: f32, large_arc: bool, sweep: bool, dx: f32, dy: f32) -> Self {
+        let (x, y) = self.pen.offset(dx, dy);
+        self.arc_to(rx, ry, x_axis_rotation, large_arc, sweep, x, y)
+    }
+    
+    /// Relative `horizontal_to`
+    pub fn horizontal_by(self, dx: f32) -> Self {
+        let (x, _) = self.pen.offset(dx, 0.0);
+        self.horizontal_to(x)
+    }
+    
+    /// Relative `vertical_to`
+    pub fn vertical_by(self, dy: f32) -> Self {
+        let (_, y) = self.pen.offset(0.0, dy);
+        self.vertical_to(y)
+    }
+    
+    /// Relative `smooth_curve_to`
+    pub fn smooth_curve_by(self, dx2: f32, dy2: f32, dx: f32, dy: f32) -> Self {
+        let ((x2, y2), (x, y)) = (self.pen.offset(dx2, dy2), self.pen.offset(dx, dy));
+        self.smooth_curve_to(x2, y2, x, y)
+    }
+    
+    /// Relative `smooth_quad_to`
+    pub fn smooth_quad_by(self, dx: f32, dy: f32) -> Self {
+        let (x, y) = self.pen.offset(dx, dy);
+        self.smooth_quad_to(x, y)
+    }
+    
+    pub fn close_path(self) -> Self {
+        self.push(PathCommand::ClosePath)
+    }
+    
+    /// Set how overlapping or nested contours are filled
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.fill_rule = rule;
+        self
+    }
+    
+    /// Commands in absolute coordinates
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+    
+    /// Check if the path has no commands
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+    
+    /// Area covered by this path or `other`
+    pub fn union(&self, other: &Path2D) -> Result<Path2D> {
+        self.boolean(other, BooleanOp::Union)
+    }
+    
+    /// Area covered by both this path and `other`
+    pub fn intersection(&self, other: &Path2D) -> Result<Path2D> {
+        self.boolean(other, BooleanOp::Intersection)
+    }
+    
+    /// Area of this path not covered by `other`
+    pub fn difference(&self, other: &Path2D) -> Result<Path2D> {
+        self.boolean(other, BooleanOp::Difference)
+    }
+    
+    /// Area covered by exactly one of the paths
+    pub fn xor(&self, other: &Path2D) -> Result<Path2D> {
+        self.boolean(other, BooleanOp::Xor)
+    }
+    
+    /// Combine with `other`, honoring each path's fill rule
+    ///
+    /// Curves are flattened, so the result is made of straight lines.
+    pub fn boolean(&self, other: &Path2D, op: BooleanOp) -> Result<Path2D> {
+        super::boolean::combine(self, other, op)
+    }
+    
+    /// Format as SVG path data
+    pub fn to_svg(&self) -> String {
+        let parts: Vec<String> = self.commands.iter().map(|command| match *command {
+            PathCommand::MoveTo(x, y) => format!("M{} {}", x, y),
+            PathCommand::LineTo(x, y) => format!("L{} {}", x, y),
+            PathCommand::CurveTo(x1, y1, x2, y2, x, y) => format!("C{} {} {} {} {} {}", x1, y1, x2, y2, x, y),
+            PathCommand::QuadTo(x1, y1, x, y) => format!("Q{} {} {} {}", x1, y1, x, y),
+            PathCommand::ArcTo { rx, ry, x_axis_rotation, large_arc, sweep, x, y } => format!(
+                "A{} {} {} {} {} {} {}", rx, ry, x_axis_rotation, large_arc as u8, sweep as u8, x, y
+            ),
+            PathCommand::ClosePath => "Z".to_string(),
+        }).collect();
+        parts.join(" ")
+    }
+    
+    /// Convert to a kurbo path, turning arcs into cubic curves
+    pub(crate) fn to_bez_path(&self) -> Result<kurbo::BezPath> {
+        let mut bez = kurbo::BezPath::new();
+        let mut pen = Pen::default();
+        let mut has_started = false;
+        let point = |x: f32, y: f32| kurbo::Point::new(x as f64, y as f64);
+        
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo(x, y) => {
+                    bez.move_to(point(x, y));
+                    has_started = true;
+                }
+                PathCommand::ClosePath => bez.close_path(),
+                _ if !has_started => {
+                    return Err(crate::error::CloveError::InvalidState(
+                        "Path must start with MoveTo".to_string()
+                    ));
+                }
+                PathCommand::LineTo(x, y) => bez.line_to(point(x, y)),
+                PathCommand::CurveTo(x1, y1, x2, y2, x, y) => bez.curve_to(point(x1, y1), point(x2, y2), point(x, y)),
+                PathCommand::QuadTo(x1, y1, x, y) => bez.quad_to(point(x1, y1), point(x, y)),
+                PathCommand::ArcTo { rx, ry, x_axis_rotation, large_arc, sweep, x, y } => {
+                    let arc = kurbo::SvgArc {
+                        from: point(pen.current.0, pen.current.1),
+                        to: point(x, y),
+                        radii: kurbo::Vec2::new(rx.abs() as f64, ry.abs() as f64),
+                        x_rotation: (x_axis_rotation as f64).to_radians(),
+                        large_arc,
+                        sweep,
+                    };
+                    // Degenerate arcs (zero radius or no movement) are straight lines per SVG
+                    match kurbo::Arc::from_svg_arc(&arc) {
+                        Some(arc) => bez.extend(arc.append_iter(0.1)),
+                        None => bez.line_to(point(x, y)),
+                    }
+                }
+            }
+            pen.advance(command);
+        }
+        Ok(bez)
+    }
+    
+    /// Convert to a tiny-skia path for drawing
+    pub(crate) fn to_skia(&self) -> Result<Path> {
+        let mut path_builder = SkiaPathBuilder::new();
+        for element in self.to_bez_path()?.elements() {
+            match *element {
+                kurbo::PathEl::MoveTo(p) => path_builder.move_to(p.x as f32, p.y as f32),
+                kurbo::PathEl::LineTo(p) => path_builder.line_to(p.x as f32, p.y as f32),
+                kurbo::PathEl::QuadTo(c, p) => path_builder.quad_to(c.x as f32, c.y as f32, p.x as f32, p.y as f32),
+                kurbo::PathEl::CurveTo(c1, c2, p) => path_builder.cubic_to(
+                    c1.x as f32, c1.y as f32, c2.x as f32, c2.y as f32, p.x as f32, p.y as f32,
+                ),
+                kurbo::PathEl::ClosePath => path_builder.close(),
+            }
+        }
+        
+        path_builder.finish().ok_or_else(|| {
+            crate::error::CloveError::InvalidState("Failed to create path".to_string())
+        })
+    }
+    
+    /// Copy a kurbo path
+    pub(crate) fn from_bez_path(path: &kurbo::BezPath) -> Self {
+        path.elements().iter().fold(Self::new(), Self::push_element)
+    }
+    
+    fn push_element(self, element: &kurbo::PathEl) -> Self {
+        let command = match *element {
+            kurbo::PathEl::MoveTo(p) => PathCommand::MoveTo(p.x as f32, p.y as f32),
+            kurbo::PathEl::LineTo(p) => PathCommand::LineTo(p.x as f32, p.y as f32),
+            kurbo::PathEl::QuadTo(c, p) => PathCommand::QuadTo(c.x as f32, c.y as f32, p.x as f32, p.y as f32),
+            kurbo::PathEl::CurveTo(c1, c2, p) => PathCommand::CurveTo(
+                c1.x as f32, c1.y as f32, c2.x as f32, c2.y as f32, p.x as f32, p.y as f32,
+            ),
+            kurbo::PathEl::ClosePath => PathCommand::ClosePath,
+        };
+        self.push(command)
+    }
+    
+    /// Copy a tiny-skia path
+    pub(crate) fn from_skia(path: &Path) -> Self {
+        use tiny_skia::PathSegment;
+        
+        path.segments().fold(Self::new(), |result, segment| match segment {
+            PathSegment::MoveTo(p) => result.move_to(p.x, p.y),
+            PathSegment::LineTo(p) => result.line_to(p.x, p.y),
+            PathSegment::QuadTo(c, p) => result.quad_to(c.x, c.y, p.x, p.y),
+            PathSegment::CubicTo(c1, c2, p) => result.curve_to(c1.x, c1.y, c2.x, c2.y, p.x, p.y),
+            PathSegment::Close => result.close_path(),
+        })
+    }
+}
+
 /// Path builder for custom paths
 pub struct PathBuilder<'a> {
     layer: &'a mut Layer,
-    commands: Vec<PathCommand>,
+    path: Path2D,
     fill: Option<Color>,
     stroke: Option<(Color, f32)>,
+    stroke_style: StrokeStyle,
+    shadow: Option<Shadow>,
+    transform: TransformOps,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> PathBuilder<'a> {
     pub fn new(layer: &'a mut Layer) -> Self {
         Self {
             layer,
-            commands: Vec::new(),
+            path: Path2D::new(),
             fill: None,
             stroke: None,
+            stroke_style: StrokeStyle::default(),
+            shadow: None,
+            transform: TransformOps::default(),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
+    /// Create a path from SVG path data, e.g. `"M10 10 h50 a20 20 0 0 1 20 20 z"`
+    pub fn from_svg(layer: &'a mut Layer, data: &str) -> Result<Self> {
+        Self::new(layer).svg(data)
+    }
+    
+    /// Draw a copy of a reusable path, including its fill rule
+    pub fn from_path(layer: &'a mut Layer, path: &Path2D) -> Self {
+        let mut builder = Self::new(layer);
+        builder.path = path.clone();
+        builder
+    }
+    
+    /// Append the commands of a reusable path
+    pub fn append(mut self, path: &Path2D) -> Self {
+        for command in path.commands() {
+            self.path = self.path.push(command.clone());
+        }
+        self
+    }
+    
+    /// Append commands from SVG path data
+    pub fn svg(mut self, data: &str) -> Result<Self> {
+        self.path = self.path.svg(data)?;
+        Ok(self)
+    }
+    
+    /// Add an absolute command
+    pub fn push(mut self, command: PathCommand) -> Self {
+        self.path = self.path.push(command);
+        self
+    }
+    
     pub fn move_to(mut self, x: f32, y: f32) -> Self {
-        self.commands.push(PathCommand::MoveTo(x, y));
+        self.path = self.path.move_to(x, y);
         self
     }
     
     pub fn line_to(mut self, x: f32, y: f32) -> Self {
-        self.commands.push(PathCommand::LineTo(x, y));
+        self.path = self.path.line_to(x, y);
         self
     }
     
     pub fn curve_to(mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) -> Self {
-        self.commands.push(PathCommand::CurveTo(x1, y1, x2, y2, x, y));
+        self.path = self.path.curve_to(x1, y1, x2, y2, x, y);
+        self
+    }
+    
+    /// Quadratic curve through control point (x1, y1)
+    pub fn quad_to(mut self, x1: f32, y1: f32, x: f32, y: f32) -> Self {
+        self.path = self.path.quad_to(x1, y1, x, y);
+        self
+    }
+    
+    /// SVG-style elliptical arc to (x, y), rotation in degrees
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_to(mut self, rx: f32, ry: f32, x_axis_rotation: f32, large_arc: bool, sweep: bool, x: f32, y: f32) -> Self {
+        self.path = self.path.arc_to(rx, ry, x_axis_rotation, large_arc, sweep, x, y);
+        self
+    }
+    
+    /// Horizontal line to `x`
+    pub fn horizontal_to(mut self, x: f32) -> Self {
+        self.path = self.path.horizontal_to(x);
+        self
+    }
+    
+    /// Vertical line to `y`
+    pub fn vertical_to(mut self, y: f32) -> Self {
+        self.path = self.path.vertical_to(y);
+        self
+    }
+    
+    /// Cubic curve whose first control point mirrors the previous curve's last one
+    pub fn smooth_curve_to(mut self, x2: f32, y2: f32, x: f32, y: f32) -> Self {
+        self.path = self.path.smooth_curve_to(x2, y2, x, y);
+        self
+    }
+    
+    /// Quadratic curve whose control point mirrors the previous quadratic's
+    pub fn smooth_quad_to(mut self, x: f32, y: f32) -> Self {
+        self.path = self.path.smooth_quad_to(x, y);
+        self
+    }
+    
+    /// Relative `move_to`
+    pub fn move_by(mut self, dx: f32, dy: f32) -> Self {
+        self.path = self.path.move_by(dx, dy);
+        self
+    }
+    
+    /// Relative `line_to`
+    pub fn line_by(mut self, dx: f32, dy: f32) -> Self {
+        self.path = self.path.line_by(dx, dy);
+        self
+    }
+    
+    /// Relative `curve_to`, all points offset from the current point
+    pub fn curve_by(mut self, dx1: f32, dy1: f32, dx2: f32, dy2: f32, dx: f32, dy: f32) -> Self {
+        self.path = self.path.curve_by(dx1, dy1, dx2, dy2, dx, dy);
+        self
+    }
+    
+    /// Relative `quad_to`
+    pub fn quad_by(mut self, dx1: f32, dy1: f32, dx: f32, dy: f32) -> Self {
+        self.path = self.path.quad_by(dx1, dy1, dx, dy);
+        self
+    }
+    
+    /// Relative `arc_to`
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_by(mut self, rx: f32, ry: f32, x_axis_rotation: f32, large_arc: bool, sweep: bool, dx: f32, dy: f32) -> Self {
+        self.path = self.path.arc_by(rx, ry, x_axis_rotation, large_arc, sweep, dx, dy);
+        self
+    }
+    
+    /// Relative `horizontal_to`
+    pub fn horizontal_by(mut self, dx: f32) -> Self {
+        self.path = self.path.horizontal_by(dx);
+        self
+    }
+    
+    /// Relative `vertical_to`
+    pub fn vertical_by(mut self, dy: f32) -> Self {
+        self.path = self.path.vertical_by(dy);
+        self
+    }
+    
+    /// Relative `smooth_curve_to`
+    pub fn smooth_curve_by(mut self, dx2: f32, dy2: f32, dx: f32, dy: f32) -> Self {
+        self.path = self.path.smooth_curve_by(dx2, dy2, dx, dy);
+        self
+    }
+    
+    /// Relative `smooth_quad_to`
+    pub fn smooth_quad_by(mut self, dx: f32, dy: f32) -> Self {
+        self.path = self.path.smooth_quad_by(dx, dy);
         self
     }
     
     pub fn close_path(mut self) -> Self {
-        self.commands.push(PathCommand::ClosePath);
+        self.path = self.path.close_path();
         self
     }
     
@@ -55,93 +582,83 @@ impl<'a> PathBuilder<'a> {
         self
     }
     
+    /// Set how overlapping or nested contours are filled
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.path.fill_rule = rule;
+        self
+    }
+    
     pub fn stroke(mut self, color: Color, width: f32) -> Self {
         self.stroke = Some((color, width));
         self
     }
     
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+    
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
+    super::hit_test_methods!(path.fill_rule);
+    
+    /// Copy of the path built so far, with transforms applied
+    pub fn to_path(&self) -> Result<Path2D> {
+        if self.transform.is_identity() {
+            return Ok(self.path.clone());
+        }
+        let path = self.build_path()?;
+        path.clone()
+            .transform(self.transform.resolve_for_path(&path))
+            .map(|path| Path2D::from_skia(&path).fill_rule(self.path.fill_rule))
+            .ok_or_else(|| crate::error::CloveError::InvalidTransform("Path could not be transformed".to_string()))
+    }
+    
+    /// Build the outline path
+    fn build_path(&self) -> Result<Path> {
+        self.path.to_skia()
+    }
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
-        if self.commands.is_empty() {
+        if self.path.is_empty() {
             return Ok(self.layer);
         }
         
         // Get pixmap from layer
         let mut pixmap = self.layer.get_pixmap_mut()?;
         
-        // Build path from commands
-        let mut path_builder = SkiaPathBuilder::new();
-        let mut has_started = false;
-        
-        for command in &self.commands {
-            match command {
-                PathCommand::MoveTo(x, y) => {
-                    path_builder.move_to(*x, *y);
-                    has_started = true;
-                }
-                PathCommand::LineTo(x, y) => {
-                    if !has_started {
-                        return Err(crate::error::CloveError::InvalidState(
-                            "Path must start with MoveTo".to_string()
-                        ));
-                    }
-                    path_builder.line_to(*x, *y);
-                }
-                PathCommand::CurveTo(x1, y1, x2, y2, x, y) => {
-                    if !has_started {
-                        return Err(crate::error::CloveError::InvalidState(
-                            "Path must start with MoveTo".to_string()
-                        ));
-                    }
-                    path_builder.cubic_to(*x1, *y1, *x2, *y2, *x, *y);
-                }
-                PathCommand::ClosePath => {
-                    path_builder.close();
-                }
-            }
-        }
-        
-        let path = path_builder.finish().ok_or_else(|| {
-            crate::error::CloveError::InvalidState("Failed to create path".to_string())
-        })?;
-        
-        // Draw fill if specified
-        if let Some(fill_color) = self.fill.clone() {
-            let mut paint = Paint::default();
-            paint.anti_alias = true;
-            
-            match fill_color {
-                Color::LinearGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_linear_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                Color::RadialGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_radial_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                _ => {
-                    let rgba = fill_color.to_rgba();
-                    paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-                    pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                }
-            }
-        }
+        let path = self.build_path()?;
         
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
-        }
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: self.fill.as_ref(),
+            fill_rule: self.path.fill_rule,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
@@ -150,3 +667,63 @@ impl<'a> PathBuilder<'a> {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::LayerId;
+
+    #[test]
+    fn test_relative_and_smooth_commands_resolve_to_absolute() {
+        let mut layer = Layer::new(LayerId::new(), "test", 50, 50);
+        let path = PathBuilder::new(&mut layer)
+            .move_to(10.0, 10.0)
+            .horizontal_by(10.0)
+            .vertical_to(30.0)
+            .curve_by(0.0, 5.0, 5.0, 10.0, 10.0, 10.0)
+            .smooth_curve_by(5.0, 10.0, 10.0, 10.0);
+
+        assert_eq!(path.path.commands[1], PathCommand::LineTo(20.0, 10.0));
+        assert_eq!(path.path.commands[2], PathCommand::LineTo(20.0, 30.0));
+        assert_eq!(path.path.commands[3], PathCommand::CurveTo(20.0, 35.0, 25.0, 40.0, 30.0, 40.0));
+        assert_eq!(path.path.commands[4], PathCommand::CurveTo(35.0, 40.0, 35.0, 50.0, 40.0, 50.0));
+    }
+
+    #[test]
+    fn test_svg_path_data() {
+        let mut layer = Layer::new(LayerId::new(), "test", 50, 50);
+        let path = PathBuilder::from_svg(&mut layer, "M10 10 h20 v20 Q 40 40 20 40 a10 10 0 0 1 -10 -10 z").unwrap();
+        assert!(path.path.commands.len() >= 5);
+        assert!(path.build_path().is_ok());
+        assert!(path.hit_fill(20.0, 20.0, 0.0));
+        assert!(!path.hit_fill(45.0, 5.0, 0.0));
+
+        let mut layer = Layer::new(LayerId::new(), "test", 50, 50);
+        assert!(PathBuilder::from_svg(&mut layer, "M10 10 L").is_err());
+    }
+
+    #[test]
+    fn test_arc_draws_curve() {
+        let mut layer = Layer::new(LayerId::new(), "test", 50, 50);
+        let path = PathBuilder::new(&mut layer)
+            .move_to(5.0, 25.0)
+            .arc_to(20.0, 20.0, 0.0, false, true, 45.0, 25.0)
+            .close_path();
+        // Sweep flag puts the half circle above the chord
+        assert!(path.hit_fill(25.0, 10.0, 0.0));
+        assert!(!path.hit_fill(25.0, 40.0, 0.0));
+    }
+
+    #[test]
+    fn test_draw_combined_shape_paths() {
+        let mut layer = Layer::new(LayerId::new(), "test", 50, 50);
+        let badge = crate::shapes::CircleBuilder::new(&mut layer, 25.0, 25.0, 20.0).to_path().unwrap();
+        let hole = Path2D::rect(20.0, 20.0, 10.0, 10.0);
+        let cut = badge.difference(&hole).unwrap();
+        assert!(cut.to_svg().starts_with('M'));
+
+        PathBuilder::from_path(&mut layer, &cut).fill(Color::rgba(255, 0, 0, 255)).draw().unwrap();
+        assert!(layer.alpha_at(10.0, 25.0) > 0);
+        assert_eq!(layer.alpha_at(25.0, 25.0), 0);
+    }
+}
@@ -0,0 +1,277 @@
+use super::path::Path2D;
+use super::StrokeStyle;
+use crate::error::Result;
+use crate::geometry::{Point, Rect};
+use kurbo::{BezPath, ParamCurve, ParamCurveArclen, ParamCurveDeriv, PathEl, PathSeg, Shape};
+
+/// Accuracy used when measuring arc length
+const ARCLEN_ACCURACY: f64 = 1e-3;
+
+/// Tolerance used when approximating strokes and offsets with curves
+const CURVE_TOLERANCE: f64 = 0.1;
+
+impl Path2D {
+    /// Total length of all contours
+    pub fn length(&self) -> Result<f32> {
+        let bez = self.to_bez_path()?;
+        Ok(bez.segments().map(|seg| seg.arclen(ARCLEN_ACCURACY)).sum::<f64>() as f32)
+    }
+
+    /// Point at `distance` along the path, clamped to its ends
+    pub fn point_at_length(&self, distance: f32) -> Result<Option<Point>> {
+        Ok(self.sample(distance)?.map(|(point, _)| point))
+    }
+
+    /// Unit direction of travel at `distance` along the path, clamped to its ends
+    ///
+    /// Zero where the path has no direction, such as a lone zero-length line.
+    pub fn tangent_at_length(&self, distance: f32) -> Result<Option<Point>> {
+        Ok(self.sample(distance)?.map(|(_, tangent)| tangent))
+    }
+
+    fn sample(&self, distance: f32) -> Result<Option<(Point, Point)>> {
+        let bez = self.to_bez_path()?;
+        let mut remaining = distance.max(0.0) as f64;
+        let mut last = None;
+        for seg in bez.segments() {
+            let length = seg.arclen(ARCLEN_ACCURACY);
+            let t = if remaining <= length {
+                seg.inv_arclen(remaining, ARCLEN_ACCURACY)
+            } else {
+                1.0
+            };
+            last = Some((seg, t));
+            if remaining <= length {
+                break;
+            }
+            remaining -= length;
+        }
+
+        Ok(last.map(|(seg, t)| {
+            let point = seg.eval(t);
+            let tangent = unit_tangent(seg, t);
+            (to_point(point), Point::new(tangent.x as f32, tangent.y as f32))
+        }))
+    }
+
+    /// Tight bounding box of the outline, including curve extremes
+    ///
+    /// `None` for empty paths and paths that don't start with a move.
+    pub fn bounds(&self) -> Option<Rect> {
+        if self.is_empty() {
+            return None;
+        }
+        let bounds = self.to_bez_path().ok()?.bounding_box();
+        Some(Rect::new(bounds.x0 as f32, bounds.y0 as f32, bounds.width() as f32, bounds.height() as f32))
+    }
+
+    /// Approximate each contour with a polyline, within `tolerance` pixels
+    ///
+    /// Closed contours end with their first point repeated.
+    pub fn flatten(&self, tolerance: f32) -> Result<Vec<Vec<Point>>> {
+        let mut polylines: Vec<Vec<Point>> = Vec::new();
+        kurbo::flatten(self.to_bez_path()?, tolerance.max(0.001) as f64, |element| match element {
+            PathEl::MoveTo(p) => polylines.push(vec![to_point(p)]),
+            PathEl::LineTo(p) => {
+                if let Some(polyline) = polylines.last_mut() {
+                    polyline.push(to_point(p));
+                }
+            }
+            PathEl::ClosePath => {
+                if let Some(polyline) = polylines.last_mut() {
+                    let start = polyline[0];
+                    polyline.push(start);
+                }
+            }
+            _ => {}
+        });
+        polylines.retain(|polyline| polyline.len() > 1);
+        Ok(polylines)
+    }
+
+    /// Refit the path with fewer curves, staying within `tolerance` pixels
+    pub fn simplify(&self, tolerance: f32) -> Result<Path2D> {
+        let options = kurbo::simplify::SimplifyOptions::default();
+        let simplified = kurbo::simplify::simplify_bezpath(self.to_bez_path()?, tolerance.max(0.001) as f64, &options);
+        Ok(Path2D::from_bez_path(&simplified).fill_rule(self.fill_rule))
+    }
+
+    /// Outline of the area a stroke of `width` would paint, as a fillable path
+    pub fn stroke_outline(&self, width: f32, style: &StrokeStyle) -> Result<Path2D> {
+        let outline = kurbo::stroke(
+            self.to_bez_path()?,
+            &style.to_kurbo(width),
+            &kurbo::StrokeOpts::default(),
+            CURVE_TOLERANCE,
+        );
+        Ok(Path2D::from_bez_path(&outline))
+    }
+
+    /// Parallel curve at `distance`, positive to the right of the direction of travel
+    ///
+    /// Segments are offset one by one and corners are joined with straight lines.
+    pub fn offset(&self, distance: f32) -> Result<Path2D> {
+        let bez = self.to_bez_path()?;
+        let d = distance as f64;
+        let mut result = BezPath::new();
+        let mut scratch = BezPath::new();
+        let mut started = false;
+        let mut current = kurbo::Point::ZERO;
+        let mut start = kurbo::Point::ZERO;
+        for element in bez.elements() {
+            let seg = match *element {
+                PathEl::MoveTo(p) => {
+                    current = p;
+                    start = p;
+                    started = false;
+                    continue;
+                }
+                PathEl::ClosePath => {
+                    if current != start {
+                        let closing = PathSeg::Line(kurbo::Line::new(current, start));
+                        offset_segment(closing, d, &mut scratch);
+                        append_joined(&mut result, &scratch, &mut started);
+                    }
+                    if started {
+                        result.close_path();
+                    }
+                    current = start;
+                    started = false;
+                    continue;
+                }
+                PathEl::LineTo(p) => PathSeg::Line(kurbo::Line::new(current, p)),
+                PathEl::QuadTo(c, p) => PathSeg::Quad(kurbo::QuadBez::new(current, c, p)),
+                PathEl::CurveTo(c1, c2, p) => PathSeg::Cubic(kurbo::CubicBez::new(current, c1, c2, p)),
+            };
+            current = seg.end();
+            if seg.arclen(ARCLEN_ACCURACY) == 0.0 {
+                continue;
+            }
+            offset_segment(seg, d, &mut scratch);
+            append_joined(&mut result, &scratch, &mut started);
+        }
+
+        Ok(Path2D::from_bez_path(&result).fill_rule(self.fill_rule))
+    }
+}
+
+fn to_point(p: kurbo::Point) -> Point {
+    Point::new(p.x as f32, p.y as f32)
+}
+
+fn derivative(seg: PathSeg, t: f64) -> kurbo::Vec2 {
+    match seg {
+        PathSeg::Line(line) => line.p1 - line.p0,
+        PathSeg::Quad(quad) => quad.deriv().eval(t).to_vec2(),
+        PathSeg::Cubic(cubic) => cubic.deriv().eval(t).to_vec2(),
+    }
+}
+
+/// Unit direction of travel at `t`, zero for a segment that doesn't move
+fn unit_tangent(seg: PathSeg, t: f64) -> kurbo::Vec2 {
+    // A control point on top of an end point stops the curve there, so use the
+    // direction it leaves (or arrives) in, then the chord
+    let second = match seg {
+        PathSeg::Line(_) => kurbo::Vec2::ZERO,
+        PathSeg::Quad(quad) => quad.deriv().deriv().eval(t).to_vec2(),
+        PathSeg::Cubic(cubic) => cubic.deriv().deriv().eval(t).to_vec2(),
+    };
+    let second = if t < 0.5 { second } else { -second };
+    [derivative(seg, t), second, seg.end() - seg.start()]
+        .into_iter()
+        .find(|direction| direction.hypot2() > 1e-12)
+        .map_or(kurbo::Vec2::ZERO, kurbo::Vec2::normalize)
+}
+
+/// Offset one segment into `out`, which starts with a `MoveTo`
+fn offset_segment(seg: PathSeg, d: f64, out: &mut BezPath) {
+    out.truncate(0);
+    let cubic = match seg {
+        PathSeg::Line(line) => {
+            let normal = (line.p1 - line.p0).normalize().turn_90() * d;
+            out.move_to(line.p0 + normal);
+            out.line_to(line.p1 + normal);
+            return;
+        }
+        PathSeg::Quad(quad) => quad.raise(),
+        PathSeg::Cubic(cubic) => cubic,
+    };
+    // Cubics with collinear control points are lines, which the curve offsetter can't handle
+    let chord = cubic.p3 - cubic.p0;
+    let flat = (cubic.p1 - cubic.p0).cross(chord).abs() < 1e-9 && (cubic.p2 - cubic.p0).cross(chord).abs() < 1e-9;
+    if flat && chord.hypot() > 0.0 {
+        offset_segment(PathSeg::Line(kurbo::Line::new(cubic.p0, cubic.p3)), d, out);
+    } else {
+        kurbo::offset::offset_cubic(cubic, d, CURVE_TOLERANCE, out);
+    }
+}
+
+/// Append an offset segment, bridging any gap from the previous one with a line
+fn append_joined(result: &mut BezPath, piece: &BezPath, started: &mut bool) {
+    for element in piece.elements() {
+        match *element {
+            PathEl::MoveTo(p) if *started => result.line_to(p),
+            PathEl::MoveTo(p) => {
+                result.move_to(p);
+                *started = true;
+            }
+            other => result.push(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_line_path() {
+        let path = Path2D::new().move_to(0.0, 0.0).line_to(30.0, 0.0).line_to(30.0, 40.0);
+        assert!((path.length().unwrap() - 70.0).abs() < 1e-3);
+
+        let point = path.point_at_length(50.0).unwrap().unwrap();
+        assert!((point.x - 30.0).abs() < 1e-3 && (point.y - 20.0).abs() < 1e-3);
+        let tangent = path.tangent_at_length(10.0).unwrap().unwrap();
+        assert!((tangent.x - 1.0).abs() < 1e-3 && tangent.y.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_tangent_where_derivative_vanishes() {
+        let curve = Path2D::from_svg("M0 0 C0 0 10 10 10 0").unwrap();
+        let start = curve.tangent_at_length(0.0).unwrap().unwrap();
+        assert!((start.x - start.y).abs() < 1e-3 && (start.x - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-3);
+
+        let curve = Path2D::from_svg("M0 0 C10 0 10 10 10 10").unwrap();
+        let end = curve.tangent_at_length(f32::MAX).unwrap().unwrap();
+        assert!(end.x.abs() < 1e-3 && (end.y - 1.0).abs() < 1e-3);
+
+        let dot = Path2D::new().move_to(5.0, 5.0).line_to(5.0, 5.0);
+        let tangent = dot.tangent_at_length(0.0).unwrap().unwrap();
+        assert_eq!((tangent.x, tangent.y), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_circle_geometry() {
+        let circle = Path2D::circle(50.0, 50.0, 10.0);
+        let circumference = 2.0 * std::f32::consts::PI * 10.0;
+        assert!((circle.length().unwrap() - circumference).abs() < 0.1);
+
+        let bounds = circle.bounds().unwrap();
+        assert!((bounds.x - 40.0).abs() < 1e-3 && (bounds.width - 20.0).abs() < 1e-3);
+
+        let polyline = &circle.flatten(0.1).unwrap()[0];
+        assert_eq!(polyline.first(), polyline.last());
+        assert!(polyline.len() > 8);
+    }
+
+    #[test]
+    fn test_offset_and_stroke_outline() {
+        let line = Path2D::new().move_to(0.0, 0.0).line_to(10.0, 0.0);
+        let offset = line.offset(2.0).unwrap();
+        assert_eq!(offset.to_svg(), "M0 2 L10 2");
+
+        let outline = line.stroke_outline(4.0, &StrokeStyle::new()).unwrap();
+        let bounds = outline.bounds().unwrap();
+        assert!((bounds.y + 2.0).abs() < 1e-3 && (bounds.height - 4.0).abs() < 1e-3);
+    }
+}
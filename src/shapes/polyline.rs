@@ -1,15 +1,25 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::geometry::Point;
 use crate::error::Result;
-use tiny_skia::{Paint, PathBuilder, Transform};
+use crate::transform::TransformOps;
+use crate::shapes::{FillRule, Shadow, StrokeStyle};
+use tiny_skia::PathBuilder;
 
 /// Polyline builder
 pub struct PolylineBuilder<'a> {
     layer: &'a mut Layer,
     points: Vec<Point>,
+    // Finished contours of a compound shape, `points` is the one being built
+    contours: Vec<Vec<Point>>,
     stroke: Option<(Color, f32)>,
+    fill_rule: FillRule,
+    stroke_style: StrokeStyle,
+    shadow: Option<Shadow>,
+    transform: TransformOps,
     fill: Option<Color>,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> PolylineBuilder<'a> {
@@ -17,8 +27,15 @@ impl<'a> PolylineBuilder<'a> {
         Self {
             layer,
             points: Vec::new(),
+            contours: Vec::new(),
             stroke: None,
+            fill_rule: FillRule::default(),
+            stroke_style: StrokeStyle::default(),
+            shadow: None,
+            transform: TransformOps::default(),
             fill: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
@@ -27,18 +44,67 @@ impl<'a> PolylineBuilder<'a> {
         self
     }
     
+    /// Add several points to the current contour
+    pub fn points(mut self, points: impl IntoIterator<Item = Point>) -> Self {
+        self.points.extend(points);
+        self
+    }
+    
+    /// Finish the current contour and start another one in the same shape
+    ///
+    /// Each contour is drawn as a separate open line.
+    pub fn new_contour(mut self) -> Self {
+        if !self.points.is_empty() {
+            self.contours.push(std::mem::take(&mut self.points));
+        }
+        self
+    }
+    
+    /// Set how overlapping or nested contours are filled
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.fill_rule = rule;
+        self
+    }
+    
     pub fn stroke(mut self, color: Color, width: f32) -> Self {
         self.stroke = Some((color, width));
         self
     }
     
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+    
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
     pub fn fill(mut self, color: Color) -> Self {
         self.fill = Some(color);
         self
     }
     
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
-        if self.points.is_empty() {
+        if self.points.is_empty() && self.contours.is_empty() {
             return Ok(self.layer);
         }
         
@@ -47,10 +113,12 @@ impl<'a> PolylineBuilder<'a> {
         
         // Create polyline path
         let mut path_builder = PathBuilder::new();
-        if let Some(first_point) = self.points.first() {
-            path_builder.move_to(first_point.x, first_point.y);
-            for point in self.points.iter().skip(1) {
-                path_builder.line_to(point.x, point.y);
+        for contour in self.contours.iter().chain(std::iter::once(&self.points)) {
+            if let Some(first_point) = contour.first() {
+                path_builder.move_to(first_point.x, first_point.y);
+                for point in contour.iter().skip(1) {
+                    path_builder.line_to(point.x, point.y);
+                }
             }
         }
         
@@ -58,44 +126,16 @@ impl<'a> PolylineBuilder<'a> {
             crate::error::CloveError::InvalidState("Failed to create polyline path".to_string())
         })?;
         
-        // Draw fill if specified (only if path is closed)
-        if let Some(fill_color) = self.fill.clone() {
-            let mut paint = Paint::default();
-            paint.anti_alias = true;
-            
-            match fill_color {
-                Color::LinearGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_linear_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                Color::RadialGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_radial_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                _ => {
-                    let rgba = fill_color.to_rgba();
-                    paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-                    pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                }
-            }
-        }
-        
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
-        }
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: self.fill.as_ref(),
+            fill_rule: self.fill_rule,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
@@ -1,8 +1,9 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::error::Result;
-use crate::shapes::Shadow;
-use tiny_skia::{Paint, PathBuilder, Transform};
+use crate::transform::TransformOps;
+use crate::shapes::{FillRule, Shadow, StrokeStyle};
+use tiny_skia::PathBuilder;
 
 /// Rounded rectangle builder
 pub struct RoundedRectBuilder<'a> {
@@ -14,8 +15,11 @@ pub struct RoundedRectBuilder<'a> {
     corner_radius: f32,
     fill: Option<Color>,
     stroke: Option<(Color, f32)>,
+    stroke_style: StrokeStyle,
     shadow: Option<Shadow>,
+    transform: TransformOps,
     opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> RoundedRectBuilder<'a> {
@@ -29,11 +33,26 @@ impl<'a> RoundedRectBuilder<'a> {
             corner_radius: 0.0,
             fill: None,
             stroke: None,
+            stroke_style: StrokeStyle::default(),
             shadow: None,
+            transform: TransformOps::default(),
             opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
+    pub fn position(mut self, x: f32, y: f32) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+    
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+    
     pub fn corner_radius(mut self, radius: f32) -> Self {
         self.corner_radius = radius;
         self
@@ -49,16 +68,33 @@ impl<'a> RoundedRectBuilder<'a> {
         self
     }
     
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
     pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
         self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
         self
     }
     
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
     pub fn opacity(mut self, opacity: f32) -> Self {
         self.opacity = opacity.clamp(0.0, 1.0);
         self
     }
     
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
         // Get pixmap from layer
         let mut pixmap = self.layer.get_pixmap_mut()?;
@@ -103,46 +139,16 @@ impl<'a> RoundedRectBuilder<'a> {
             crate::error::CloveError::InvalidState("Failed to create rounded rectangle path".to_string())
         })?;
         
-        // Draw fill if specified
-        if let Some(fill_color) = self.fill.clone() {
-            let mut paint = Paint::default();
-            paint.anti_alias = true;
-            
-            match fill_color {
-                Color::LinearGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_linear_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                Color::RadialGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_radial_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                _ => {
-                    let rgba = fill_color.to_rgba();
-                    let alpha = (self.opacity * (rgba.a as f32 / 255.0) * 255.0) as u8;
-                    paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, alpha);
-                    pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                }
-            }
-        }
-        
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            let alpha = (self.opacity * (rgba.a as f32 / 255.0) * 255.0) as u8;
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, alpha);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
-        }
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: self.fill.as_ref(),
+            fill_rule: FillRule::NonZero,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
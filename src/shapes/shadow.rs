@@ -0,0 +1,215 @@
+use crate::color::Color;
+use crate::layer::BlendMode;
+use image::GrayImage;
+use super::FillRule;
+use tiny_skia::{Paint, Path, Pixmap, Stroke, Transform};
+
+/// Shadow for shapes
+#[derive(Debug, Clone)]
+pub struct Shadow {
+    pub color: Color,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// Blur radius, the Gaussian sigma is half of it as in CSS
+    pub blur: f32,
+    /// Grows (or with a negative value shrinks) the shadow before blurring
+    pub spread: f32,
+    /// Draw inside the shape, over its fill, instead of beneath it
+    pub inset: bool,
+}
+
+impl Shadow {
+    pub fn new(color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        Self {
+            color,
+            offset_x,
+            offset_y,
+            blur,
+            spread: 0.0,
+            inset: false,
+        }
+    }
+
+    /// Set spread distance
+    pub fn spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Make this an inner shadow
+    pub fn inset(mut self) -> Self {
+        self.inset = true;
+        self
+    }
+
+    /// Draw this shadow for a shape covering `coverage` onto `target`
+    ///
+    /// Drop shadows should be drawn before the shape and inset shadows after its fill.
+    pub(crate) fn draw(&self, target: &mut Pixmap, coverage: &GrayImage, opacity: f32, blend_mode: BlendMode) {
+        let (dx, dy) = (self.offset_x.round() as i32, self.offset_y.round() as i32);
+        let radius = self.spread.abs().round() as u32;
+
+        let alpha = if self.inset {
+            // Shadow cast by the area outside the shape, kept only inside it
+            let mut shape = coverage.clone();
+            if self.spread > 0.0 {
+                shape = erode(&shape, radius);
+            } else if self.spread < 0.0 {
+                shape = dilate(&shape, radius);
+            }
+            image::imageops::invert(&mut shape);
+            let mut alpha = blur(&shift(&shape, dx, dy, 255), self.blur);
+            for (a, c) in alpha.pixels_mut().zip(coverage.pixels()) {
+                a[0] = ((a[0] as u32 * c[0] as u32 + 127) / 255) as u8;
+            }
+            alpha
+        } else {
+            let mut shape = shift(coverage, dx, dy, 0);
+            if self.spread > 0.0 {
+                shape = dilate(&shape, radius);
+            } else if self.spread < 0.0 {
+                shape = erode(&shape, radius);
+            }
+            blur(&shape, self.blur)
+        };
+
+        let Some(shadow) = colorize(&alpha, &self.color, opacity) else {
+            return;
+        };
+        let paint = tiny_skia::PixmapPaint {
+            blend_mode: blend_mode.to_skia(),
+            ..Default::default()
+        };
+        target.draw_pixmap(0, 0, shadow.as_ref(), &paint, Transform::identity(), None);
+    }
+}
+
+/// Coverage of a filled (with the given rule) and/or stroked path, sized like the target layer
+pub(crate) fn path_coverage(path: &Path, transform: Transform, fill: Option<FillRule>, stroke: Option<&Stroke>, width: u32, height: u32) -> Option<GrayImage> {
+    let mut pixmap = Pixmap::new(width, height)?;
+    let mut paint = Paint {
+        anti_alias: true,
+        ..Default::default()
+    };
+    paint.set_color_rgba8(0, 0, 0, 255);
+
+    if let Some(fill_rule) = fill {
+        pixmap.fill_path(path, &paint, fill_rule.to_skia(), transform, None);
+    }
+    if let Some(stroke) = stroke {
+        pixmap.stroke_path(path, &paint, stroke, transform, None);
+    }
+    Some(alpha_of(&pixmap))
+}
+
+/// Alpha channel of a pixmap
+pub(crate) fn alpha_of(pixmap: &Pixmap) -> GrayImage {
+    let alpha = pixmap.data().chunks_exact(4).map(|px| px[3]).collect();
+    GrayImage::from_raw(pixmap.width(), pixmap.height(), alpha)
+        .unwrap_or_else(|| GrayImage::new(pixmap.width(), pixmap.height()))
+}
+
+/// Move coverage by whole pixels, filling uncovered edges with `fill`
+fn shift(image: &GrayImage, dx: i32, dy: i32, fill: u8) -> GrayImage {
+    let (width, height) = image.dimensions();
+    GrayImage::from_fn(width, height, |x, y| {
+        let (sx, sy) = (x as i32 - dx, y as i32 - dy);
+        if sx < 0 || sy < 0 || sx >= width as i32 || sy >= height as i32 {
+            image::Luma([fill])
+        } else {
+            *image.get_pixel(sx as u32, sy as u32)
+        }
+    })
+}
+
+fn blur(image: &GrayImage, radius: f32) -> GrayImage {
+    if radius <= 0.0 {
+        return image.clone();
+    }
+    image::imageops::blur(image, radius / 2.0)
+}
+
+/// Grow coverage by `radius` pixels (separable max filter)
+fn dilate(image: &GrayImage, radius: u32) -> GrayImage {
+    morph(image, radius, u8::max)
+}
+
+/// Shrink coverage by `radius` pixels (separable min filter)
+fn erode(image: &GrayImage, radius: u32) -> GrayImage {
+    morph(image, radius, u8::min)
+}
+
+fn morph(image: &GrayImage, radius: u32, pick: fn(u8, u8) -> u8) -> GrayImage {
+    if radius == 0 {
+        return image.clone();
+    }
+    let (width, height) = image.dimensions();
+    let r = radius as i64;
+    let pass = |src: &GrayImage, horizontal: bool| {
+        GrayImage::from_fn(width, height, |x, y| {
+            let mut value = src.get_pixel(x, y)[0];
+            for d in -r..=r {
+                let (sx, sy) = if horizontal { (x as i64 + d, y as i64) } else { (x as i64, y as i64 + d) };
+                if sx >= 0 && sy >= 0 && sx < width as i64 && sy < height as i64 {
+                    value = pick(value, src.get_pixel(sx as u32, sy as u32)[0]);
+                }
+            }
+            image::Luma([value])
+        })
+    };
+    pass(&pass(image, true), false)
+}
+
+/// Turn coverage into a premultiplied pixmap of a solid color
+fn colorize(alpha: &GrayImage, color: &Color, opacity: f32) -> Option<Pixmap> {
+    let rgba = color.to_rgba();
+    let base = rgba.a as f32 / 255.0 * opacity.clamp(0.0, 1.0);
+    let mut pixmap = Pixmap::new(alpha.width(), alpha.height())?;
+    for (px, a) in pixmap.data_mut().chunks_exact_mut(4).zip(alpha.pixels()) {
+        let a = a[0] as f32 / 255.0 * base;
+        px[0] = (rgba.r as f32 * a).round() as u8;
+        px[1] = (rgba.g as f32 * a).round() as u8;
+        px[2] = (rgba.b as f32 * a).round() as u8;
+        px[3] = (a * 255.0).round() as u8;
+    }
+    Some(pixmap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: u32, from: u32, to: u32) -> GrayImage {
+        GrayImage::from_fn(size, size, |x, y| {
+            image::Luma([if (from..to).contains(&x) && (from..to).contains(&y) { 255 } else { 0 }])
+        })
+    }
+
+    #[test]
+    fn test_drop_shadow_offset_and_spread() {
+        let coverage = square(20, 5, 10);
+        let mut target = Pixmap::new(20, 20).unwrap();
+        Shadow::new(Color::rgba(0, 0, 0, 255), 5.0, 5.0, 0.0)
+            .spread(1.0)
+            .draw(&mut target, &coverage, 1.0, BlendMode::Normal);
+
+        let alpha = alpha_of(&target);
+        assert_eq!(alpha.get_pixel(12, 12)[0], 255);
+        assert_eq!(alpha.get_pixel(9, 9)[0], 255);
+        assert_eq!(alpha.get_pixel(8, 8)[0], 0);
+    }
+
+    #[test]
+    fn test_inset_shadow_stays_inside() {
+        let coverage = square(20, 5, 15);
+        let mut target = Pixmap::new(20, 20).unwrap();
+        Shadow::new(Color::rgba(0, 0, 0, 255), 2.0, 2.0, 0.0)
+            .inset()
+            .draw(&mut target, &coverage, 1.0, BlendMode::Normal);
+
+        let alpha = alpha_of(&target);
+        assert_eq!(alpha.get_pixel(5, 5)[0], 255);
+        assert_eq!(alpha.get_pixel(10, 10)[0], 0);
+        assert_eq!(alpha.get_pixel(2, 2)[0], 0);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::color::Color;
+use crate::error::Result;
+use crate::geometry::{Point, Rect};
+use crate::layer::{BlendMode, Layer};
+use crate::transform::TransformOps;
+use super::{FillRule, Path2D, Shadow, StrokeStyle};
+
+/// Geometry that can be stored, measured and turned into a path
+pub trait Shape {
+    /// Outline of the shape
+    fn to_path(&self) -> Path2D;
+
+    /// Tight bounding box, `None` for empty shapes
+    fn bounds(&self) -> Option<Rect> {
+        self.to_path().bounds()
+    }
+}
+
+/// Something that can be drawn onto any layer with a `Style`
+pub trait Drawable {
+    fn draw_on(&self, layer: &mut Layer, style: &Style) -> Result<()>;
+}
+
+/// Paint settings for drawing owned shapes, reusable across shapes and layers
+#[derive(Debug, Clone)]
+pub struct Style {
+    pub(crate) fill: Option<Color>,
+    pub(crate) stroke: Option<(Color, f32)>,
+    pub(crate) stroke_style: StrokeStyle,
+    pub(crate) shadow: Option<Shadow>,
+    pub(crate) opacity: f32,
+    pub(crate) blend_mode: BlendMode,
+    pub(crate) transform: TransformOps,
+}
+
+impl Style {
+    /// Style that paints nothing until a fill or stroke is set
+    pub fn new() -> Self {
+        Self {
+            fill: None,
+            stroke: None,
+            stroke_style: StrokeStyle::default(),
+            shadow: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            transform: TransformOps::default(),
+        }
+    }
+
+    pub fn fill(mut self, color: Color) -> Self {
+        self.fill = Some(color);
+        self
+    }
+
+    pub fn stroke(mut self, color: Color, width: f32) -> Self {
+        self.stroke = Some((color, width));
+        self
+    }
+
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+
+    crate::transform::transform_methods!("shape");
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fill and stroke a path on a layer
+pub(crate) fn draw_path_on(path: &Path2D, layer: &mut Layer, style: &Style) -> Result<()> {
+    if path.is_empty() {
+        return Ok(());
+    }
+    let skia_path = path.to_skia()?;
+    let mut pixmap = layer.get_pixmap_mut()?;
+
+    super::fill_and_stroke(&mut pixmap, &skia_path, &super::ShapeStyle {
+        fill: style.fill.as_ref(),
+        fill_rule: path.fill_rule,
+        stroke: style.stroke.as_ref(),
+        stroke_style: &style.stroke_style,
+        shadow: style.shadow.as_ref(),
+        opacity: style.opacity,
+        blend_mode: style.blend_mode,
+        transform: style.transform.resolve_for_path(&skia_path),
+    });
+
+    layer.update_from_pixmap(&pixmap)
+}
+
+/// Circle shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub fn new(center_x: f32, center_y: f32, radius: f32) -> Self {
+        Self { center: Point::new(center_x, center_y), radius }
+    }
+}
+
+/// Ellipse shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipse {
+    pub center: Point,
+    pub radius_x: f32,
+    pub radius_y: f32,
+}
+
+impl Ellipse {
+    pub fn new(center_x: f32, center_y: f32, radius_x: f32, radius_y: f32) -> Self {
+        Self { center: Point::new(center_x, center_y), radius_x, radius_y }
+    }
+}
+
+/// Polygon shape, optionally with several contours
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Polygon {
+    pub contours: Vec<Vec<Point>>,
+    pub fill_rule: FillRule,
+}
+
+impl Polygon {
+    /// Polygon with a single contour
+    pub fn new(points: Vec<Point>) -> Self {
+        Self { contours: vec![points], fill_rule: FillRule::default() }
+    }
+
+    /// Add another contour, e.g. a hole
+    pub fn contour(mut self, points: Vec<Point>) -> Self {
+        self.contours.push(points);
+        self
+    }
+
+    /// Set how overlapping or nested contours are filled
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.fill_rule = rule;
+        self
+    }
+}
+
+/// Star shape, first point straight up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Star {
+    pub center: Point,
+    pub outer_radius: f32,
+    pub inner_radius: f32,
+    pub points: u32,
+}
+
+impl Star {
+    pub fn new(center: Point, outer_radius: f32, inner_radius: f32, points: u32) -> Self {
+        Self { center, outer_radius, inner_radius, points: points.max(3) }
+    }
+}
+
+/// Circular arc, angles in degrees clockwise from the positive x axis
+///
+/// Filling draws a pie slice closed through the center, like `ArcBuilder`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arc {
+    pub center: Point,
+    pub radius: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl Arc {
+    pub fn new(center: Point, radius: f32, start_angle: f32, end_angle: f32) -> Self {
+        Self { center, radius, start_angle, end_angle }
+    }
+}
+
+/// Quadratic or cubic Bezier curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bezier {
+    pub start: Point,
+    pub control1: Point,
+    /// `None` for a quadratic curve
+    pub control2: Option<Point>,
+    pub end: Point,
+}
+
+impl Bezier {
+    pub fn quadratic(start: Point, control: Point, end: Point) -> Self {
+        Self { start, control1: control, control2: None, end }
+    }
+
+    pub fn cubic(start: Point, control1: Point, control2: Point, end: Point) -> Self {
+        Self { start, control1, control2: Some(control2), end }
+    }
+}
+
+impl Shape for Rect {
+    fn to_path(&self) -> Path2D {
+        Path2D::rect(self.x, self.y, self.width, self.height)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        Some(*self)
+    }
+}
+
+impl Shape for Circle {
+    fn to_path(&self) -> Path2D {
+        Path2D::circle(self.center.x, self.center.y, self.radius)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        let r = self.radius.abs();
+        Some(Rect::new(self.center.x - r, self.center.y - r, r * 2.0, r * 2.0))
+    }
+}
+
+impl Shape for Ellipse {
+    fn to_path(&self) -> Path2D {
+        Path2D::ellipse(self.center.x, self.center.y, self.radius_x, self.radius_y)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        let (rx, ry) = (self.radius_x.abs(), self.radius_y.abs());
+        Some(Rect::new(self.center.x - rx, self.center.y - ry, rx * 2.0, ry * 2.0))
+    }
+}
+
+impl Shape for Polygon {
+    fn to_path(&self) -> Path2D {
+        let mut path = Path2D::new().fill_rule(self.fill_rule);
+        for contour in &self.contours {
+            for command in Path2D::polygon(contour).commands() {
+                path = path.push(command.clone());
+            }
+        }
+        path
+    }
+}
+
+impl Shape for Star {
+    fn to_path(&self) -> Path2D {
+        super::star::star_path(self.center, self.outer_radius, self.inner_radius, self.points)
+            .map(|path| Path2D::from_skia(&path))
+            .unwrap_or_default()
+    }
+}
+
+impl Arc {
+    /// Arc outline, closed through the center for a pie slice when `pie`
+    fn path(&self, pie: bool) -> Path2D {
+        super::arc::arc_path(self.center, self.radius, self.start_angle, self.end_angle, pie)
+            .map(|path| Path2D::from_skia(&path))
+            .unwrap_or_default()
+    }
+}
+
+impl Shape for Arc {
+    fn to_path(&self) -> Path2D {
+        self.path(false)
+    }
+}
+
+impl Shape for Bezier {
+    fn to_path(&self) -> Path2D {
+        let path = Path2D::new().move_to(self.start.x, self.start.y);
+        match self.control2 {
+            Some(control2) => path.curve_to(self.control1.x, self.control1.y, control2.x, control2.y, self.end.x, self.end.y),
+            None => path.quad_to(self.control1.x, self.control1.y, self.end.x, self.end.y),
+        }
+    }
+}
+
+impl Shape for Path2D {
+    fn to_path(&self) -> Path2D {
+        self.clone()
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        Path2D::bounds(self)
+    }
+}
+
+macro_rules! drawable_shapes {
+    ($($shape:ty),*) => {
+        $(
+            impl Drawable for $shape {
+                fn draw_on(&self, layer: &mut Layer, style: &Style) -> Result<()> {
+                    draw_path_on(&self.to_path(), layer, style)
+                }
+            }
+        )*
+    };
+}
+
+drawable_shapes!(Rect, Circle, Ellipse, Polygon, Star, Bezier, Path2D);
+
+impl Drawable for Arc {
+    fn draw_on(&self, layer: &mut Layer, style: &Style) -> Result<()> {
+        draw_path_on(&self.path(style.fill.is_some()), layer, style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::LayerId;
+
+    #[test]
+    fn test_one_shape_on_several_layers() {
+        let badge = Circle::new(10.0, 10.0, 5.0);
+        let style = Style::new().fill(Color::rgba(255, 0, 0, 255));
+        let mut first = Layer::new(LayerId::new(), "first", 20, 20);
+        let mut second = Layer::new(LayerId::new(), "second", 20, 20);
+
+        badge.draw_on(&mut first, &style).unwrap();
+        badge.draw_on(&mut second, &style.clone().opacity(0.5)).unwrap();
+
+        assert_eq!(first.alpha_at(10.0, 10.0), 255);
+        assert!((126..=129).contains(&second.alpha_at(10.0, 10.0)));
+        assert_eq!(first.alpha_at(1.0, 1.0), 0);
+    }
+
+    #[test]
+    fn test_shape_bounds() {
+        let star = Star::new(Point::new(50.0, 50.0), 20.0, 10.0, 5);
+        let bounds = star.bounds().unwrap();
+        assert!((bounds.y - 30.0).abs() < 1e-3);
+        assert!(bounds.height < 40.0);
+
+        let arc = Arc::new(Point::new(0.0, 0.0), 10.0, 0.0, 90.0);
+        let bounds = arc.bounds().unwrap();
+        assert!(bounds.x.abs() < 1e-3 && (bounds.width - 10.0).abs() < 1e-3);
+
+        let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Rect::new(0.0, 0.0, 4.0, 4.0)), Box::new(Circle::new(0.0, 0.0, 1.0))];
+        assert!(shapes.iter().all(|shape| !shape.to_path().is_empty()));
+    }
+
+    #[test]
+    fn test_star_and_arc_match_their_builders() {
+        let mut layer = Layer::new(LayerId::new(), "shapes", 20, 20);
+        let star = Star::new(Point::new(10.0, 10.0), 8.0, 4.0, 6);
+        let built = layer.draw_star().center(10.0, 10.0).radius(8.0, 4.0).points(6).to_path().unwrap();
+        assert_eq!(star.to_path().commands(), built.commands());
+
+        let arc = Arc::new(Point::new(10.0, 10.0), 8.0, 30.0, 200.0);
+        let style = Style::new().fill(Color::rgba(0, 0, 0, 255));
+        arc.draw_on(&mut layer, &style).unwrap();
+        let mut expected = Layer::new(LayerId::new(), "builder", 20, 20);
+        expected.draw_arc().center(10.0, 10.0).radius(8.0).angles(30.0, 200.0).fill(Color::rgba(0, 0, 0, 255)).draw().unwrap();
+        assert_eq!(layer.content, expected.content);
+    }
+}
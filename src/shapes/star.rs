@@ -1,8 +1,10 @@
-use crate::layer::Layer;
+use crate::layer::{Layer, BlendMode};
 use crate::color::Color;
 use crate::geometry::Point;
 use crate::error::Result;
-use tiny_skia::{Paint, PathBuilder, Transform};
+use crate::transform::TransformOps;
+use crate::shapes::{FillRule, Path2D, Shadow, StrokeStyle};
+use tiny_skia::{Path, PathBuilder};
 
 /// Star builder
 pub struct StarBuilder<'a> {
@@ -13,6 +15,12 @@ pub struct StarBuilder<'a> {
     points: u32,
     fill: Option<Color>,
     stroke: Option<(Color, f32)>,
+    fill_rule: FillRule,
+    stroke_style: StrokeStyle,
+    shadow: Option<Shadow>,
+    transform: TransformOps,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl<'a> StarBuilder<'a> {
@@ -25,89 +33,106 @@ impl<'a> StarBuilder<'a> {
             points,
             fill: None,
             stroke: None,
+            fill_rule: FillRule::default(),
+            stroke_style: StrokeStyle::default(),
+            shadow: None,
+            transform: TransformOps::default(),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center = Point::new(x, y);
+        self
+    }
+    
+    pub fn radius(mut self, outer_radius: f32, inner_radius: f32) -> Self {
+        self.outer_radius = outer_radius;
+        self.inner_radius = inner_radius;
+        self
+    }
+    
+    pub fn points(mut self, points: u32) -> Self {
+        self.points = points.max(3);
+        self
+    }
+    
     pub fn fill(mut self, color: Color) -> Self {
         self.fill = Some(color);
         self
     }
     
+    /// Set how overlapping or nested contours are filled
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.fill_rule = rule;
+        self
+    }
+    
     pub fn stroke(mut self, color: Color, width: f32) -> Self {
         self.stroke = Some((color, width));
         self
     }
     
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+    
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("shape");
+    
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
+    super::hit_test_methods!(fill_rule);
+    
+    /// Outline as a reusable path, with transforms applied
+    pub fn to_path(&self) -> Option<Path2D> {
+        let path = self.build_path()?;
+        let path = path.clone().transform(self.transform.resolve_for_path(&path))?;
+        Some(Path2D::from_skia(&path).fill_rule(self.fill_rule))
+    }
+    
+    /// Build the outline path
+    fn build_path(&self) -> Option<Path> {
+        star_path(self.center, self.outer_radius, self.inner_radius, self.points)
+    }
+    
     pub fn draw(self) -> Result<&'a mut Layer> {
         // Get pixmap from layer
         let mut pixmap = self.layer.get_pixmap_mut()?;
         
-        // Create star path
-        let mut path_builder = PathBuilder::new();
-        let angle_step = std::f32::consts::PI * 2.0 / (self.points as f32);
-        
-        for i in 0..self.points {
-            // Outer point
-            let outer_angle = i as f32 * angle_step - std::f32::consts::PI / 2.0;
-            let outer_x = self.center.x + self.outer_radius * outer_angle.cos();
-            let outer_y = self.center.y + self.outer_radius * outer_angle.sin();
-            
-            if i == 0 {
-                path_builder.move_to(outer_x, outer_y);
-            } else {
-                path_builder.line_to(outer_x, outer_y);
-            }
-            
-            // Inner point
-            let inner_angle = (i as f32 + 0.5) * angle_step - std::f32::consts::PI / 2.0;
-            let inner_x = self.center.x + self.inner_radius * inner_angle.cos();
-            let inner_y = self.center.y + self.inner_radius * inner_angle.sin();
-            path_builder.line_to(inner_x, inner_y);
-        }
-        path_builder.close();
-        
-        let path = path_builder.finish().ok_or_else(|| {
+        let path = self.build_path().ok_or_else(|| {
             crate::error::CloveError::InvalidState("Failed to create star path".to_string())
         })?;
         
-        // Draw fill if specified
-        if let Some(fill_color) = self.fill.clone() {
-            let mut paint = Paint::default();
-            paint.anti_alias = true;
-            
-            match fill_color {
-                Color::LinearGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_linear_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                Color::RadialGradient(grad) => {
-                    if let Some(shader) = grad.to_skia_radial_gradient(Transform::identity()) {
-                        paint.shader = shader;
-                        pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                    }
-                }
-                _ => {
-                    let rgba = fill_color.to_rgba();
-                    paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-                    pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, Transform::identity(), None);
-                }
-            }
-        }
-        
-        // Draw stroke if specified
-        if let Some((stroke_color, stroke_width)) = self.stroke {
-            let rgba = stroke_color.to_rgba();
-            let mut paint = Paint::default();
-            paint.set_color_rgba8(rgba.r, rgba.g, rgba.b, rgba.a);
-            paint.anti_alias = true;
-            
-            let mut stroke = tiny_skia::Stroke::default();
-            stroke.width = stroke_width;
-            
-            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
-        }
+        super::fill_and_stroke(&mut pixmap, &path, &super::ShapeStyle {
+            fill: self.fill.as_ref(),
+            fill_rule: self.fill_rule,
+            stroke: self.stroke.as_ref(),
+            stroke_style: &self.stroke_style,
+            shadow: self.shadow.as_ref(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            transform: self.transform.resolve_for_path(&path),
+        });
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
@@ -116,3 +141,32 @@ impl<'a> StarBuilder<'a> {
     }
 }
 
+/// Star outline alternating outer and inner vertices, first point straight up
+///
+/// Shared by `StarBuilder` and the `Star` shape.
+pub(crate) fn star_path(center: Point, outer_radius: f32, inner_radius: f32, points: u32) -> Option<Path> {
+    let mut path_builder = PathBuilder::new();
+    let angle_step = std::f32::consts::PI * 2.0 / (points as f32);
+    
+    for i in 0..points {
+        // Outer point
+        let outer_angle = i as f32 * angle_step - std::f32::consts::PI / 2.0;
+        let outer_x = center.x + outer_radius * outer_angle.cos();
+        let outer_y = center.y + outer_radius * outer_angle.sin();
+        
+        if i == 0 {
+            path_builder.move_to(outer_x, outer_y);
+        } else {
+            path_builder.line_to(outer_x, outer_y);
+        }
+        
+        // Inner point
+        let inner_angle = (i as f32 + 0.5) * angle_step - std::f32::consts::PI / 2.0;
+        let inner_x = center.x + inner_radius * inner_angle.cos();
+        let inner_y = center.y + inner_radius * inner_angle.sin();
+        path_builder.line_to(inner_x, inner_y);
+    }
+    path_builder.close();
+    
+    path_builder.finish()
+}
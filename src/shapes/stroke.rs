@@ -0,0 +1,153 @@
+/// Shape of open stroke ends
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+/// Shape of stroke corners
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// Stroke styling: dashes, caps, joins and miter limit
+///
+/// The stroke width is still set with `stroke(color, width)` on each builder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeStyle {
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub miter_limit: f32,
+    /// Alternating dash and gap lengths, empty for a solid line
+    pub dash_array: Vec<f32>,
+    pub dash_offset: f32,
+}
+
+impl StrokeStyle {
+    /// Solid stroke with butt caps and miter joins
+    pub fn new() -> Self {
+        Self {
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+            dash_array: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+
+    /// Set line cap
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Set line join
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Set the limit on miter length relative to stroke width
+    pub fn miter_limit(mut self, limit: f32) -> Self {
+        self.miter_limit = limit.max(1.0);
+        self
+    }
+
+    /// Set dash pattern and offset into it
+    pub fn dash(mut self, dash_array: Vec<f32>, offset: f32) -> Self {
+        self.dash_array = dash_array;
+        self.dash_offset = offset;
+        self
+    }
+
+    /// Check if this style draws dashes
+    pub fn is_dashed(&self) -> bool {
+        !self.dash_array.is_empty()
+    }
+
+    /// Convert to tiny-skia stroke of the given width
+    pub(crate) fn to_skia(&self, width: f32) -> tiny_skia::Stroke {
+        tiny_skia::Stroke {
+            width,
+            miter_limit: self.miter_limit,
+            line_cap: match self.cap {
+                LineCap::Butt => tiny_skia::LineCap::Butt,
+                LineCap::Round => tiny_skia::LineCap::Round,
+                LineCap::Square => tiny_skia::LineCap::Square,
+            },
+            line_join: match self.join {
+                LineJoin::Miter => tiny_skia::LineJoin::Miter,
+                LineJoin::Round => tiny_skia::LineJoin::Round,
+                LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
+            },
+            dash: self.to_skia_dash(),
+        }
+    }
+
+    /// Convert to kurbo stroke of the given width
+    pub(crate) fn to_kurbo(&self, width: f32) -> kurbo::Stroke {
+        let stroke = kurbo::Stroke::new(width as f64)
+            .with_miter_limit(self.miter_limit as f64)
+            .with_caps(match self.cap {
+                LineCap::Butt => kurbo::Cap::Butt,
+                LineCap::Round => kurbo::Cap::Round,
+                LineCap::Square => kurbo::Cap::Square,
+            })
+            .with_join(match self.join {
+                LineJoin::Miter => kurbo::Join::Miter,
+                LineJoin::Round => kurbo::Join::Round,
+                LineJoin::Bevel => kurbo::Join::Bevel,
+            });
+        match self.to_skia_dash() {
+            Some(_) => stroke.with_dashes(self.dash_offset as f64, self.intervals().into_iter().map(f64::from)),
+            None => stroke,
+        }
+    }
+
+    /// Dash intervals with odd patterns repeated, as SVG does
+    fn intervals(&self) -> Vec<f32> {
+        let mut intervals = self.dash_array.clone();
+        if intervals.len() % 2 == 1 {
+            intervals.extend_from_slice(&self.dash_array);
+        }
+        intervals
+    }
+
+    fn to_skia_dash(&self) -> Option<tiny_skia::StrokeDash> {
+        if !self.is_dashed() {
+            return None;
+        }
+        // tiny-skia needs an even number of intervals
+        tiny_skia::StrokeDash::new(self.intervals(), self.dash_offset)
+    }
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odd_dash_array_is_repeated() {
+        let style = StrokeStyle::new().dash(vec![4.0, 2.0, 1.0], 0.0);
+        assert!(style.to_skia(1.0).dash.is_some());
+        assert!(StrokeStyle::new().to_skia(1.0).dash.is_none());
+    }
+
+    #[test]
+    fn test_invalid_dash_falls_back_to_solid() {
+        let style = StrokeStyle::new().dash(vec![0.0, 0.0], 0.0);
+        assert!(style.to_skia(1.0).dash.is_none());
+    }
+}
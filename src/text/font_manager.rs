@@ -7,6 +7,7 @@ pub struct FontManager {
     font_system: FontSystem,
     font_names: HashSet<String>, // Track loaded font names
     font_name_map: HashMap<String, String>, // Maps custom names to font family names
+    font_paths: HashMap<String, String>, // Maps custom names to the files they were loaded from
     default_family: Option<String>,
 }
 
@@ -16,6 +17,7 @@ impl FontManager {
             font_system: FontSystem::new(),
             font_names: HashSet::new(),
             font_name_map: HashMap::new(),
+            font_paths: HashMap::new(),
             default_family: None,
         }
     }
@@ -33,6 +35,7 @@ impl FontManager {
         
         // Map custom name to family name (use name as family name for simplicity)
         self.font_name_map.insert(name.to_string(), name.to_string());
+        self.font_paths.insert(name.to_string(), path.to_string());
         
         Ok(self)
     }
@@ -66,6 +69,11 @@ impl FontManager {
         self.font_names.len()
     }
     
+    /// Get (name, path) pairs of fonts loaded from files
+    pub fn font_paths(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.font_paths.iter().map(|(name, path)| (name.as_str(), path.as_str()))
+    }
+    
     /// Get font family name by custom name
     pub(crate) fn get_font_family(&self, name: &str) -> Option<&String> {
         self.font_name_map.get(name)
@@ -93,6 +101,7 @@ impl Clone for FontManager {
             font_system: FontSystem::new(),
             font_names: self.font_names.clone(),
             font_name_map: self.font_name_map.clone(),
+            font_paths: self.font_paths.clone(),
             default_family: self.default_family.clone(),
         }
     }
@@ -9,7 +9,7 @@ pub mod line_break;
 pub use font_manager::FontManager;
 pub use text_style::{TextStyle, FontWeight, FontStyle, TextAlign, TextWidth};
 pub use text_layout::TextLayout;
-pub use text_builder::TextBuilder;
+pub use text_builder::{Text, TextBuilder};
 pub use shaping::shape_arabic_text;
 pub use bidi::apply_bidi;
 
@@ -1,16 +1,260 @@
-use crate::layer::Layer;
-use crate::text::TextStyle;
+use crate::layer::{Layer, BlendMode};
+use crate::text::{FontManager, TextStyle};
+use crate::color::Color;
 use crate::geometry::Point;
-use crate::error::Result;
-use cosmic_text::{Buffer, Attrs, Family, Weight, Style as CosmicStyle, Shaping, SwashCache, CacheKey, SubpixelBin, CacheKeyFlags};
+use crate::error::{CloveError, Result};
+use crate::shapes::{Drawable, FillRule, Path2D, Shadow, Shape, StrokeStyle, Style};
+use crate::transform::TransformOps;
+use cosmic_text::{Buffer, Attrs, Family, LayoutGlyph, Weight, Style as CosmicStyle, Shaping, SwashCache, CacheKey, SubpixelBin, CacheKeyFlags};
+use std::sync::{Arc, Mutex};
+use swash::zeno::Command;
 use tiny_skia::Pixmap;
 
+/// Text value that can be stored, measured and drawn on several layers
+///
+/// Drawing uses the target layer's fonts. Outlines and bounds need fonts of
+/// their own, set with `fonts`; without them the outline is empty. A `Style`
+/// fill paints the glyphs, so without one only the stroke and shadow are drawn.
+#[derive(Clone)]
+pub struct Text {
+    pub text: String,
+    pub style: TextStyle,
+    pub position: Point,
+    fonts: Option<Arc<Mutex<FontManager>>>,
+}
+
+impl Text {
+    pub fn new(text: &str, style: TextStyle, position: Point) -> Self {
+        Self {
+            text: text.to_string(),
+            style,
+            position,
+            fonts: None,
+        }
+    }
+    
+    /// Fonts used to build the outline for `to_path` and `bounds`
+    pub fn fonts(mut self, fonts: Arc<Mutex<FontManager>>) -> Self {
+        self.fonts = Some(fonts);
+        self
+    }
+}
+
+impl std::fmt::Debug for Text {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Text")
+            .field("text", &self.text)
+            .field("style", &self.style)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Shape for Text {
+    /// Glyph outlines where `draw_on` puts them, laid out without a page or layer width
+    fn to_path(&self) -> Path2D {
+        let Some(mut fonts) = self.fonts.as_ref().and_then(|fonts| fonts.lock().ok()) else {
+            return Path2D::new();
+        };
+        ShapedText::new(&mut fonts, &self.text, &self.style, self.position, None, None)
+            .map(|shaped| shaped.outline(&mut fonts))
+            .unwrap_or_default()
+    }
+}
+
+impl Drawable for Text {
+    fn draw_on(&self, layer: &mut Layer, style: &Style) -> Result<()> {
+        let mut builder = TextBuilder::new(layer, &self.text, self.style.clone(), self.position)
+            .opacity(style.opacity)
+            .blend_mode(style.blend_mode)
+            .stroke_style(style.stroke_style.clone());
+        match &style.fill {
+            Some(color) => builder = builder.color(color.clone()),
+            None => builder.filled = false,
+        }
+        builder.stroke = style.stroke.clone();
+        builder.shadow = style.shadow.clone();
+        builder.transform = style.transform.clone();
+        builder.draw()?;
+        Ok(())
+    }
+}
+
+/// Text shaped by cosmic-text and placed in layer coordinates
+struct ShapedText {
+    buffer: Buffer,
+    weight: Weight,
+    /// Left edge of the glyphs in the buffer, moved onto `origin`
+    min_x: f32,
+    ascent: f32,
+    line_height: f32,
+    /// Top left of the text box, on whole pixels
+    origin: Point,
+    width: f32,
+    height: f32,
+}
+
+impl ShapedText {
+    /// Shape `text`, wrapping and aligning within `page_width` or `layer_width` when the style asks for them
+    fn new(fonts: &mut FontManager, text: &str, style: &TextStyle, position: Point, page_width: Option<f32>, layer_width: Option<f32>) -> Result<Self> {
+        let font_family = fonts.get_font_family(&style.font_family)
+            .ok_or_else(|| CloveError::InvalidState(format!("Font '{}' not found", style.font_family)))?
+            .clone();
+        
+        // Calculate buffer size based on TextWidth
+        let buffer_width = match style.width {
+            crate::text::TextWidth::None => None,
+            crate::text::TextWidth::Max(w) => Some(w),
+            crate::text::TextWidth::FullPage => page_width,
+            crate::text::TextWidth::Layer => layer_width,
+        };
+        
+        let line_count = text.matches('\n').count() + 1;
+        let ascent = fonts.get_text_height(&style.font_family, style.font_size)
+            .unwrap_or(style.font_size);
+        let estimated_height = (line_count as f32 * style.line_height * style.font_size).max(ascent * 2.0);
+        
+        // Create buffer for text rendering
+        let mut buffer = Buffer::new(
+            fonts.font_system_mut(),
+            cosmic_text::Metrics::new(style.font_size, style.line_height)
+        );
+        
+        buffer.set_size(
+            fonts.font_system_mut(),
+            buffer_width,
+            Some(estimated_height.max(1000.0)),
+        );
+        
+        // Set text attributes
+        let weight = match style.font_weight {
+            crate::text::FontWeight::Normal => Weight::NORMAL,
+            crate::text::FontWeight::Bold => Weight::BOLD,
+            crate::text::FontWeight::Light => Weight::LIGHT,
+        };
+        let attrs = Attrs::new()
+            .family(Family::Name(&font_family))
+            .weight(weight)
+            .style(match style.font_style {
+                crate::text::FontStyle::Normal => CosmicStyle::Normal,
+                crate::text::FontStyle::Italic => CosmicStyle::Italic,
+            })
+            .stretch(cosmic_text::Stretch::Normal);
+        
+        // Advanced shaping handles both LTR and RTL (Arabic, Hebrew) text
+        buffer.set_text(fonts.font_system_mut(), text, &attrs, Shaping::Advanced, None);
+        buffer.shape_until_scroll(fonts.font_system_mut(), false);
+        
+        let line_height = style.line_height * style.font_size;
+        
+        // Calculate text bounds - use glyph positions relative to buffer (without position)
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        let mut line_total = 0;
+        for run in buffer.layout_runs() {
+            for glyph in run.glyphs.iter() {
+                min_x = min_x.min(glyph.x);
+                max_x = max_x.max(glyph.x + glyph.w);
+            }
+            line_total += 1;
+        }
+        
+        // Ensure valid bounds for width
+        if min_x == f32::MAX {
+            min_x = 0.0;
+        }
+        if max_x == f32::MIN {
+            max_x = 0.0;
+        }
+        
+        // Height = baseline of the last line + ascent for the last line itself
+        let height = if line_total == 0 {
+            ascent
+        } else {
+            ascent + ((line_total - 1) as f32 * line_height) + ascent
+        };
+        let width = (max_x - min_x).max(1.0);
+        
+        // Align within the wrap width, or the text's own width when unwrapped
+        let container_width = buffer_width.unwrap_or(width);
+        let x = match style.align {
+            crate::text::TextAlign::Left => position.x,
+            crate::text::TextAlign::Center => position.x + (container_width - width) / 2.0,
+            crate::text::TextAlign::Right => position.x + container_width - width,
+        };
+        
+        Ok(Self {
+            buffer,
+            weight,
+            min_x,
+            ascent,
+            line_height,
+            origin: Point::new(x.max(0.0).trunc(), position.y.max(0.0).trunc()),
+            width,
+            height: height.max(1.0),
+        })
+    }
+    
+    /// Glyph runs with the baseline of each line, relative to the text box
+    fn lines(&self) -> impl Iterator<Item = (f32, &[LayoutGlyph])> + '_ {
+        self.buffer.layout_runs()
+            .enumerate()
+            .map(|(line_index, run)| (self.ascent + line_index as f32 * self.line_height, run.glyphs))
+    }
+    
+    fn cache_key(&self, glyph: &LayoutGlyph) -> CacheKey {
+        CacheKey {
+            font_id: glyph.font_id,
+            glyph_id: glyph.glyph_id,
+            font_size_bits: glyph.font_size.to_bits(),
+            x_bin: SubpixelBin::new(glyph.x).1,
+            y_bin: SubpixelBin::new(glyph.y).1,
+            flags: CacheKeyFlags::empty(),
+            font_weight: self.weight,
+        }
+    }
+    
+    /// Glyph outlines in layer coordinates, before transforms
+    fn outline(&self, fonts: &mut FontManager) -> Path2D {
+        let mut swash_cache = SwashCache::new();
+        let mut path = Path2D::new();
+        for (baseline, glyphs) in self.lines() {
+            for glyph in glyphs {
+                let Some(commands) = swash_cache.get_outline_commands(fonts.font_system_mut(), self.cache_key(glyph)) else {
+                    continue;
+                };
+                // Outlines are y-up around the glyph origin
+                let x = self.origin.x + glyph.x - self.min_x;
+                let y = self.origin.y + baseline + glyph.y;
+                for command in commands {
+                    path = match *command {
+                        Command::MoveTo(p) => path.move_to(x + p.x, y - p.y),
+                        Command::LineTo(p) => path.line_to(x + p.x, y - p.y),
+                        Command::QuadTo(c, p) => path.quad_to(x + c.x, y - c.y, x + p.x, y - p.y),
+                        Command::CurveTo(c1, c2, p) => path.curve_to(x + c1.x, y - c1.y, x + c2.x, y - c2.y, x + p.x, y - p.y),
+                        Command::Close => path.close_path(),
+                    };
+                }
+            }
+        }
+        path
+    }
+}
+
 /// Text builder for drawing text
 pub struct TextBuilder<'a> {
     layer: &'a mut Layer,
     text: String,
     style: TextStyle,
     position: Point,
+    /// Paint the glyphs with the style color, off for a `Text` drawn without a fill
+    filled: bool,
+    stroke: Option<(Color, f32)>,
+    stroke_style: StrokeStyle,
+    opacity: f32,
+    blend_mode: BlendMode,
+    shadow: Option<Shadow>,
+    transform: TransformOps,
 }
 
 impl<'a> TextBuilder<'a> {
@@ -20,6 +264,13 @@ impl<'a> TextBuilder<'a> {
             text: text.to_string(),
             style,
             position,
+            filled: true,
+            stroke: None,
+            stroke_style: StrokeStyle::default(),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            shadow: None,
+            transform: TransformOps::default(),
         }
     }
     
@@ -38,7 +289,7 @@ impl<'a> TextBuilder<'a> {
         self
     }
     
-    pub fn color(mut self, color: crate::color::Color) -> Self {
+    pub fn color(mut self, color: Color) -> Self {
         self.style = self.style.color(color);
         self
     }
@@ -63,85 +314,57 @@ impl<'a> TextBuilder<'a> {
         self
     }
     
+    /// Outline the glyphs with a stroke of `width`, drawn over the fill
+    pub fn stroke(mut self, color: Color, width: f32) -> Self {
+        self.stroke = Some((color, width));
+        self
+    }
+    
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+    
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    
+    pub fn shadow(mut self, color: Color, offset_x: f32, offset_y: f32, blur: f32) -> Self {
+        self.shadow = Some(Shadow::new(color, offset_x, offset_y, blur));
+        self
+    }
+    
+    pub fn shadow_style(mut self, shadow: Shadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
+    crate::transform::transform_methods!("text");
+    
     /// Draw text - uses FontManager from Layer
     pub fn draw(self) -> Result<&'a mut Layer> {
         // Get FontManager from layer
         let mut font_manager_guard = self.layer.font_manager()
             .ok_or_else(|| {
-                crate::error::CloveError::InvalidState("FontManager not set on Canvas".to_string())
+                CloveError::InvalidState("FontManager not set on Canvas".to_string())
             })?;
         
-        let font_family = font_manager_guard.get_font_family(&self.style.font_family)
-            .ok_or_else(|| {
-                crate::error::CloveError::InvalidState(
-                    format!("Font '{}' not found", self.style.font_family)
-                )
-            })?
-            .clone();
-        
-        // Calculate buffer size based on TextWidth
-        let (width, _height) = self.layer.dimensions();
-        let buffer_width = match self.style.width {
-            crate::text::TextWidth::None => None,
-            crate::text::TextWidth::Max(w) => Some(w),
-            crate::text::TextWidth::FullPage => Some(width as f32),
-            crate::text::TextWidth::Layer => Some(self.layer.width.unwrap_or(width) as f32),
-        };
-        
-        let line_count = self.text.matches('\n').count() + 1;
-        let text_ascent = font_manager_guard.get_text_height(&self.style.font_family, self.style.font_size)
-            .unwrap_or(self.style.font_size);
-        let estimated_height = (line_count as f32 * self.style.line_height * self.style.font_size).max(text_ascent * 2.0);
-        
-        // Create buffer for text rendering
-        let mut buffer = Buffer::new(
-            font_manager_guard.font_system_mut(),
-            cosmic_text::Metrics::new(self.style.font_size, self.style.line_height)
-        );
-        
-        buffer.set_size(
-            font_manager_guard.font_system_mut(),
-            buffer_width,
-            Some(estimated_height.max(1000.0)),
-        );
-        
-        // Set text attributes
-        let attrs = Attrs::new()
-            .family(Family::Name(&font_family))
-            .weight(match self.style.font_weight {
-                crate::text::FontWeight::Normal => Weight::NORMAL,
-                crate::text::FontWeight::Bold => Weight::BOLD,
-                crate::text::FontWeight::Light => Weight::LIGHT,
-            })
-            .style(match self.style.font_style {
-                crate::text::FontStyle::Normal => CosmicStyle::Normal,
-                crate::text::FontStyle::Italic => CosmicStyle::Italic,
-            })
-            .stretch(cosmic_text::Stretch::Normal);
-        
-        // Detect text direction (simplified - check first character)
-        let is_rtl = self.text.chars().next()
-            .map(|c| {
-                let code = c as u32;
-                code >= 0x0600 && code <= 0x06FF // Arabic Unicode range
-            })
-            .unwrap_or(false);
-        
-        let shaping = if is_rtl {
-            Shaping::Advanced
-        } else {
-            Shaping::Advanced
-        };
-        
-        buffer.set_text(
-            font_manager_guard.font_system_mut(),
+        let (page_width, _height) = self.layer.dimensions();
+        let layer_width = self.layer.width.unwrap_or(page_width);
+        let shaped = ShapedText::new(
+            &mut font_manager_guard,
             &self.text,
-            &attrs,
-            shaping,
-            None,
-        );
-        
-        buffer.shape_until_scroll(font_manager_guard.font_system_mut(), false);
+            &self.style,
+            self.position,
+            Some(page_width as f32),
+            Some(layer_width as f32),
+        )?;
         
         // Get text color
         let rgba = self.style.color.to_rgba();
@@ -153,78 +376,22 @@ impl<'a> TextBuilder<'a> {
         // Create swash cache
         let mut swash_cache = SwashCache::new();
         
-        // Get layout runs
-        let layout_runs: Vec<_> = buffer.layout_runs().collect();
-        let text_ascent = font_manager_guard.get_text_height(&self.style.font_family, self.style.font_size)
-            .unwrap_or(self.style.font_size);
-        let line_height = self.style.line_height * self.style.font_size;
-        
-        // Calculate text bounds - use glyph positions relative to buffer (without self.position)
-        let mut min_x = f32::MAX;
-        let mut max_x = f32::MIN;
-        
-        // Calculate width from glyph positions
-        for run in layout_runs.iter() {
-            for glyph in run.glyphs.iter() {
-                let x = glyph.x;
-                min_x = min_x.min(x);
-                max_x = max_x.max(x + glyph.w);
-            }
-        }
-        
-        // Ensure valid bounds for width
-        if min_x == f32::MAX {
-            min_x = 0.0;
-        }
-        if max_x == f32::MIN {
-            max_x = 0.0;
-        }
-        
-        // Calculate height based on line count (like old code)
-        let actual_height = if layout_runs.is_empty() {
-            text_ascent
-        } else {
-            let last_line_index = (layout_runs.len() - 1) as f32;
-            // Height = baseline_y of last line + text_ascent (for the last line itself)
-            let baseline_y_last = text_ascent + (last_line_index * line_height);
-            baseline_y_last + text_ascent
-        };
-        
-        let text_width = (max_x - min_x).max(1.0).ceil() as u32;
-        let text_height = actual_height.max(1.0).ceil() as u32;
-        let actual_text_width = (max_x - min_x).max(1.0);
-        
         // Create temporary pixmap for text (starts at 0,0)
-        let mut text_pixmap = Pixmap::new(text_width, text_height)
-            .ok_or_else(|| crate::error::CloveError::InvalidState("Failed to create text pixmap".to_string()))?;
+        let mut text_pixmap = Pixmap::new(shaped.width.ceil() as u32, shaped.height.ceil() as u32)
+            .ok_or_else(|| CloveError::InvalidState("Failed to create text pixmap".to_string()))?;
         text_pixmap.fill(tiny_skia::Color::TRANSPARENT);
         
         // Render glyphs to temporary pixmap (relative to text_pixmap origin 0,0)
-        for (line_index, run) in layout_runs.iter().enumerate() {
-            let baseline_y = text_ascent + (line_index as f32 * line_height);
-            
-            for glyph in run.glyphs.iter() {
+        for (baseline_y, glyphs) in shaped.lines() {
+            for glyph in glyphs {
                 // Position glyph relative to text_pixmap origin (0,0)
-                let x = glyph.x - min_x;
+                let x = glyph.x - shaped.min_x;
                 let y = baseline_y + glyph.y;
                 
-                // Get cache key
-                let (_x_int, x_bin) = SubpixelBin::new(glyph.x);
-                let (_y_int, y_bin) = SubpixelBin::new(glyph.y);
-                let cache_key = CacheKey {
-                    font_id: glyph.font_id,
-                    glyph_id: glyph.glyph_id,
-                    font_size_bits: glyph.font_size.to_bits(),
-                    x_bin,
-                    y_bin,
-                    flags: CacheKeyFlags::empty(),
-                    font_weight: attrs.weight,
-                };
-                
                 // Get glyph image and draw it
                 if let Some(image) = swash_cache.get_image(
                     font_manager_guard.font_system_mut(),
-                    cache_key,
+                    shaped.cache_key(glyph),
                 ).as_ref() {
                     Self::draw_glyph_to_pixmap(
                         &mut text_pixmap,
@@ -240,39 +407,85 @@ impl<'a> TextBuilder<'a> {
             }
         }
         
+        // Gradients and patterns fill the glyph outlines with the shapes' paint, solid colors use the glyph images
+        let solid = self.style.color.is_solid();
+        let outline_fill = (self.filled && !solid).then_some(&self.style.color);
+        let outline = (self.stroke.is_some() || outline_fill.is_some())
+            .then(|| shaped.outline(&mut font_manager_guard));
+        
         // Drop font_manager_guard before borrowing layer mutably
         drop(font_manager_guard);
         
-        // Calculate container width for alignment
-        let (layer_width, _layer_height) = self.layer.dimensions();
-        let container_width = match self.style.width {
-            crate::text::TextWidth::None => actual_text_width,
-            crate::text::TextWidth::Max(w) => w,
-            crate::text::TextWidth::FullPage => layer_width as f32,
-            crate::text::TextWidth::Layer => self.layer.width.unwrap_or(layer_width) as f32,
+        // Get pixmap from layer
+        let mut pixmap = self.layer.get_pixmap_mut()?;
+        
+        let (draw_x, draw_y) = (shaped.origin.x, shaped.origin.y);
+        let center = Point::new(
+            draw_x + text_pixmap.width() as f32 / 2.0,
+            draw_y + text_pixmap.height() as f32 / 2.0,
+        );
+        let transform = tiny_skia::Transform::from_translate(draw_x, draw_y)
+            .post_concat(self.transform.resolve(center));
+        let quality = if self.transform.is_identity() {
+            tiny_skia::FilterQuality::Nearest
+        } else {
+            tiny_skia::FilterQuality::Bilinear
         };
         
-        // Calculate target position based on alignment
-        let target_x = match self.style.align {
-            crate::text::TextAlign::Left => self.position.x,
-            crate::text::TextAlign::Center => self.position.x + (container_width - actual_text_width) / 2.0,
-            crate::text::TextAlign::Right => self.position.x + container_width - actual_text_width,
+        // Glyph coverage in layer coordinates, for the shadow
+        let coverage = match &self.shadow {
+            Some(_) => {
+                let mut glyphs = Pixmap::new(pixmap.width(), pixmap.height())
+                    .ok_or_else(|| CloveError::InvalidState("Failed to create text pixmap".to_string()))?;
+                let paint = tiny_skia::PixmapPaint {
+                    quality,
+                    ..Default::default()
+                };
+                glyphs.draw_pixmap(0, 0, text_pixmap.as_ref(), &paint, transform, None);
+                Some(crate::shapes::shadow::alpha_of(&glyphs))
+            }
+            None => None,
         };
-        let target_y = self.position.y;
         
-        // Get pixmap from layer
-        let mut pixmap = self.layer.get_pixmap_mut()?;
+        if let (Some(shadow), Some(coverage)) = (self.shadow.as_ref().filter(|s| !s.inset), &coverage) {
+            shadow.draw(&mut pixmap, coverage, self.opacity, self.blend_mode);
+        }
         
         // Draw text pixmap onto layer pixmap at calculated position
-        let paint = tiny_skia::PixmapPaint::default();
-        pixmap.draw_pixmap(
-            target_x.max(0.0) as i32,
-            target_y.max(0.0) as i32,
-            text_pixmap.as_ref(),
-            &paint,
-            tiny_skia::Transform::identity(),
-            None,
-        );
+        if self.filled && solid {
+            let paint = tiny_skia::PixmapPaint {
+                opacity: self.opacity,
+                blend_mode: self.blend_mode.to_skia(),
+                quality,
+            };
+            pixmap.draw_pixmap(
+                0,
+                0,
+                text_pixmap.as_ref(),
+                &paint,
+                transform,
+                None,
+            );
+        }
+        
+        // Fill and stroke the glyph outlines with the same transform as the glyph image
+        if let Some(outline) = outline.filter(|o| !o.is_empty()) {
+            let style = crate::shapes::ShapeStyle {
+                fill: outline_fill,
+                fill_rule: FillRule::NonZero,
+                stroke: self.stroke.as_ref(),
+                stroke_style: &self.stroke_style,
+                shadow: None,
+                opacity: self.opacity,
+                blend_mode: self.blend_mode,
+                transform: self.transform.resolve(center),
+            };
+            crate::shapes::fill_and_stroke(&mut pixmap, &outline.to_skia()?, &style);
+        }
+        
+        if let (Some(shadow), Some(coverage)) = (self.shadow.as_ref().filter(|s| s.inset), &coverage) {
+            shadow.draw(&mut pixmap, coverage, self.opacity, self.blend_mode);
+        }
         
         // Update layer content from pixmap
         self.layer.update_from_pixmap(&pixmap)?;
@@ -398,3 +611,90 @@ impl<'a> TextBuilder<'a> {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::LayerId;
+
+    /// Fonts holding the system's DejaVu Sans, which the tests using it are ignored without
+    fn fonts() -> Arc<Mutex<FontManager>> {
+        let mut fonts = FontManager::new();
+        fonts.load("DejaVu Sans", "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf")
+            .expect("DejaVu Sans installed; run with --ignored only where it is");
+        Arc::new(Mutex::new(fonts))
+    }
+
+    fn layer(fonts: &Arc<Mutex<FontManager>>) -> Layer {
+        let mut layer = Layer::new(LayerId::new(), "text", 100, 80);
+        layer.set_font_manager(fonts.clone());
+        layer
+    }
+
+    #[test]
+    #[ignore = "needs DejaVu Sans installed"]
+    fn test_text_outline_matches_drawn_glyphs() {
+        let fonts = fonts();
+        let text = Text::new("H", TextStyle::new("DejaVu Sans", 40.0), Point::new(10.0, 10.0));
+        assert!(text.bounds().is_none());
+
+        let text = text.fonts(fonts.clone());
+        let bounds = text.bounds().unwrap();
+        assert!(bounds.width > 15.0 && bounds.height > 25.0);
+
+        let mut layer = layer(&fonts);
+        text.draw_on(&mut layer, &Style::new().fill(Color::rgba(0, 0, 0, 255))).unwrap();
+        let middle = bounds.y + bounds.height / 2.0;
+        assert_eq!(layer.alpha_at(bounds.x + 2.0, middle), 255);
+        assert_eq!(layer.alpha_at(bounds.x - 2.0, middle), 0);
+        assert_eq!(layer.alpha_at(bounds.x + bounds.width + 2.0, middle), 0);
+        assert_eq!(layer.alpha_at(bounds.x + 2.0, bounds.y - 2.0), 0);
+    }
+
+    #[test]
+    #[ignore = "needs DejaVu Sans installed"]
+    fn test_text_opacity_and_stroke() {
+        let fonts = fonts();
+        let text = Text::new("H", TextStyle::new("DejaVu Sans", 40.0), Point::new(10.0, 10.0)).fonts(fonts.clone());
+        let bounds = text.bounds().unwrap();
+        let (left, middle) = (bounds.x + 2.0, bounds.y + bounds.height / 2.0);
+        let black = Style::new().fill(Color::rgba(0, 0, 0, 255));
+
+        let mut faded = layer(&fonts);
+        text.draw_on(&mut faded, &black.clone().opacity(0.5)).unwrap();
+        assert!(faded.alpha_at(left, middle).abs_diff(128) <= 1);
+
+        let mut stroked = layer(&fonts);
+        text.draw_on(&mut stroked, &black.stroke(Color::rgba(255, 0, 0, 255), 2.0)).unwrap();
+        // The stroke straddles the outline, so it covers the stem's edge but not its middle
+        assert_eq!(stroked.content.get_pixel(bounds.x as u32, middle as u32).0, [255, 0, 0, 255]);
+        assert_eq!(stroked.content.get_pixel(left as u32, middle as u32).0, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    #[ignore = "needs DejaVu Sans installed"]
+    fn test_text_fill_none_and_gradient() {
+        let fonts = fonts();
+        let text = Text::new("H", TextStyle::new("DejaVu Sans", 40.0), Point::new(10.0, 10.0)).fonts(fonts.clone());
+        let bounds = text.bounds().unwrap();
+        let (left, middle) = (bounds.x + 2.0, bounds.y + bounds.height / 2.0);
+
+        let mut outlined = layer(&fonts);
+        text.draw_on(&mut outlined, &Style::new().stroke(Color::rgba(255, 0, 0, 255), 2.0)).unwrap();
+        assert_eq!(outlined.content.get_pixel(bounds.x as u32, middle as u32).0, [255, 0, 0, 255]);
+        assert_eq!(outlined.alpha_at(left, middle), 0);
+
+        let gradient = Color::linear_gradient()
+            .start(bounds.x, 0.0)
+            .end(bounds.x + bounds.width, 0.0)
+            .add_stop(0.0, Color::rgba(255, 0, 0, 255))
+            .add_stop(1.0, Color::rgba(0, 0, 255, 255))
+            .build();
+        let mut painted = layer(&fonts);
+        text.draw_on(&mut painted, &Style::new().fill(gradient)).unwrap();
+        let left_pixel = painted.content.get_pixel(left as u32, middle as u32).0;
+        let right_pixel = painted.content.get_pixel((bounds.x + bounds.width - 2.0) as u32, middle as u32).0;
+        assert!(left_pixel[0] > 200 && left_pixel[3] == 255);
+        assert!(right_pixel[2] > 200 && right_pixel[3] == 255);
+    }
+}
@@ -1,7 +1,7 @@
-use crate::geometry::Rect;
+use crate::geometry::{Point, Rect};
 
 /// Transform struct for geometric transformations
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Transform {
     pub rotation: f32,      // degrees
     pub scale_x: f32,
@@ -60,3 +60,145 @@ impl Default for Transform {
     }
 }
 
+impl Transform {
+    /// Convert to a matrix: scale and rotate around `center`, then translate
+    pub(crate) fn to_skia_about(&self, center: Point) -> tiny_skia::Transform {
+        tiny_skia::Transform::from_translate(-center.x, -center.y)
+            .post_scale(self.scale_x, self.scale_y)
+            .post_rotate(self.rotation)
+            .post_translate(center.x + self.translate_x, center.y + self.translate_y)
+    }
+}
+
+/// One transform step queued on a builder
+#[derive(Debug, Clone)]
+pub(crate) enum TransformOp {
+    /// Degrees around the drawn object's center
+    Rotate(f32),
+    /// Degrees around a fixed point
+    RotateAbout(f32, Point),
+    Scale(f32, f32),
+    /// Skew angles in degrees
+    Skew(f32, f32),
+    Transform(Transform),
+}
+
+/// Transform steps collected by a builder, applied in call order
+///
+/// Rotation, scale and skew act around the center of the object being drawn,
+/// which is only known once the builder draws.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TransformOps {
+    ops: Vec<TransformOp>,
+}
+
+impl TransformOps {
+    pub(crate) fn push(&mut self, op: TransformOp) {
+        self.ops.push(op);
+    }
+
+    pub(crate) fn is_identity(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Resolve to a single matrix around `center`
+    pub(crate) fn resolve(&self, center: Point) -> tiny_skia::Transform {
+        let about = |m: tiny_skia::Transform| {
+            tiny_skia::Transform::from_translate(-center.x, -center.y)
+                .post_concat(m)
+                .post_translate(center.x, center.y)
+        };
+
+        self.ops.iter().fold(tiny_skia::Transform::identity(), |acc, op| {
+            let step = match op {
+                TransformOp::Rotate(degrees) => tiny_skia::Transform::from_rotate_at(*degrees, center.x, center.y),
+                TransformOp::RotateAbout(degrees, pivot) => tiny_skia::Transform::from_rotate_at(*degrees, pivot.x, pivot.y),
+                TransformOp::Scale(sx, sy) => about(tiny_skia::Transform::from_scale(*sx, *sy)),
+                TransformOp::Skew(x_degrees, y_degrees) => about(tiny_skia::Transform::from_skew(
+                    x_degrees.to_radians().tan(),
+                    y_degrees.to_radians().tan(),
+                )),
+                TransformOp::Transform(transform) => transform.to_skia_about(center),
+            };
+            acc.post_concat(step)
+        })
+    }
+
+    /// Resolve around the center of a path's bounds
+    pub(crate) fn resolve_for_path(&self, path: &tiny_skia::Path) -> tiny_skia::Transform {
+        let bounds = path.bounds();
+        self.resolve(Point::new(
+            (bounds.left() + bounds.right()) / 2.0,
+            (bounds.top() + bounds.bottom()) / 2.0,
+        ))
+    }
+}
+
+/// Builder methods that queue transform steps on a `transform: TransformOps` field
+///
+/// `$subject` names what the builder draws in the generated docs.
+macro_rules! transform_methods {
+    ($subject:literal) => {
+        #[doc = concat!("Rotate by `degrees` around the ", $subject, "'s center")]
+        pub fn rotate(mut self, degrees: f32) -> Self {
+            self.transform.push($crate::transform::TransformOp::Rotate(degrees));
+            self
+        }
+
+        /// Rotate by `degrees` around a fixed point
+        pub fn rotate_about(mut self, degrees: f32, pivot: $crate::geometry::Point) -> Self {
+            self.transform.push($crate::transform::TransformOp::RotateAbout(degrees, pivot));
+            self
+        }
+
+        #[doc = concat!("Scale around the ", $subject, "'s center")]
+        pub fn scale(mut self, sx: f32, sy: f32) -> Self {
+            self.transform.push($crate::transform::TransformOp::Scale(sx, sy));
+            self
+        }
+
+        #[doc = concat!("Skew by angles in degrees around the ", $subject, "'s center")]
+        pub fn skew(mut self, x_degrees: f32, y_degrees: f32) -> Self {
+            self.transform.push($crate::transform::TransformOp::Skew(x_degrees, y_degrees));
+            self
+        }
+
+        #[doc = concat!("Apply a `Transform`, scaling and rotating around the ", $subject, "'s center")]
+        pub fn transform(mut self, transform: $crate::transform::Transform) -> Self {
+            self.transform.push($crate::transform::TransformOp::Transform(transform));
+            self
+        }
+    };
+}
+
+pub(crate) use transform_methods;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapped(transform: tiny_skia::Transform, x: f32, y: f32) -> (f32, f32) {
+        let mut point = tiny_skia::Point::from_xy(x, y);
+        transform.map_point(&mut point);
+        ((point.x * 1000.0).round() / 1000.0, (point.y * 1000.0).round() / 1000.0)
+    }
+
+    #[test]
+    fn test_ops_act_around_center_in_call_order() {
+        let mut ops = TransformOps::default();
+        ops.push(TransformOp::Scale(2.0, 2.0));
+        ops.push(TransformOp::Rotate(90.0));
+        let transform = ops.resolve(Point::new(10.0, 10.0));
+
+        assert_eq!(mapped(transform, 10.0, 10.0), (10.0, 10.0));
+        // (11, 10) scales to (12, 10), then rotates a quarter turn clockwise to (10, 12)
+        assert_eq!(mapped(transform, 11.0, 10.0), (10.0, 12.0));
+    }
+
+    #[test]
+    fn test_rotate_about_uses_pivot() {
+        let mut ops = TransformOps::default();
+        ops.push(TransformOp::RotateAbout(180.0, Point::new(0.0, 0.0)));
+        assert_eq!(mapped(ops.resolve(Point::new(50.0, 50.0)), 1.0, 2.0), (-1.0, -2.0));
+    }
+}